@@ -0,0 +1,46 @@
+use std::{hint::black_box, io::Cursor};
+
+use bevy::prelude::*;
+use bevy_replicon::core::entity_serde;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const ENTITIES: u32 = 100;
+
+fn entity_serde(c: &mut Criterion) {
+    let entities: Vec<_> = (10_000..10_000 + ENTITIES).map(Entity::from_raw).collect();
+
+    c.bench_function("100 entities, separate serialization", |b| {
+        b.iter(|| {
+            let mut message = Vec::new();
+            entity_serde::serialize_entity(&mut message, Entity::from_raw(ENTITIES)).unwrap();
+            for &entity in &entities {
+                entity_serde::serialize_entity(&mut message, entity).unwrap();
+            }
+
+            let mut cursor = Cursor::new(&*message);
+            let len = entity_serde::deserialize_entity(&mut cursor)
+                .unwrap()
+                .index();
+            let deserialized: Vec<_> = (0..len)
+                .map(|_| entity_serde::deserialize_entity(&mut cursor).unwrap())
+                .collect();
+
+            black_box((message.len(), deserialized))
+        })
+    });
+
+    c.bench_function("100 entities, list serialization", |b| {
+        b.iter(|| {
+            let mut message = Vec::new();
+            entity_serde::serialize_entity_list(&mut message, &entities).unwrap();
+
+            let mut cursor = Cursor::new(&*message);
+            let deserialized = entity_serde::deserialize_entity_list(&mut cursor).unwrap();
+
+            black_box((message.len(), deserialized))
+        })
+    });
+}
+
+criterion_group!(entity_serde_benches, entity_serde);
+criterion_main!(entity_serde_benches);
@@ -0,0 +1,140 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::{Deserialize, Serialize};
+
+const ENTITIES: usize = 1000;
+
+#[derive(Clone, Copy, Component, Default, Deserialize, Serialize)]
+struct DummyComponent<const N: usize>(usize);
+
+/// 20 components split into 2 tuples since [`Bundle`] is implemented only for tuples up to 15 elements.
+type DummyBundle = (
+    (
+        DummyComponent<0>,
+        DummyComponent<1>,
+        DummyComponent<2>,
+        DummyComponent<3>,
+        DummyComponent<4>,
+        DummyComponent<5>,
+        DummyComponent<6>,
+        DummyComponent<7>,
+        DummyComponent<8>,
+        DummyComponent<9>,
+    ),
+    (
+        DummyComponent<10>,
+        DummyComponent<11>,
+        DummyComponent<12>,
+        DummyComponent<13>,
+        DummyComponent<14>,
+        DummyComponent<15>,
+        DummyComponent<16>,
+        DummyComponent<17>,
+        DummyComponent<18>,
+        DummyComponent<19>,
+    ),
+);
+
+macro_rules! for_each_component {
+    ($f:ident) => {
+        $f!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19);
+    };
+}
+
+fn insertion(c: &mut Criterion) {
+    c.bench_function("20 components, separate inserts", |b| {
+        b.iter_custom(|iter| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iter {
+                let mut world = World::new();
+                let entities: Vec<_> = (0..ENTITIES).map(|_| world.spawn_empty().id()).collect();
+
+                let instant = Instant::now();
+                for &entity in &entities {
+                    let mut entity = world.entity_mut(entity);
+                    macro_rules! insert {
+                        ($($n:literal),*) => {
+                            $(entity.insert(DummyComponent::<$n>::default());)*
+                        };
+                    }
+                    for_each_component!(insert);
+                }
+                elapsed += instant.elapsed();
+            }
+
+            elapsed
+        })
+    });
+
+    c.bench_function("20 components, batch insert", |b| {
+        b.iter_custom(|iter| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iter {
+                let mut world = World::new();
+                let entities: Vec<_> = (0..ENTITIES).map(|_| world.spawn_empty().id()).collect();
+
+                let instant = Instant::now();
+                for &entity in &entities {
+                    world.entity_mut(entity).insert(DummyBundle::default());
+                }
+                elapsed += instant.elapsed();
+            }
+
+            elapsed
+        })
+    });
+
+    c.bench_function("20 components, changes receive", |b| {
+        b.iter_custom(|iter| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iter {
+                let mut server_app = create_app();
+                let mut client_app = create_app();
+
+                server_app.connect_client(&mut client_app);
+
+                server_app
+                    .world_mut()
+                    .spawn_batch(vec![(Replicated, DummyBundle::default()); ENTITIES]);
+
+                server_app.update();
+                server_app.exchange_with_client(&mut client_app);
+
+                let instant = Instant::now();
+                client_app.update();
+                elapsed += instant.elapsed();
+
+                let mut replicated = client_app.world_mut().query::<&Replicated>();
+                assert_eq!(replicated.iter(client_app.world()).count(), ENTITIES);
+            }
+
+            elapsed
+        })
+    });
+}
+
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ));
+
+    macro_rules! replicate {
+        ($($n:literal),*) => {
+            $(app.replicate::<DummyComponent<$n>>();)*
+        };
+    }
+    for_each_component!(replicate);
+
+    app
+}
+
+criterion_group!(insertion_benches, insertion);
+criterion_main!(insertion_benches);
@@ -4,12 +4,25 @@ pub mod diagnostics;
 pub mod event;
 pub mod server_mutate_ticks;
 
-use std::{io::Cursor, mem};
+use std::{
+    io::{self, Cursor},
+    mem,
+    time::Duration,
+};
 
-use bevy::{ecs::world::CommandQueue, prelude::*};
+use bevy::{
+    ecs::{
+        component::ComponentId,
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::CommandQueue,
+    },
+    prelude::*,
+    utils::HashMap,
+};
 use bincode::{DefaultOptions, Options};
 use bytes::Bytes;
 use integer_encoding::{FixedIntReader, VarIntReader};
+use smallvec::SmallVec;
 
 use crate::core::{
     channels::{ReplicationChannel, RepliconChannels},
@@ -17,36 +30,155 @@ use crate::core::{
     entity_serde,
     replication::{
         command_markers::{CommandMarkers, EntityMarkers},
-        deferred_entity::DeferredEntity,
+        deferred_entity::{DeferredChanges, DeferredEntity},
+        replicated_resources::{ReceivedResources, ReplicatedResources},
         replication_registry::{
             ctx::{DespawnCtx, RemoveCtx, WriteCtx},
+            delta_fns::DeltaBaseCache,
             ReplicationRegistry,
         },
         track_mutate_messages::TrackMutateMessages,
         update_message_flags::UpdateMessageFlags,
-        Replicated,
+        ClientSnapshotRequest, Replicated,
     },
     replicon_client::RepliconClient,
     replicon_tick::RepliconTick,
     server_entity_map::ServerEntityMap,
 };
-use confirm_history::{ConfirmHistory, EntityReplicated};
+use confirm_history::{ConfirmHistory, EntityReplicated, ReplicatedSource};
 use server_mutate_ticks::{MutateTickReceived, ServerMutateTicks};
 
 /// Client functionality and replication receiving.
 ///
 /// Can be disabled for server-only apps.
-pub struct ClientPlugin;
+#[derive(Default)]
+pub struct ClientPlugin {
+    /// Populates [`EntityReplicated::source`] to distinguish updates from mutations.
+    ///
+    /// Useful for rollback systems since update messages are reliable while mutate messages are not.
+    ///
+    /// By default set to `false`.
+    pub detailed_replicated_events: bool,
+
+    /// Populates [`MutateTickReceived::entities_confirmed`].
+    ///
+    /// Has effect only if [`TrackAppExt::track_mutate_messages`](crate::core::replication::track_mutate_messages::TrackAppExt::track_mutate_messages)
+    /// was called. Disabled by default because hashing all received entities has a cost.
+    ///
+    /// By default set to `false`.
+    pub track_confirmed_entities: bool,
+
+    /// Preserves replication state across reconnects.
+    ///
+    /// If enabled, [`ClientSet::Reset`] will be disabled and the client won't be reset on disconnect.
+    /// Useful for seamless server migration or reconnecting with state repair.
+    ///
+    /// The typical workflow is:
+    /// 1. The client disconnects, [`ServerEntityMap`], [`ServerUpdateTick`] and [`BufferedMutations`] are preserved.
+    /// 2. The client reconnects and repairs its state, for example by matching preserved entities with the ones
+    ///    received from the server.
+    /// 3. If repair is not possible, the state should be cleaned up before receiving replication by adding
+    ///    [`ClientPlugin::reset_manually`] or by calling [`BufferedMutations::clear`], [`ServerEntityMap::clear`]
+    ///    and resetting [`ServerUpdateTick`] to its default.
+    ///
+    /// By default set to `false`.
+    pub preserve_state_on_disconnect: bool,
+
+    /// Order in which received update messages are applied.
+    ///
+    /// By default set to [`ReceiveOrder::Arrival`].
+    pub replication_receive_order: ReceiveOrder,
+
+    /// Response to mutations that are too old to be stored in [`ConfirmHistory`].
+    ///
+    /// Such mutations can arrive only for entities with a marker that requires history
+    /// (see [`MarkerConfig::need_history`](crate::core::replication::command_markers::MarkerConfig::need_history))
+    /// and are always discarded.
+    ///
+    /// By default set to [`DropPolicy::Silent`].
+    pub mutation_drop_policy: DropPolicy,
+
+    /// Maximum time a mutate message can wait in [`BufferedMutations`] for its update message.
+    ///
+    /// On lossy connections an update message may never arrive, so buffered mutations
+    /// would accumulate without bound. After the timeout they are discarded and
+    /// [`MutationEvicted`] is sent for each known entity from the message.
+    /// Buffered messages are acknowledged only after they are applied, so the server will keep
+    /// resending the discarded mutations until newer ones are acknowledged.
+    /// If [`Self::mutation_drop_policy`] is [`DropPolicy::RequestResync`], the eviction also requests
+    /// the entire state from the server.
+    ///
+    /// By default set to [`None`], which means mutations wait indefinitely.
+    pub mutation_buffer_timeout: Option<Duration>,
+
+    /// Maximum number of mutate messages that can be stored in [`BufferedMutations`].
+    ///
+    /// If the server sends mutations faster than update messages arrive, the buffer may
+    /// grow indefinitely. When the limit is exceeded, the message with the oldest tick
+    /// is discarded and [`BufferedMutationsOverflow`] is sent.
+    /// Like with [`Self::mutation_buffer_timeout`], the discarded message won't be acknowledged,
+    /// so the server will keep resending its mutations until newer ones are acknowledged.
+    /// If [`Self::mutation_drop_policy`] is [`DropPolicy::RequestResync`], the overflow also requests
+    /// the entire state from the server.
+    ///
+    /// By default set to [`None`], which means the buffer is unbounded.
+    pub max_buffered_mutations: Option<usize>,
+
+    /// Applies received replication in [`FixedPreUpdate`] instead of [`PreUpdate`].
+    ///
+    /// Useful for games that run their logic in [`FixedUpdate`] to avoid applying
+    /// replicated values in the middle of fixed steps.
+    ///
+    /// All built-in systems from [`ClientSet::Receive`], including server events and resources, are moved
+    /// together with [`ClientSet::BeforeReceive`], [`ClientSet::AfterReceive`] and [`ClientSet::SyncHierarchy`].
+    /// [`ClientSet::ReceivePackets`], [`ClientSet::Reset`] and [`ClientSet::Diagnostics`] stay in [`PreUpdate`]
+    /// since packets still arrive every frame.
+    /// Messages received during frames without fixed steps will be applied on the next step.
+    ///
+    /// Replicated resources should be registered after this plugin is added
+    /// to have their receive systems scheduled in [`FixedPreUpdate`].
+    ///
+    /// By default set to `false`.
+    pub apply_replication_in_fixed_update: bool,
+}
 
 impl Plugin for ClientPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<RepliconClient>()
-            .init_resource::<ServerEntityMap>()
-            .init_resource::<ServerUpdateTick>()
-            .init_resource::<BufferedMutations>()
-            .add_event::<EntityReplicated>()
-            .add_event::<MutateTickReceived>()
-            .configure_sets(
+        app.insert_resource(ReceiveSettings {
+            fixed_update: self.apply_replication_in_fixed_update,
+            detailed_events: self.detailed_replicated_events,
+            track_confirmed_entities: self.track_confirmed_entities,
+            receive_order: self.replication_receive_order,
+            drop_policy: self.mutation_drop_policy,
+            mutation_buffer_timeout: self.mutation_buffer_timeout,
+        })
+        .init_resource::<RepliconClient>()
+        .init_resource::<ServerEntityMap>()
+        .init_resource::<ServerUpdateTick>()
+        .insert_resource(BufferedMutations::new(self.max_buffered_mutations))
+        .init_resource::<DeltaBaseCache>()
+        .init_resource::<ServerUpdateMetadata>()
+        .init_resource::<ReceivedResources>()
+        .add_event::<EntityReplicated>()
+        .add_event::<MutateTickReceived>()
+        .add_event::<MutationEvicted>()
+        .add_event::<BufferedMutationsOverflow>()
+        .configure_sets(
+            PostUpdate,
+            (ClientSet::Send, ClientSet::SendPackets).chain(),
+        )
+        .add_systems(Startup, Self::setup_channels);
+
+        app.add_systems(
+            Self::receive_schedule(app.world()),
+            Self::receive_replication
+                .map(Result::unwrap)
+                .in_set(ClientSet::Receive)
+                .run_if(client_connected),
+        );
+
+        if self.apply_replication_in_fixed_update {
+            app.configure_sets(
                 PreUpdate,
                 (
                     ClientSet::ReceivePackets,
@@ -54,44 +186,166 @@ impl Plugin for ClientPlugin {
                         ClientSet::ResetEvents.run_if(client_just_connected),
                         ClientSet::Reset.run_if(client_just_disconnected),
                     ),
-                    ClientSet::Receive,
-                    (ClientSet::Diagnostics, ClientSet::SyncHierarchy),
+                    ClientSet::Diagnostics,
                 )
                     .chain(),
             )
             .configure_sets(
-                PostUpdate,
-                (ClientSet::Send, ClientSet::SendPackets).chain(),
-            )
-            .add_systems(Startup, Self::setup_channels)
-            .add_systems(
+                FixedPreUpdate,
+                (
+                    ClientSet::BeforeReceive,
+                    ClientSet::Receive,
+                    ClientSet::AfterReceive,
+                    ClientSet::SyncHierarchy,
+                )
+                    .chain(),
+            );
+        } else {
+            app.configure_sets(
                 PreUpdate,
-                Self::receive_replication
-                    .map(Result::unwrap)
-                    .in_set(ClientSet::Receive)
-                    .run_if(client_connected),
-            )
-            .add_systems(PreUpdate, Self::reset.in_set(ClientSet::Reset));
+                (
+                    ClientSet::ReceivePackets,
+                    (
+                        ClientSet::ResetEvents.run_if(client_just_connected),
+                        ClientSet::Reset.run_if(client_just_disconnected),
+                    ),
+                    ClientSet::BeforeReceive,
+                    ClientSet::Receive,
+                    ClientSet::AfterReceive,
+                    (ClientSet::Diagnostics, ClientSet::SyncHierarchy),
+                )
+                    .chain(),
+            );
+        }
+
+        if self.preserve_state_on_disconnect {
+            app.configure_sets(PreUpdate, ClientSet::Reset.run_if(|| false));
+        } else {
+            app.add_systems(PreUpdate, Self::reset.in_set(ClientSet::Reset));
+        }
     }
 
     fn finish(&self, app: &mut App) {
+        let channels_count = app
+            .world()
+            .get_resource::<RepliconChannels>()
+            .map(|channels| channels.server_channels().len())
+            .unwrap_or_default();
+        if channels_count == 0 {
+            warn!(
+                "`ClientPlugin` has no server channels to receive from, \
+                make sure that `RepliconCorePlugin` is added before it"
+            );
+            return;
+        }
+
         if **app.world().resource::<TrackMutateMessages>() {
             app.init_resource::<ServerMutateTicks>();
         }
+
+        if self.apply_replication_in_fixed_update {
+            validate_fixed_update(app);
+        }
+    }
+}
+
+/// Warns if replication applied in [`FixedPreUpdate`] conflicts with the app configuration.
+///
+/// See also [`ClientPlugin::apply_replication_in_fixed_update`].
+fn validate_fixed_update(app: &App) {
+    if !app.world().contains_resource::<Time<Fixed>>() {
+        warn!(
+            "replication is configured to be applied in `FixedPreUpdate`, \
+            but `Time<Fixed>` is missing, make sure that `TimePlugin` is added"
+        );
+        return;
+    }
+
+    #[cfg(feature = "server")]
+    for plugin in app.get_added_plugins::<crate::server::ServerPlugin>() {
+        use crate::server::TickPolicy;
+
+        let fixed_time = app.world().resource::<Time<Fixed>>();
+        let fixed_hz = fixed_time.timestep().as_secs_f64().recip();
+        match plugin.tick_policy {
+            TickPolicy::MaxTickRate(max_tick_rate) if f64::from(max_tick_rate) > fixed_hz => {
+                warn!(
+                    "tick rate {max_tick_rate} is higher than the fixed timestep rate {fixed_hz:.2}, \
+                    multiple replication messages will be applied in a single fixed step"
+                );
+            }
+            TickPolicy::EveryFrame => {
+                warn!(
+                    "server ticks every frame, but replication is applied in `FixedPreUpdate`, \
+                    multiple replication messages may be applied in a single fixed step"
+                );
+            }
+            _ => (),
+        }
     }
 }
 
 impl ClientPlugin {
+    /// Returns the schedule in which [`ClientSet::Receive`] systems should be added.
+    ///
+    /// Depends on [`Self::apply_replication_in_fixed_update`].
+    /// Returns [`PreUpdate`] if the plugin wasn't built yet.
+    pub(crate) fn receive_schedule(world: &World) -> InternedScheduleLabel {
+        match world.get_resource::<ReceiveSettings>() {
+            Some(settings) if settings.fixed_update => FixedPreUpdate.intern(),
+            _ => PreUpdate.intern(),
+        }
+    }
+
+    /// Returns a system that resets the client replication state.
+    ///
+    /// The same system runs in [`ClientSet::Reset`] when [`Self::preserve_state_on_disconnect`] is disabled.
+    /// Can be added manually to clean up the state when the preserved state is no longer needed.
+    ///
+    /// # Examples
+    ///
+    /// Reset the state if it wasn't repaired right after reconnecting:
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::prelude::*;
+    ///
+    /// # let mut app = App::new();
+    /// app.add_plugins(RepliconPlugins.set(ClientPlugin {
+    ///     preserve_state_on_disconnect: true,
+    ///     ..Default::default()
+    /// }))
+    /// .add_systems(
+    ///     PreUpdate,
+    ///     ClientPlugin::reset_manually()
+    ///         .before(ClientSet::Receive)
+    ///         .run_if(client_just_connected)
+    ///         .run_if(|repaired: Res<StateRepaired>| !**repaired),
+    /// );
+    ///
+    /// #[derive(Resource, Deref)]
+    /// struct StateRepaired(bool);
+    /// ```
+    pub fn reset_manually() -> impl System<In = (), Out = ()> {
+        IntoSystem::into_system(Self::reset)
+    }
+
     fn setup_channels(mut client: ResMut<RepliconClient>, channels: Res<RepliconChannels>) {
         client.setup_server_channels(channels.server_channels().len());
     }
 
     /// Receives and applies replication messages from the server.
     ///
-    /// Update messages are sent over the [`ReplicationChannel::Updates`] and are applied first to ensure valid state
+    /// Update messages are sent over the [`ReplicationChannel::Updates`] and are applied before mutations to ensure valid state
     /// for component mutations.
     ///
-    /// Mutate messages are sent over [`ReplicationChannel::Mutations`], which means they may appear
+    /// Resources are sent over the [`ReplicationChannel::Resources`] and, like mutations, are buffered until
+    /// the update message with their update tick is applied. They are applied by systems registered with
+    /// [`AppRuleExt::replicate_resource`](crate::core::replication::replication_rules::AppRuleExt::replicate_resource)
+    /// right after this system.
+    ///
+    /// Mutate messages are sent over [`ReplicationChannel::Mutations`] and other channels from
+    /// [`RepliconChannels::mutation_channel_ids`], which means they may appear
     /// ahead-of or behind update messages from the same server tick. A mutation will only be applied if its
     /// update tick has already appeared in an update message, otherwise it will be buffered while waiting.
     /// Since component mutations can arrive in any order, they will only be applied if they correspond to a more
@@ -99,14 +353,17 @@ impl ClientPlugin {
     ///
     /// Buffered mutate messages are processed last.
     ///
-    /// Acknowledgments for received mutate messages are sent back to the server.
+    /// Acknowledgments for applied mutate messages are sent back to the server.
     ///
     /// See also [`ReplicationMessages`](crate::server::replication_messages::ReplicationMessages).
     pub(super) fn receive_replication(
         world: &mut World,
         mut queue: Local<CommandQueue>,
+        mut changes: Local<DeferredChanges>,
         mut entity_markers: Local<EntityMarkers>,
+        mut update_messages: Local<Vec<Bytes>>,
     ) -> bincode::Result<()> {
+        world.resource_mut::<ServerUpdateMetadata>().0.clear();
         world.resource_scope(|world, mut client: Mut<RepliconClient>| {
             world.resource_scope(|world, mut entity_map: Mut<ServerEntityMap>| {
                 world.resource_scope(|world, mut buffered_mutations: Mut<BufferedMutations>| {
@@ -118,23 +375,40 @@ impl ClientPlugin {
                                         world.remove_resource::<ClientReplicationStats>();
                                     let mut mutate_ticks =
                                         world.remove_resource::<ServerMutateTicks>();
+                                    let settings = *world.resource::<ReceiveSettings>();
+                                    let mut delta_bases =
+                                        mem::take(&mut *world.resource_mut::<DeltaBaseCache>());
                                     let mut params = ReceiveParams {
                                         queue: &mut queue,
+                                        changes: &mut changes,
                                         entity_markers: &mut entity_markers,
                                         entity_map: &mut entity_map,
                                         replicated_events: &mut replicated_events,
                                         mutate_ticks: mutate_ticks.as_mut(),
                                         stats: stats.as_mut(),
+                                        delta_bases: &mut delta_bases,
                                         command_markers: &command_markers,
                                         registry: &registry,
+                                        settings,
+                                        resync_requested: false,
                                     };
 
-                                    apply_replication(
+                                    let result = apply_replication(
                                         world,
                                         &mut params,
                                         &mut client,
                                         &mut buffered_mutations,
-                                    )?;
+                                        &mut update_messages,
+                                    );
+
+                                    let resync_requested = params.resync_requested;
+                                    delta_bases.retain_existing(world);
+                                    *world.resource_mut::<DeltaBaseCache>() = delta_bases;
+                                    result?;
+
+                                    if resync_requested {
+                                        world.send_event(ClientSnapshotRequest);
+                                    }
 
                                     if let Some(stats) = stats {
                                         world.insert_resource(stats);
@@ -157,11 +431,17 @@ impl ClientPlugin {
         mut update_tick: ResMut<ServerUpdateTick>,
         mut entity_map: ResMut<ServerEntityMap>,
         mut buffered_mutations: ResMut<BufferedMutations>,
+        mut delta_bases: ResMut<DeltaBaseCache>,
+        mut metadata: ResMut<ServerUpdateMetadata>,
+        mut received_resources: ResMut<ReceivedResources>,
         stats: Option<ResMut<ClientReplicationStats>>,
     ) {
         *update_tick = Default::default();
         entity_map.clear();
         buffered_mutations.clear();
+        delta_bases.clear();
+        metadata.0.clear();
+        received_resources.clear();
         if let Some(mut stats) = stats {
             *stats = Default::default();
         }
@@ -170,34 +450,90 @@ impl ClientPlugin {
 
 /// Reads all received messages and applies them.
 ///
-/// Sends acknowledgments for mutate messages back.
+/// Sends acknowledgments for applied mutate messages back.
 fn apply_replication(
     world: &mut World,
     params: &mut ReceiveParams,
     client: &mut RepliconClient,
     buffered_mutations: &mut BufferedMutations,
+    update_messages: &mut Vec<Bytes>,
 ) -> bincode::Result<()> {
-    for message in client.receive(ReplicationChannel::Updates) {
-        apply_update_message(world, params, &message)?;
+    match params.settings.receive_order {
+        ReceiveOrder::Arrival => {
+            for message in client.receive(ReplicationChannel::Updates) {
+                apply_update_message(world, params, &message)?;
+            }
+        }
+        ReceiveOrder::Sorted => {
+            update_messages.extend(client.receive(ReplicationChannel::Updates));
+            // Compare signed distances from the last update tick since wrapping comparison of ticks is not transitive.
+            // Stale messages will be placed before newer ones and invalid messages will be placed first and fail on apply.
+            let update_tick = **world.resource::<ServerUpdateTick>();
+            update_messages.sort_by_cached_key(|message| {
+                read_message_tick(message)
+                    .ok()
+                    .map(|tick| (tick - update_tick) as i32)
+            });
+            for message in update_messages.drain(..) {
+                apply_update_message(world, params, &message)?;
+            }
+        }
     }
 
+    // Resources may arrive before update messages sent earlier since they use a separate channel,
+    // so they are buffered until the update message with their tick is applied.
+    let update_tick = **world.resource::<ServerUpdateTick>();
+    let registered = world.resource::<ReplicatedResources>().len();
+    world.resource_scope(|_, mut received_resources: Mut<ReceivedResources>| {
+        for message in client.receive(ReplicationChannel::Resources) {
+            received_resources.insert(message)?;
+        }
+        received_resources.read_pending(update_tick, registered)
+    })?;
+
     // Unlike update messages, we read all mutate messages first, sort them by tick
     // in descending order to ensure that the last mutation will be applied first.
     // Since mutate messages manually split by packet size, we apply all messages,
     // but skip outdated data per-entity by checking last received tick for it
     // (unless user requested history via marker).
     let update_tick = *world.resource::<ServerUpdateTick>();
-    let acks_size = mem::size_of::<u16>() * client.received_count(ReplicationChannel::Mutations);
-    if acks_size != 0 {
-        let mut acks = Vec::with_capacity(acks_size);
-        for message in client.receive(ReplicationChannel::Mutations) {
-            let mutate_index = buffer_mutate_message(params, buffered_mutations, message)?;
-            bincode::serialize_into(&mut acks, &mutate_index)?;
+    let elapsed = world
+        .get_resource::<Time>()
+        .map(Time::elapsed)
+        .unwrap_or_default();
+    let mutation_channels: SmallVec<[u8; 4]> = world
+        .resource::<RepliconChannels>()
+        .mutation_channel_ids()
+        .into();
+    for (channel_index, &channel_id) in mutation_channels.iter().enumerate() {
+        for message in client.receive(channel_id) {
+            buffer_mutate_message(
+                world,
+                params,
+                buffered_mutations,
+                message,
+                (channel_index, mutation_channels.len()),
+                elapsed,
+            )?;
         }
+    }
+
+    evict_mutate_messages(world, params, buffered_mutations, elapsed)?;
+
+    let mut acks = Vec::new();
+    apply_mutate_messages(world, params, buffered_mutations, update_tick, &mut acks)?;
+    if !acks.is_empty() {
         client.send(ReplicationChannel::Updates, acks);
     }
 
-    apply_mutate_messages(world, params, buffered_mutations, update_tick)
+    Ok(())
+}
+
+/// Reads the tick of an update message without applying it.
+fn read_message_tick(message: &[u8]) -> bincode::Result<RepliconTick> {
+    let mut cursor = Cursor::new(message);
+    let _flags: u8 = cursor.read_fixedint()?;
+    bincode::deserialize_from(&mut cursor)
 }
 
 /// Reads and applies an update message.
@@ -222,6 +558,7 @@ fn apply_update_message(
     trace!("applying update message for {message_tick:?}");
     world.resource_mut::<ServerUpdateTick>().0 = message_tick;
 
+    let mut entities_changed = 0;
     let last_flag = flags.last();
     for (_, flag) in flags.iter_names() {
         let array_kind = if flag != last_flag {
@@ -252,36 +589,82 @@ fn apply_update_message(
                 let len = apply_array(array_kind, &mut cursor, |cursor| {
                     apply_removals(world, params, cursor, message_tick)
                 })?;
-                if let Some(stats) = &mut params.stats {
-                    stats.entities_changed += len;
-                }
+                entities_changed += len;
+            }
+            UpdateMessageFlags::METADATA => {
+                // Always sized, regardless of the position.
+                let size: usize = cursor.read_varint()?;
+                let data = read_slice(&mut cursor, size)?;
+                let mut metadata = world.resource_mut::<ServerUpdateMetadata>();
+                apply_metadata(&mut metadata, data)?;
             }
             UpdateMessageFlags::CHANGES => {
-                debug_assert_eq!(array_kind, ArrayKind::Dynamic);
                 let len = apply_array(array_kind, &mut cursor, |cursor| {
                     apply_changes(world, params, cursor, message_tick)
                 })?;
-                if let Some(stats) = &mut params.stats {
-                    stats.entities_changed += len;
-                }
+                entities_changed += len;
             }
             _ => unreachable!("iteration should yield only named flags"),
         }
     }
 
+    if let Some(stats) = &mut params.stats {
+        stats.entities_changed += entities_changed;
+    }
+
+    world.trigger(UpdateMessageApplied {
+        tick: message_tick,
+        entities_changed,
+    });
+
+    Ok(())
+}
+
+/// Deserializes key-data pairs from the metadata section of an update message.
+fn apply_metadata(metadata: &mut ServerUpdateMetadata, data: &[u8]) -> bincode::Result<()> {
+    let mut cursor = Cursor::new(data);
+    apply_array(ArrayKind::Dynamic, &mut cursor, |cursor| {
+        let key = cursor.read_varint()?;
+        let size: usize = cursor.read_varint()?;
+        let value = read_slice(cursor, size)?;
+        metadata.0.insert(key, Bytes::copy_from_slice(value));
+
+        Ok(())
+    })?;
+
     Ok(())
 }
 
+/// Reads `size` bytes from the cursor without copying.
+fn read_slice<'a>(cursor: &mut Cursor<&'a [u8]>, size: usize) -> bincode::Result<&'a [u8]> {
+    let start = cursor.position() as usize;
+    let end = start
+        .checked_add(size)
+        .filter(|&end| end <= cursor.get_ref().len())
+        .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+    let data = &cursor.get_ref()[start..end];
+    cursor.set_position(end as u64);
+
+    Ok(data)
+}
+
 /// Reads and buffers mutate message.
 ///
 /// For details see [`replication_messages`](crate::server::replication_messages).
 ///
-/// Returns mutate index to be used for acknowledgment.
+/// The message will be acknowledged only after it's applied. This way the server keeps resending
+/// mutations from discarded messages.
+///
+/// `channel` contains the index of the mutation channel the message was received from
+/// and the number of mutation channels.
 fn buffer_mutate_message(
+    world: &mut World,
     params: &mut ReceiveParams,
     buffered_mutations: &mut BufferedMutations,
     message: Bytes,
-) -> bincode::Result<u16> {
+    channel: (usize, usize),
+    elapsed: Duration,
+) -> bincode::Result<()> {
     let end_pos = message.len();
     let mut cursor = Cursor::new(&*message);
     if let Some(stats) = &mut params.stats {
@@ -297,18 +680,113 @@ fn buffer_mutate_message(
         1
     };
     let mutate_index = cursor.read_varint()?;
+    if !matches_channel(&mut cursor.clone(), channel)? {
+        error_once!(
+            "discarding mutate messages sent over a different mutation channel, \
+            make sure that `ServerPlugin::mutation_channels` is the same on both sides \
+            or call `RepliconChannels::set_mutation_channels` on client-only apps"
+        );
+        return Ok(());
+    }
     trace!("received mutate message for {message_tick:?}");
-    buffered_mutations.insert(BufferedMutate {
+    let dropped = buffered_mutations.insert(BufferedMutate {
         update_tick,
         message_tick,
         messages_count,
+        mutate_index,
         message: message.slice(cursor.position() as usize..),
+        queued_at: elapsed,
+    });
+
+    if let Some(dropped) = dropped {
+        debug!(
+            "dropping mutate message for {:?} because the buffer is full",
+            dropped.message_tick
+        );
+        world.send_event(BufferedMutationsOverflow {
+            dropped_tick: dropped.message_tick,
+        });
+        if params.settings.drop_policy == DropPolicy::RequestResync {
+            params.resync_requested = true;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the first entity in the mutate message belongs to the channel it was received from.
+///
+/// The server assigns entities to mutation channels by their index, so a mismatch means that
+/// the client and the server have a different number of mutation channels.
+/// Always returns `true` for a single channel to avoid reading the entity.
+fn matches_channel(
+    cursor: &mut Cursor<&[u8]>,
+    (channel_index, channels_count): (usize, usize),
+) -> bincode::Result<bool> {
+    if channels_count == 1 {
+        return Ok(true);
+    }
+
+    let server_entity = entity_serde::deserialize_entity(cursor)?;
+    Ok(server_entity.index() as usize % channels_count == channel_index)
+}
+
+/// Removes mutate messages that waited for their update message longer than
+/// [`ClientPlugin::mutation_buffer_timeout`].
+///
+/// Sends [`MutationEvicted`] for each known entity from evicted messages.
+fn evict_mutate_messages(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    buffered_mutations: &mut BufferedMutations,
+    elapsed: Duration,
+) -> bincode::Result<()> {
+    let Some(timeout) = params.settings.mutation_buffer_timeout else {
+        return Ok(());
+    };
+
+    let mut result = Ok(());
+    buffered_mutations.mutations.retain(|mutate| {
+        if elapsed.saturating_sub(mutate.queued_at) <= timeout {
+            return true;
+        }
+
+        debug!(
+            "evicting mutate message for {:?} that waited for {:?} longer than {timeout:?}",
+            mutate.message_tick, mutate.update_tick
+        );
+        if let Err(e) = apply_array(
+            ArrayKind::Dynamic,
+            &mut Cursor::new(&*mutate.message),
+            |cursor| {
+                let server_entity = entity_serde::deserialize_entity(cursor)?;
+                let data_size: usize = cursor.read_varint()?;
+                cursor.set_position(cursor.position() + data_size as u64);
+
+                if let Some(entity) = params.entity_map.get_by_server(server_entity) {
+                    world.send_event(MutationEvicted {
+                        entity,
+                        tick: mutate.message_tick,
+                    });
+                }
+
+                Ok(())
+            },
+        ) {
+            result = Err(e);
+        }
+
+        if params.settings.drop_policy == DropPolicy::RequestResync {
+            params.resync_requested = true;
+        }
+
+        false
     });
 
-    Ok(mutate_index)
+    result
 }
 
-/// Applies mutations from [`BufferedMutations`].
+/// Applies mutations from [`BufferedMutations`] and writes indices of applied messages into `acks`.
 ///
 /// If the mutate message can't be applied yet (because the update message with the
 /// corresponding tick hasn't arrived), it will be kept in the buffer.
@@ -317,13 +795,18 @@ fn apply_mutate_messages(
     params: &mut ReceiveParams,
     buffered_mutations: &mut BufferedMutations,
     update_tick: ServerUpdateTick,
+    acks: &mut Vec<u8>,
 ) -> bincode::Result<()> {
     let mut result = Ok(());
-    buffered_mutations.0.retain(|mutate| {
+    buffered_mutations.mutations.retain(|mutate| {
         if mutate.update_tick > *update_tick {
             return true;
         }
 
+        if let Err(e) = bincode::serialize_into(&mut *acks, &mutate.mutate_index) {
+            result = Err(e);
+        }
+
         trace!("applying mutate message for {:?}", mutate.message_tick);
         let len = apply_array(
             ArrayKind::Dynamic,
@@ -344,6 +827,7 @@ fn apply_mutate_messages(
             if mutate_ticks.confirm(mutate.message_tick, mutate.messages_count) {
                 world.send_event(MutateTickReceived {
                     tick: mutate.message_tick,
+                    entities_confirmed: mutate_ticks.take_entities(mutate.message_tick),
                 });
             }
         }
@@ -364,7 +848,20 @@ fn apply_entity_mapping(
     let client_entity = entity_serde::deserialize_entity(cursor)?;
 
     if let Ok(mut entity) = world.get_entity_mut(client_entity) {
-        debug!("received mapping from {server_entity:?} to {client_entity:?}");
+        match params.entity_map.get_by_server(server_entity) {
+            Some(mapped_entity) if mapped_entity == client_entity => {
+                debug!(
+                    "ignoring already applied mapping from {server_entity:?} to {client_entity:?}"
+                );
+                return Ok(());
+            }
+            Some(mapped_entity) => {
+                warn!("remapping {server_entity:?} from {mapped_entity:?} to {client_entity:?}");
+                params.entity_map.remove_by_server(server_entity);
+            }
+            None => debug!("received mapping from {server_entity:?} to {client_entity:?}"),
+        }
+
         entity.insert(Replicated);
         params.entity_map.insert(server_entity, client_entity);
     } else {
@@ -411,17 +908,19 @@ fn apply_removals(
         .entity_map
         .get_by_server_or_insert(server_entity, || world.spawn(Replicated).id());
 
-    let mut client_entity = DeferredEntity::new(world, client_entity);
+    let entity = client_entity;
+    let mut client_entity = DeferredEntity::new(world, params.changes, client_entity);
     let mut commands = client_entity.commands(params.queue);
     params
         .entity_markers
         .read(params.command_markers, &*client_entity);
 
     confirm_tick(
-        &mut commands,
         &mut client_entity,
         params.replicated_events,
         message_tick,
+        params.settings.detailed_events,
+        Default::default(),
     );
 
     let len = apply_array(ArrayKind::Sized, cursor, |cursor| {
@@ -441,6 +940,7 @@ fn apply_removals(
         stats.components_changed += len;
     }
 
+    params.changes.apply(world, entity);
     params.queue.apply(world);
 
     Ok(())
@@ -459,42 +959,50 @@ fn apply_changes(
         .entity_map
         .get_by_server_or_insert(server_entity, || world.spawn(Replicated).id());
 
-    let mut client_entity = DeferredEntity::new(world, client_entity);
+    let entity = client_entity;
+    let mut client_entity = DeferredEntity::new(world, params.changes, client_entity);
     let mut commands = client_entity.commands(params.queue);
     params
         .entity_markers
         .read(params.command_markers, &*client_entity);
 
-    confirm_tick(
-        &mut commands,
-        &mut client_entity,
-        params.replicated_events,
-        message_tick,
-    );
-
+    let mut changed_components = SmallVec::new();
     let len = apply_array(ArrayKind::Sized, cursor, |cursor| {
         let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
         let (component_id, component_fns, rule_fns) = params.registry.get(fns_id);
         let mut ctx = WriteCtx::new(&mut commands, params.entity_map, component_id, message_tick);
 
         // SAFETY: `rule_fns` and `component_fns` were created for the same type.
-        unsafe {
+        let written = unsafe {
             component_fns.write(
                 &mut ctx,
                 rule_fns,
                 params.entity_markers,
+                params.delta_bases,
                 &mut client_entity,
                 cursor,
-            )?;
+            )?
+        };
+        if written {
+            changed_components.push(component_id);
         }
 
         Ok(())
     })?;
 
+    confirm_tick(
+        &mut client_entity,
+        params.replicated_events,
+        message_tick,
+        params.settings.detailed_events,
+        changed_components,
+    );
+
     if let Some(stats) = &mut params.stats {
         stats.components_changed += len;
     }
 
+    params.changes.apply(world, entity);
     params.queue.apply(world);
 
     Ok(())
@@ -537,21 +1045,22 @@ enum ArrayKind {
 }
 
 fn confirm_tick(
-    commands: &mut Commands,
     entity: &mut DeferredEntity,
     replicated_events: &mut Events<EntityReplicated>,
     tick: RepliconTick,
+    detailed_events: bool,
+    changed_components: SmallVec<[ComponentId; 8]>,
 ) {
     if let Some(mut history) = entity.get_mut::<ConfirmHistory>() {
         history.set_last_tick(tick);
     } else {
-        commands
-            .entity(entity.id())
-            .insert(ConfirmHistory::new(tick));
+        entity.insert(ConfirmHistory::new(tick));
     }
     replicated_events.send(EntityReplicated {
         entity: entity.id(),
         tick,
+        changed_components,
+        source: detailed_events.then_some(ReplicatedSource::Update),
     });
 }
 
@@ -574,7 +1083,8 @@ fn apply_mutations(
         return Ok(());
     };
 
-    let mut client_entity = DeferredEntity::new(world, client_entity);
+    let entity = client_entity;
+    let mut client_entity = DeferredEntity::new(world, params.changes, client_entity);
     let mut commands = client_entity.commands(params.queue);
     params
         .entity_markers
@@ -598,74 +1108,161 @@ fn apply_mutations(
 
         let ago = history.last_tick().get().wrapping_sub(message_tick.get());
         if ago >= u64::BITS {
-            trace!(
-                "discarding {ago} ticks old mutations for client's {:?}",
-                client_entity.id()
-            );
+            match params.settings.drop_policy {
+                DropPolicy::Silent => trace!(
+                    "discarding {ago} ticks old mutations for {message_tick:?} for client's {:?}",
+                    client_entity.id()
+                ),
+                DropPolicy::Warn => warn!(
+                    "discarding {ago} ticks old mutations for {message_tick:?} for client's {:?}",
+                    client_entity.id()
+                ),
+                DropPolicy::Error => error!(
+                    "discarding {ago} ticks old mutations for {message_tick:?} for client's {:?}",
+                    client_entity.id()
+                ),
+                DropPolicy::RequestResync => {
+                    debug!(
+                        "discarding {ago} ticks old mutations for {message_tick:?} for client's {:?} and requesting resync",
+                        client_entity.id()
+                    );
+                    params.resync_requested = true;
+                }
+            }
             cursor.set_position(cursor.position() + data_size as u64);
             return Ok(());
         }
 
         history.set(ago);
     }
-    params.replicated_events.send(EntityReplicated {
-        entity: client_entity.id(),
-        tick: message_tick,
-    });
+
+    if params.settings.track_confirmed_entities {
+        if let Some(mutate_ticks) = &mut params.mutate_ticks {
+            mutate_ticks.add_pending_entity(entity);
+        }
+    }
 
     let end_pos = cursor.position() + data_size as u64;
-    let mut components_count = 0;
+    let mut changed_components = SmallVec::new();
     while cursor.position() < end_pos {
         let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
         let (component_id, component_fns, rule_fns) = params.registry.get(fns_id);
         let mut ctx = WriteCtx::new(&mut commands, params.entity_map, component_id, message_tick);
 
         // SAFETY: `rule_fns` and `component_fns` were created for the same type.
-        unsafe {
+        let written = unsafe {
             if new_tick {
                 component_fns.write(
                     &mut ctx,
                     rule_fns,
                     params.entity_markers,
+                    params.delta_bases,
                     &mut client_entity,
                     cursor,
-                )?;
+                )?
             } else {
                 component_fns.consume_or_write(
                     &mut ctx,
                     rule_fns,
                     params.entity_markers,
                     params.command_markers,
+                    params.delta_bases,
                     &mut client_entity,
                     cursor,
-                )?;
+                )?
             }
-        }
+        };
 
-        components_count += 1;
+        if written {
+            changed_components.push(component_id);
+        }
     }
 
     if let Some(stats) = &mut params.stats {
-        stats.components_changed += components_count;
+        stats.components_changed += changed_components.len();
     }
 
+    params.replicated_events.send(EntityReplicated {
+        entity: client_entity.id(),
+        tick: message_tick,
+        changed_components,
+        source: params
+            .settings
+            .detailed_events
+            .then_some(ReplicatedSource::Mutation),
+    });
+
+    params.changes.apply(world, entity);
     params.queue.apply(world);
 
     Ok(())
 }
 
+/// Receive-related settings from [`ClientPlugin`].
+#[derive(Resource, Clone, Copy)]
+struct ReceiveSettings {
+    fixed_update: bool,
+    detailed_events: bool,
+    track_confirmed_entities: bool,
+    receive_order: ReceiveOrder,
+    drop_policy: DropPolicy,
+    mutation_buffer_timeout: Option<Duration>,
+}
+
+/// Order in which received update messages are applied.
+///
+/// See also [`ClientPlugin::replication_receive_order`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiveOrder {
+    /// Apply messages in the order they were received from the messaging backend.
+    #[default]
+    Arrival,
+    /// Buffer all messages received in a frame and apply them sorted by their server tick.
+    ///
+    /// Useful for messaging backends that don't preserve the order of reliable messages.
+    Sorted,
+}
+
+/// Response to discarded mutations.
+///
+/// See also [`ClientPlugin::mutation_drop_policy`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Discard mutations with only a trace-level log.
+    #[default]
+    Silent,
+    /// Log a warning with the tick and the entity.
+    Warn,
+    /// Log an error with the tick and the entity.
+    Error,
+    /// Send [`ClientSnapshotRequest`] to the server to receive the entire state again.
+    ///
+    /// Has an effect only if [`ServerPlugin::allow_snapshot_requests`](crate::server::ServerPlugin::allow_snapshot_requests)
+    /// is enabled on the server. Sent at most once per frame.
+    ///
+    /// Also sent on eviction of buffered mutations, see [`ClientPlugin::mutation_buffer_timeout`]
+    /// and [`ClientPlugin::max_buffered_mutations`].
+    RequestResync,
+}
+
 /// Borrowed resources from the world and locals.
 ///
 /// To avoid passing a lot of arguments into all receive functions.
 struct ReceiveParams<'a> {
     queue: &'a mut CommandQueue,
+    changes: &'a mut DeferredChanges,
     entity_markers: &'a mut EntityMarkers,
     entity_map: &'a mut ServerEntityMap,
     replicated_events: &'a mut Events<EntityReplicated>,
     mutate_ticks: Option<&'a mut ServerMutateTicks>,
     stats: Option<&'a mut ClientReplicationStats>,
+    delta_bases: &'a mut DeltaBaseCache,
     command_markers: &'a CommandMarkers,
     registry: &'a ReplicationRegistry,
+    settings: ReceiveSettings,
+
+    /// Set when a mutation was discarded with [`DropPolicy::RequestResync`].
+    resync_requested: bool,
 }
 
 /// Set with replication and event systems related to client.
@@ -677,12 +1274,26 @@ pub enum ClientSet {
     ///
     /// Runs in [`PreUpdate`].
     ReceivePackets,
+    /// Systems that run right before replication is applied.
+    ///
+    /// Intended for user systems that need to observe the state before [`ClientSet::Receive`],
+    /// such as snapshotting predicted components to compare them with the received values later.
+    ///
+    /// Runs in [`PreUpdate`].
+    BeforeReceive,
     /// Systems that receive data from [`RepliconClient`].
     ///
     /// Used by `bevy_replicon`.
     ///
     /// Runs in [`PreUpdate`].
     Receive,
+    /// Systems that run right after replication is applied.
+    ///
+    /// Intended for user systems that react to the received state,
+    /// such as detecting divergence from predicted values and scheduling a rollback.
+    ///
+    /// Runs in [`PreUpdate`].
+    AfterReceive,
     /// Systems that populate Bevy's [`Diagnostics`](bevy::diagnostic::Diagnostics).
     ///
     /// Used by `bevy_replicon`.
@@ -729,6 +1340,15 @@ pub enum ClientSet {
     Reset,
 }
 
+/// User-defined metadata received with update messages.
+///
+/// Populated in [`ClientSet::Receive`] from update messages received during the frame and cleared before
+/// receiving new ones. If multiple messages contain the same key, the value from the last applied message is kept.
+///
+/// Sent from the server via [`PendingUpdateMetadata`](crate::server::PendingUpdateMetadata).
+#[derive(Debug, Default, Deref, Resource)]
+pub struct ServerUpdateMetadata(HashMap<u32, Bytes>);
+
 /// Last received tick for update messages from the server.
 ///
 /// In other words, the last [`RepliconTick`] with a removal, insertion, spawn or despawn.
@@ -738,23 +1358,100 @@ pub enum ClientSet {
 #[derive(Clone, Copy, Debug, Default, Deref, Resource)]
 pub struct ServerUpdateTick(RepliconTick);
 
+/// Triggered on client right after an update message is applied.
+///
+/// Unlike reading the world after [`ClientSet::Receive`], observers will see
+/// the state after each individual message, even if multiple messages were received during a frame.
+///
+/// Observers run in the middle of receiving, so [`RepliconClient`], [`ServerEntityMap`],
+/// [`BufferedMutations`] and other resources used by replication are unavailable.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct UpdateMessageApplied {
+    /// Tick of the applied message.
+    pub tick: RepliconTick,
+
+    /// Number of entities that had insertions or removals in this message.
+    pub entities_changed: usize,
+}
+
+/// Sent on client when buffered mutations for an entity are discarded
+/// because their update message didn't arrive in time.
+///
+/// See also [`ClientPlugin::mutation_buffer_timeout`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct MutationEvicted {
+    /// Entity whose mutations were discarded.
+    pub entity: Entity,
+
+    /// Tick of the discarded mutations.
+    pub tick: RepliconTick,
+}
+
+/// Sent on client when a buffered mutate message is discarded because
+/// [`BufferedMutations`] exceeded its capacity.
+///
+/// See also [`ClientPlugin::max_buffered_mutations`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct BufferedMutationsOverflow {
+    /// Tick of the discarded mutate message.
+    pub dropped_tick: RepliconTick,
+}
+
 /// Cached buffered mutate messages, used to synchronize mutations with update messages.
 ///
 /// If [`ClientSet::Reset`] is disabled, then this needs to be cleaned up manually with [`Self::clear`].
 #[derive(Default, Resource)]
-pub struct BufferedMutations(Vec<BufferedMutate>);
+pub struct BufferedMutations {
+    mutations: Vec<BufferedMutate>,
+
+    /// Maximum number of stored messages.
+    ///
+    /// See also [`ClientPlugin::max_buffered_mutations`].
+    max: Option<usize>,
+}
 
 impl BufferedMutations {
+    fn new(max: Option<usize>) -> Self {
+        Self {
+            mutations: Default::default(),
+            max,
+        }
+    }
+
     pub fn clear(&mut self) {
-        self.0.clear();
+        self.mutations.clear();
+    }
+
+    /// Returns the number of buffered mutate messages.
+    pub fn len(&self) -> usize {
+        self.mutations.len()
+    }
+
+    /// Returns `true` if there are no buffered mutate messages.
+    pub fn is_empty(&self) -> bool {
+        self.mutations.is_empty()
+    }
+
+    /// Returns `true` if the number of buffered messages reached [`ClientPlugin::max_buffered_mutations`].
+    pub fn is_full(&self) -> bool {
+        self.max.is_some_and(|max| self.mutations.len() >= max)
     }
 
     /// Inserts a new buffered message, maintaining sorting by their message tick in descending order.
-    fn insert(&mut self, mutation: BufferedMutate) {
+    ///
+    /// If the capacity is exceeded, removes and returns the message with the oldest tick.
+    fn insert(&mut self, mutation: BufferedMutate) -> Option<BufferedMutate> {
         let index = self
-            .0
+            .mutations
             .partition_point(|other_mutation| mutation.message_tick < other_mutation.message_tick);
-        self.0.insert(index, mutation);
+        self.mutations.insert(index, mutation);
+
+        let max = self.max?;
+        if self.mutations.len() > max {
+            self.mutations.pop()
+        } else {
+            None
+        }
     }
 }
 
@@ -773,8 +1470,14 @@ pub(super) struct BufferedMutate {
     /// May not be equal to the number of received messages.
     messages_count: usize,
 
+    /// Index to acknowledge the message after applying.
+    mutate_index: u16,
+
     /// Mutations data.
     message: Bytes,
+
+    /// Value of [`Time::elapsed`] when the message was buffered.
+    queued_at: Duration,
 }
 
 /// Replication stats during message processing.
@@ -1,6 +1,7 @@
 use std::fmt::{self, Debug, Formatter};
 
-use bevy::prelude::*;
+use bevy::{ecs::component::ComponentId, prelude::*};
+use smallvec::SmallVec;
 
 use crate::core::replicon_tick::RepliconTick;
 
@@ -41,6 +42,28 @@ impl ConfirmHistory {
         self.mask
     }
 
+    /// Returns an iterator over confirmed ticks from the mask, from newest to oldest.
+    ///
+    /// Only the most recent 64 ticks since [`Self::last_tick`] are returned,
+    /// even though older ticks are considered received by [`Self::contains`].
+    pub fn confirmed_ticks_iter(&self) -> impl Iterator<Item = RepliconTick> + '_ {
+        (0..u64::BITS)
+            .filter(|&ago| (self.mask >> ago & 1) == 1)
+            .map(|ago| self.last_tick - ago)
+    }
+
+    /// Returns the oldest confirmed tick from the mask.
+    ///
+    /// Like [`Self::confirmed_ticks_iter`], considers only the most recent 64 ticks.
+    pub fn oldest_confirmed_tick(&self) -> Option<RepliconTick> {
+        if self.mask == 0 {
+            return None;
+        }
+
+        let ago = u64::BITS - 1 - self.mask.leading_zeros();
+        Some(self.last_tick - ago)
+    }
+
     /// Returns `true` if this tick is confirmed for an entity.
     ///
     /// All ticks older then 64 ticks since [`Self::last_tick`] are considered received.
@@ -129,13 +152,37 @@ impl ConfirmHistory {
 /// Triggered for an entity when it receives updates for a tick.
 ///
 /// See also [`ConfirmHistory`].
-#[derive(Debug, Event, Clone, Copy)]
+#[derive(Debug, Event, Clone)]
 pub struct EntityReplicated {
     /// Entity that received an update.
     pub entity: Entity,
 
     /// Message tick.
     pub tick: RepliconTick,
+
+    /// Components that were received for the entity in this message.
+    ///
+    /// Empty for updates that contain only removals.
+    pub changed_components: SmallVec<[ComponentId; 8]>,
+
+    /// Kind of message that caused the update.
+    ///
+    /// Populated only if [`ClientPlugin::detailed_replicated_events`](crate::client::ClientPlugin::detailed_replicated_events)
+    /// is enabled.
+    pub source: Option<ReplicatedSource>,
+}
+
+/// Kind of message from which [`EntityReplicated`] originated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicatedSource {
+    /// The entity was changed by an update message.
+    ///
+    /// Update messages are reliable and contain all insertions and removals.
+    Update,
+    /// The entity was changed by a mutate message.
+    ///
+    /// Mutate messages are unreliable and contain only mutated components.
+    Mutation,
 }
 
 #[cfg(test)]
@@ -274,6 +321,27 @@ mod tests {
         assert!(!history.contains(RepliconTick::new(u64::BITS + 2)));
     }
 
+    #[test]
+    fn confirmed_ticks() {
+        let mut history = ConfirmHistory::new(RepliconTick::new(1));
+        history.confirm(RepliconTick::new(u32::MAX));
+        history.confirm(RepliconTick::new(3));
+
+        let ticks: Vec<_> = history.confirmed_ticks_iter().collect();
+        assert_eq!(
+            ticks,
+            [
+                RepliconTick::new(3),
+                RepliconTick::new(1),
+                RepliconTick::new(u32::MAX)
+            ]
+        );
+        assert_eq!(
+            history.oldest_confirmed_tick(),
+            Some(RepliconTick::new(u32::MAX))
+        );
+    }
+
     #[test]
     fn confirm_with_overflow() {
         let mut history = ConfirmHistory::new(RepliconTick::new(u32::MAX));
@@ -101,16 +101,15 @@ impl Plugin for ClientEventPlugin {
             .build_state(app.world_mut())
             .build_system(Self::reset);
 
+        let receive_schedule = ClientPlugin::receive_schedule(app.world());
         app.insert_resource(event_registry)
+            .add_systems(PreUpdate, reset.in_set(ClientSet::ResetEvents))
             .add_systems(
-                PreUpdate,
-                (
-                    reset.in_set(ClientSet::ResetEvents),
-                    receive
-                        .after(ClientPlugin::receive_replication)
-                        .in_set(ClientSet::Receive)
-                        .run_if(client_connected),
-                ),
+                receive_schedule,
+                receive
+                    .after(ClientPlugin::receive_replication)
+                    .in_set(ClientSet::Receive)
+                    .run_if(client_connected),
             )
             .add_systems(
                 PostUpdate,
@@ -1,6 +1,6 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, mem};
 
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
 
 use crate::core::replicon_tick::RepliconTick;
 
@@ -21,6 +21,12 @@ pub struct ServerMutateTicks {
 
     /// The last received server tick with mutation.
     last_tick: RepliconTick,
+
+    /// Entities from the last processed message that will be assigned to its tick on [`Self::confirm`].
+    ///
+    /// Populated only if [`ClientPlugin::track_confirmed_entities`](super::ClientPlugin::track_confirmed_entities)
+    /// is enabled.
+    pending_entities: Vec<Entity>,
 }
 
 impl ServerMutateTicks {
@@ -108,7 +114,7 @@ impl ServerMutateTicks {
             if ago >= len {
                 // If the difference exceeds the size, clear all ticks.
                 self.ticks.clear();
-                self.ticks.resize(u64::BITS as usize, Default::default());
+                self.ticks.resize_with(u64::BITS as usize, Default::default);
             } else {
                 for _ in 0..ago {
                     self.ticks.pop_back();
@@ -117,29 +123,56 @@ impl ServerMutateTicks {
             }
 
             self.last_tick = tick;
-            self.ticks[0].confirm(messages_count)
+            let tick_messages = &mut self.ticks[0];
+            tick_messages
+                .entities
+                .extend(self.pending_entities.drain(..));
+            tick_messages.confirm(messages_count)
         } else {
             let ago = (self.last_tick - tick) as usize;
-            if ago < len {
-                self.ticks[ago].confirm(messages_count)
+            if let Some(tick_messages) = self.ticks.get_mut(ago) {
+                tick_messages
+                    .entities
+                    .extend(self.pending_entities.drain(..));
+                tick_messages.confirm(messages_count)
             } else {
+                self.pending_entities.clear();
                 false
             }
         }
     }
+
+    /// Adds an entity from the currently processed message to be assigned on the next [`Self::confirm`].
+    pub(super) fn add_pending_entity(&mut self, entity: Entity) {
+        self.pending_entities.push(entity);
+    }
+
+    /// Takes all entities collected for a tick.
+    pub(super) fn take_entities(&mut self, tick: RepliconTick) -> EntityHashSet {
+        if tick > self.last_tick {
+            return Default::default();
+        }
+
+        let ago = (self.last_tick - tick) as usize;
+        self.ticks
+            .get_mut(ago)
+            .map(|tick_messages| mem::take(&mut tick_messages.entities))
+            .unwrap_or_default()
+    }
 }
 
 impl Default for ServerMutateTicks {
     fn default() -> Self {
         Self {
-            ticks: VecDeque::from([Default::default(); u64::BITS as usize]),
+            ticks: VecDeque::from_iter((0..u64::BITS).map(|_| Default::default())),
             last_tick: Default::default(),
+            pending_entities: Default::default(),
         }
     }
 }
 
 /// Tracker for mutable messages received for a tick.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 struct TickMessages {
     /// Number of sent messages.
     ///
@@ -148,6 +181,9 @@ struct TickMessages {
 
     /// Number of received messages.
     received: usize,
+
+    /// Entities from all received messages for this tick.
+    entities: EntityHashSet,
 }
 
 impl TickMessages {
@@ -171,10 +207,16 @@ impl TickMessages {
 /// Triggered when all mutate messages are received for a tick.
 ///
 /// See also [`ServerMutateTicks`].
-#[derive(Debug, Event, Clone, Copy)]
+#[derive(Debug, Event, Clone)]
 pub struct MutateTickReceived {
     /// Message(s) tick.
     pub tick: RepliconTick,
+
+    /// Entities from all mutate messages for this tick.
+    ///
+    /// Populated only if [`ClientPlugin::track_confirmed_entities`](super::ClientPlugin::track_confirmed_entities)
+    /// is enabled.
+    pub entities_confirmed: EntityHashSet,
 }
 
 #[cfg(test)]
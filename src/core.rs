@@ -12,12 +12,20 @@ pub mod server_entity_map;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use channels::RepliconChannels;
-use event::event_registry::EventRegistry;
+use channels::{ChannelKind, RepliconChannels};
+use event::{
+    client_event::{ClientEventAppExt, ClientEventRateLimits},
+    event_registry::EventRegistry,
+    server_event::ServerEventAppExt,
+};
 use replication::{
-    command_markers::CommandMarkers, replication_registry::ReplicationRegistry,
-    replication_rules::ReplicationRules, track_mutate_messages::TrackMutateMessages, Replicated,
+    command_markers::CommandMarkers, prediction_registry::PredictionRegistry,
+    replicated_resources::ReplicatedResources, replication_registry::ReplicationRegistry,
+    replication_rules::ReplicationRules, track_mutate_messages::TrackMutateMessages,
+    ClientSnapshotRequest, EntityReplicationOrder, Replicated, ReplicationPaused,
+    ReplicationPriority, ReplicationSleeping, ReplicationTags,
 };
+use replicon_server::KickReason;
 
 /// Initializes types and resources needed for both client and server.
 pub struct RepliconCorePlugin;
@@ -25,12 +33,23 @@ pub struct RepliconCorePlugin;
 impl Plugin for RepliconCorePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Replicated>()
+            .register_type::<ReplicationSleeping>()
+            .register_type::<ReplicationPaused>()
+            .register_type::<ReplicationPriority>()
+            .register_type::<ReplicationTags>()
+            .register_type::<EntityReplicationOrder>()
             .init_resource::<TrackMutateMessages>()
             .init_resource::<RepliconChannels>()
             .init_resource::<ReplicationRegistry>()
+            .init_resource::<PredictionRegistry>()
             .init_resource::<ReplicationRules>()
+            .init_resource::<ReplicatedResources>()
             .init_resource::<CommandMarkers>()
-            .init_resource::<EventRegistry>();
+            .init_resource::<EventRegistry>()
+            .init_resource::<ClientEventRateLimits>()
+            .add_client_event::<ClientSnapshotRequest>(ChannelKind::Unordered)
+            .add_server_event::<KickReason>(ChannelKind::Ordered)
+            .make_independent::<KickReason>();
     }
 }
 
@@ -13,14 +13,21 @@ pub enum ReplicationChannel {
     Updates,
     /// For sending messages with component mutations.
     ///
-    /// This is an unreliable channel.
+    /// This is an unreliable channel. Additional channels for mutations can be
+    /// created with [`RepliconChannels::set_mutation_channels`].
     Mutations,
+    /// For sending messages with replicated resources.
+    ///
+    /// This is an ordered reliable channel.
+    Resources,
 }
 
 impl From<ReplicationChannel> for RepliconChannel {
     fn from(value: ReplicationChannel) -> Self {
         match value {
-            ReplicationChannel::Updates => ChannelKind::Ordered.into(),
+            ReplicationChannel::Updates | ReplicationChannel::Resources => {
+                ChannelKind::Ordered.into()
+            }
             ReplicationChannel::Mutations => ChannelKind::Unreliable.into(),
         }
     }
@@ -41,6 +48,14 @@ pub struct RepliconChannels {
     /// Same as [`Self::server`], but for client.
     client: Vec<RepliconChannel>,
 
+    /// IDs of channels created with [`Self::add_user_channel`].
+    user_ids: Vec<u8>,
+
+    /// IDs of server channels used for mutations.
+    ///
+    /// See [`Self::set_mutation_channels`].
+    mutation_ids: Vec<u8>,
+
     /// Stores the default max memory usage bytes for all channels.
     ///
     /// This value will be used instead of [`None`].
@@ -55,11 +70,14 @@ impl Default for RepliconChannels {
             server: vec![
                 ReplicationChannel::Updates.into(),
                 ReplicationChannel::Mutations.into(),
+                ReplicationChannel::Resources.into(),
             ],
             client: vec![
                 ReplicationChannel::Updates.into(),
                 ReplicationChannel::Mutations.into(),
             ],
+            user_ids: Default::default(),
+            mutation_ids: vec![ReplicationChannel::Mutations.into()],
             default_max_bytes: 5 * 1024 * 1024,
         }
     }
@@ -78,7 +96,10 @@ impl RepliconChannels {
     /// Panics if the number of events exceeds [`u8::MAX`].
     pub fn create_server_channel(&mut self, channel: impl Into<RepliconChannel>) -> u8 {
         if self.server.len() == u8::MAX as usize {
-            panic!("number of server channels shouldn't exceed `u8::MAX`");
+            panic!(
+                "number of server channels shouldn't exceed `u8::MAX`, \
+                consider combining multiple server events into a single enum event"
+            );
         }
 
         self.server.push(channel.into());
@@ -95,7 +116,10 @@ impl RepliconChannels {
     /// Panics if the number of events exceeds [`u8::MAX`].
     pub fn create_client_channel(&mut self, channel: impl Into<RepliconChannel>) -> u8 {
         if self.client.len() == u8::MAX as usize {
-            panic!("number of client channels shouldn't exceed `u8::MAX`");
+            panic!(
+                "number of client channels shouldn't exceed `u8::MAX`, \
+                consider combining multiple client events into a single enum event"
+            );
         }
 
         self.client.push(channel.into());
@@ -105,6 +129,74 @@ impl RepliconChannels {
         id
     }
 
+    /// Creates a channel for application-defined messages in both directions and returns its ID.
+    ///
+    /// Useful for traffic that is not related to replication or events, such as voice chat metadata.
+    /// Messages can be sent and received directly via [`RepliconServer`](super::replicon_server::RepliconServer)
+    /// and [`RepliconClient`](super::replicon_client::RepliconClient) with the returned ID.
+    ///
+    /// The ID is the same for server and client channels. To achieve this, the shorter list of channels
+    /// is padded with unreliable channels that are never used by Replicon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of server or client channels exceeds [`u8::MAX`].
+    pub fn add_user_channel(&mut self, channel: impl Into<RepliconChannel>) -> u8 {
+        let len = self.server.len().max(self.client.len());
+        if len == u8::MAX as usize {
+            panic!("number of channels shouldn't exceed `u8::MAX` to create a user channel");
+        }
+
+        let channel = channel.into();
+        for channels in [&mut self.server, &mut self.client] {
+            channels.resize(len, ChannelKind::Unreliable.into());
+            channels.push(channel.clone());
+        }
+        let id = len as u8;
+        self.user_ids.push(id);
+        debug!("creating a user channel with ID {id}");
+
+        id
+    }
+
+    /// Returns IDs of channels created with [`Self::add_user_channel`] in the order of creation.
+    pub fn user_channel_ids(&self) -> &[u8] {
+        &self.user_ids
+    }
+
+    /// Creates additional unreliable server channels until the number of channels for mutations reaches `count`.
+    ///
+    /// Mutations are distributed between channels by entity index, so a saturated channel
+    /// drops only a part of them. The channel list should be identical on server and client,
+    /// so client-only apps need to call this method with the same count as the server.
+    /// On mismatch, the client logs an error and discards mutate messages received over the wrong channel.
+    ///
+    /// Usually configured via [`ServerPlugin::mutation_channels`](crate::server::ServerPlugin::mutation_channels).
+    ///
+    /// Channel IDs are allocated at the time of the call, so it should be called at the same point
+    /// relative to [`Self::create_server_channel`] and [`Self::add_user_channel`] on both server and client.
+    /// [`ServerPlugin`](crate::server::ServerPlugin) calls it during build, so on the client
+    /// it should be called right after adding the plugins and before registering any events
+    /// or user channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero or the number of server channels exceeds [`u8::MAX`].
+    pub fn set_mutation_channels(&mut self, count: usize) {
+        assert_ne!(count, 0, "there should be at least one mutation channel");
+        while self.mutation_ids.len() < count {
+            let id = self.create_server_channel(ChannelKind::Unreliable);
+            self.mutation_ids.push(id);
+        }
+    }
+
+    /// Returns IDs of server channels used for mutations.
+    ///
+    /// Always starts with [`ReplicationChannel::Mutations`].
+    pub fn mutation_channel_ids(&self) -> &[u8] {
+        &self.mutation_ids
+    }
+
     /// Returns a mutable reference to a server channel.
     ///
     /// # Panics
@@ -123,15 +215,53 @@ impl RepliconChannels {
         &mut self.client[channel_id.into() as usize]
     }
 
+    /// Returns a server channel by its ID.
+    ///
+    /// Returns [`None`] if there is no such channel.
+    pub fn server_channel<I: Into<u8>>(&self, channel_id: I) -> Option<&RepliconChannel> {
+        self.server.get(channel_id.into() as usize)
+    }
+
+    /// Same as [`Self::server_channel`], but for client.
+    pub fn client_channel<I: Into<u8>>(&self, channel_id: I) -> Option<&RepliconChannel> {
+        self.client.get(channel_id.into() as usize)
+    }
+
     /// Returns registered server channels.
+    ///
+    /// Channel IDs are their indices. See also [`Self::iter_server_channels`].
     pub fn server_channels(&self) -> &[RepliconChannel] {
         &self.server
     }
 
     /// Returns registered client channels.
+    ///
+    /// Channel IDs are their indices. See also [`Self::iter_client_channels`].
     pub fn client_channels(&self) -> &[RepliconChannel] {
         &self.client
     }
+
+    /// Returns an iterator over registered server channels with their IDs.
+    ///
+    /// Useful for messaging backends to configure their transport.
+    pub fn iter_server_channels(&self) -> impl Iterator<Item = (u8, &RepliconChannel)> {
+        iter_with_ids(&self.server)
+    }
+
+    /// Same as [`Self::iter_server_channels`], but for client.
+    pub fn iter_client_channels(&self) -> impl Iterator<Item = (u8, &RepliconChannel)> {
+        iter_with_ids(&self.client)
+    }
+}
+
+/// Pairs channels with their IDs.
+///
+/// Channels count can't exceed [`u8::MAX`], which is checked on creation.
+fn iter_with_ids(channels: &[RepliconChannel]) -> impl Iterator<Item = (u8, &RepliconChannel)> {
+    channels
+        .iter()
+        .enumerate()
+        .map(|(id, channel)| (id as u8, channel))
 }
 
 /// Channel configuration.
@@ -173,3 +303,119 @@ impl From<ChannelKind> for RepliconChannel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "shouldn't exceed `u8::MAX`")]
+    fn server_channels_overflow() {
+        let mut channels = RepliconChannels::default();
+        for _ in 0..=u8::MAX {
+            channels.create_server_channel(ChannelKind::Ordered);
+        }
+    }
+
+    #[test]
+    fn user_channels() {
+        let mut channels = RepliconChannels::default();
+        channels.create_server_channel(ChannelKind::Ordered);
+        let first_id = channels.add_user_channel(ChannelKind::Unordered);
+        channels.create_client_channel(ChannelKind::Ordered);
+        let second_id = channels.add_user_channel(ChannelKind::Ordered);
+
+        assert_eq!(first_id, 4);
+        assert_eq!(second_id, 6);
+        assert_eq!(channels.user_channel_ids(), [first_id, second_id]);
+        assert_eq!(channels.server_channels().len(), 7);
+        assert_eq!(channels.client_channels().len(), 7);
+        for id in [first_id, second_id] {
+            assert_eq!(
+                channels.server_channels()[id as usize].kind,
+                channels.client_channels()[id as usize].kind
+            );
+        }
+        assert_eq!(
+            channels.server_channels()[second_id as usize].kind,
+            ChannelKind::Ordered
+        );
+    }
+
+    #[test]
+    fn channel_lookup() {
+        let mut channels = RepliconChannels::default();
+        let server_id = channels.create_server_channel(ChannelKind::Unreliable);
+        let client_id = channels.create_client_channel(ChannelKind::Unordered);
+
+        let server_channels: Vec<_> = channels
+            .iter_server_channels()
+            .map(|(id, channel)| (id, channel.kind))
+            .collect();
+        assert_eq!(
+            server_channels,
+            [
+                (ReplicationChannel::Updates.into(), ChannelKind::Ordered),
+                (
+                    ReplicationChannel::Mutations.into(),
+                    ChannelKind::Unreliable
+                ),
+                (ReplicationChannel::Resources.into(), ChannelKind::Ordered),
+                (server_id, ChannelKind::Unreliable),
+            ]
+        );
+        assert_eq!(channels.iter_client_channels().count(), 3);
+        assert_eq!(
+            channels.client_channel(client_id).unwrap().kind,
+            ChannelKind::Unordered
+        );
+        assert!(channels.server_channel(server_id + 1).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "shouldn't exceed `u8::MAX`")]
+    fn user_channels_overflow() {
+        let mut channels = RepliconChannels::default();
+        for _ in 0..=u8::MAX {
+            channels.add_user_channel(ChannelKind::Ordered);
+        }
+    }
+
+    #[test]
+    fn mutation_channels() {
+        let mut channels = RepliconChannels::default();
+        assert_eq!(
+            channels.mutation_channel_ids(),
+            [ReplicationChannel::Mutations.into()]
+        );
+
+        let server_id = channels.create_server_channel(ChannelKind::Ordered);
+        channels.set_mutation_channels(3);
+        assert_eq!(
+            channels.mutation_channel_ids(),
+            [
+                ReplicationChannel::Mutations.into(),
+                server_id + 1,
+                server_id + 2
+            ]
+        );
+        for &id in channels.mutation_channel_ids() {
+            assert_eq!(
+                channels.server_channel(id).unwrap().kind,
+                ChannelKind::Unreliable
+            );
+        }
+
+        channels.set_mutation_channels(2);
+        assert_eq!(channels.mutation_channel_ids().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "shouldn't exceed `u8::MAX`")]
+    fn client_channels_overflow() {
+        let mut channels = RepliconChannels::default();
+        for _ in 0..=u8::MAX {
+            channels.create_client_channel(ChannelKind::Ordered);
+        }
+    }
+}
@@ -40,3 +40,105 @@ pub fn serialize_entity(writer: &mut impl VarIntWriter, entity: Entity) -> binco
 
     Ok(())
 }
+
+/// Deserializes a list of entities.
+///
+/// For details see [`serialize_entity_list`].
+pub fn deserialize_entity_list(reader: &mut impl VarIntReader) -> bincode::Result<Vec<Entity>> {
+    let len: usize = reader.read_varint()?;
+    let mut entities = Vec::with_capacity(len.min(1024));
+    let mut prev_index = 0;
+    for _ in 0..len {
+        let flagged_delta: u64 = reader.read_varint()?;
+        let has_generation = (flagged_delta & 1) > 0;
+        let generation = if has_generation {
+            reader.read_varint::<u32>()? + 1
+        } else {
+            1u32
+        };
+
+        let zigzag = flagged_delta >> 1;
+        let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+        let index = prev_index + delta;
+        let index = u32::try_from(index).map_err(|_| {
+            bincode::ErrorKind::Custom(format!("entity index {index} is out of range"))
+        })?;
+        prev_index = index.into();
+
+        let bits = (generation as u64) << 32 | index as u64;
+        entities.push(Entity::from_bits(bits));
+    }
+
+    Ok(entities)
+}
+
+/// Serializes `entities` by writing their count followed by each entity with its index delta-encoded.
+///
+/// Similar to [`serialize_entity`], but instead of the index, each entity stores the difference
+/// from the previous entity index as a zigzag-encoded varint. This makes lists of close entities,
+/// such as entities spawned together, take 1 byte per entity.
+///
+/// See also [`deserialize_entity_list`].
+pub fn serialize_entity_list(
+    writer: &mut impl VarIntWriter,
+    entities: &[Entity],
+) -> bincode::Result<()> {
+    writer.write_varint(entities.len())?;
+    let mut prev_index = 0;
+    for entity in entities {
+        let index = entity.index() as i64;
+        let delta = index - prev_index;
+        prev_index = index;
+
+        let zigzag = ((delta << 1) ^ (delta >> 63)) as u64;
+        let mut flagged_delta = zigzag << 1;
+        let flag = entity.generation() > 1;
+        flagged_delta |= flag as u64;
+
+        writer.write_varint(flagged_delta)?;
+        if flag {
+            writer.write_varint(entity.generation() - 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn entity_list() {
+        let entities = [
+            Entity::from_raw(5),
+            Entity::from_raw(6),
+            Entity::from_raw(2),
+            Entity::from_bits(2 << 32 | 100),
+            Entity::from_raw(u32::MAX - 1),
+            Entity::from_raw(0),
+        ];
+
+        let mut message = Vec::new();
+        serialize_entity_list(&mut message, &entities).unwrap();
+        let mut cursor = Cursor::new(&*message);
+        let deserialized = deserialize_entity_list(&mut cursor).unwrap();
+        assert_eq!(deserialized, entities);
+        assert_eq!(cursor.position(), message.len() as u64);
+    }
+
+    #[test]
+    fn consecutive_entity_list() {
+        let entities: Vec<_> = (1000..1100).map(Entity::from_raw).collect();
+
+        let mut message = Vec::new();
+        serialize_entity_list(&mut message, &entities).unwrap();
+        assert_eq!(
+            message.len(),
+            1 + 2 + 99,
+            "only the first index should take more than 1 byte"
+        );
+    }
+}
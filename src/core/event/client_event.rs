@@ -12,6 +12,7 @@ use bevy::{
     },
     prelude::*,
     ptr::{Ptr, PtrMut},
+    utils::HashMap,
 };
 use bincode::{DefaultOptions, Options};
 use serde::{de::DeserializeOwned, Serialize};
@@ -52,6 +53,8 @@ pub trait ClientEventAppExt {
     /// Same as [`Self::add_client_event`], but additionally maps client entities to server inside the event before sending.
     ///
     /// Always use it for events that contain entities.
+    /// Unlike [`Self::add_client_event`], requires [`Clone`] because entities are mapped
+    /// on a copy of the event before serialization.
     /// See also [`Self::add_client_event`].
     fn add_mapped_client_event<E: Event + Serialize + DeserializeOwned + MapEntities + Clone>(
         &mut self,
@@ -121,6 +124,20 @@ pub trait ClientEventAppExt {
         serialize: SerializeFn<E>,
         deserialize: DeserializeFn<E>,
     ) -> &mut Self;
+
+    /// Same as [`Self::add_client_event`], but limits how many events `E` each client can send per second.
+    ///
+    /// Events that exceed the limit are dropped on the server before deserialization.
+    /// Clients can send a burst of up to `max_per_second` events (but at least one),
+    /// after which the allowance is restored at the specified rate.
+    ///
+    /// See also [`ClientEventRateLimits`] and
+    /// [`ServerPlugin::default_event_rate_limit`](crate::server::ServerPlugin::default_event_rate_limit).
+    fn add_client_event_with_rate_limit<E: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        max_per_second: f32,
+    ) -> &mut Self;
 }
 
 impl ClientEventAppExt for App {
@@ -136,10 +153,14 @@ impl ClientEventAppExt for App {
             .add_event::<FromClient<E>>()
             .init_resource::<ClientEventReader<E>>();
 
-        let channel_id = self
-            .world_mut()
-            .resource_mut::<RepliconChannels>()
-            .create_client_channel(channel);
+        let mut channels = self.world_mut().resource_mut::<RepliconChannels>();
+        assert!(
+            channels.client_channels().len() < u8::MAX as usize,
+            "unable to create a channel for `{}`: number of client channels shouldn't exceed `u8::MAX`, \
+            consider combining multiple client events into a single enum event",
+            any::type_name::<E>()
+        );
+        let channel_id = channels.create_client_channel(channel);
 
         self.world_mut()
             .resource_scope(|world, mut event_registry: Mut<EventRegistry>| {
@@ -153,6 +174,19 @@ impl ClientEventAppExt for App {
 
         self
     }
+
+    fn add_client_event_with_rate_limit<E: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        max_per_second: f32,
+    ) -> &mut Self {
+        self.add_client_event::<E>(channel);
+        self.world_mut()
+            .resource_mut::<ClientEventRateLimits>()
+            .set_limit::<E>(max_per_second);
+
+        self
+    }
 }
 
 /// Type-erased functions and metadata for a registered client event.
@@ -294,8 +328,9 @@ impl ClientEvent {
         ctx: &mut ServerReceiveCtx,
         client_events: PtrMut,
         server: &mut RepliconServer,
+        rate_limits: &mut ClientEventRateLimits,
     ) {
-        (self.receive)(self, ctx, client_events, server);
+        (self.receive)(self, ctx, client_events, server, rate_limits);
     }
 
     /// Typed version of [`Self::receive`].
@@ -309,11 +344,20 @@ impl ClientEvent {
         ctx: &mut ServerReceiveCtx,
         events: PtrMut,
         server: &mut RepliconServer,
+        rate_limits: &mut ClientEventRateLimits,
     ) {
         self.check_type::<E>();
 
         let events: &mut Events<FromClient<E>> = events.deref_mut();
         for (client_id, message) in server.receive(self.channel_id) {
+            if !rate_limits.try_consume(self.event_id, client_id) {
+                debug!(
+                    "dropping event `{}` from {client_id:?} due to rate limit",
+                    any::type_name::<E>()
+                );
+                continue;
+            }
+
             let mut cursor = Cursor::new(&*message);
             match self.deserialize(ctx, &mut cursor) {
                 Ok(event) => {
@@ -435,7 +479,13 @@ pub type DeserializeFn<E> = fn(&mut ServerReceiveCtx, &mut Cursor<&[u8]>) -> bin
 type SendFn = unsafe fn(&ClientEvent, &mut ClientSendCtx, &Ptr, PtrMut, &mut RepliconClient);
 
 /// Signature of client event receiving functions.
-type ReceiveFn = unsafe fn(&ClientEvent, &mut ServerReceiveCtx, PtrMut, &mut RepliconServer);
+type ReceiveFn = unsafe fn(
+    &ClientEvent,
+    &mut ServerReceiveCtx,
+    PtrMut,
+    &mut RepliconServer,
+    &mut ClientEventRateLimits,
+);
 
 /// Signature of client event resending functions.
 type ResendLocallyFn = unsafe fn(PtrMut, PtrMut);
@@ -456,6 +506,105 @@ impl<E: Event> FromWorld for ClientEventReader<E> {
     }
 }
 
+/// Per-client limits on the number of received client events.
+///
+/// Uses a token bucket for each event type and client: each received event consumes a token,
+/// and tokens are restored every frame at the limit rate. Events received without tokens are dropped.
+///
+/// Limits can be set with [`ClientEventAppExt::add_client_event_with_rate_limit`] for specific events
+/// or with [`ServerPlugin::default_event_rate_limit`](crate::server::ServerPlugin::default_event_rate_limit)
+/// for all other events.
+#[derive(Resource, Default)]
+pub struct ClientEventRateLimits {
+    /// Maximum number of events per second for specific event types.
+    limits: HashMap<TypeId, f32>,
+
+    /// Maximum number of events per second for event types without a specific limit.
+    default_limit: Option<f32>,
+
+    /// Available tokens for each event type and client.
+    ///
+    /// Full buckets are removed.
+    buckets: HashMap<(TypeId, ClientId), f64>,
+}
+
+impl ClientEventRateLimits {
+    /// Sets the maximum number of events `E` that each client can send per second.
+    pub fn set_limit<E: Event>(&mut self, max_per_second: f32) {
+        self.limits.insert(TypeId::of::<E>(), max_per_second);
+    }
+
+    /// Returns the maximum number of events `E` that each client can send per second.
+    ///
+    /// Falls back to [`Self::default_limit`] if no limit was set for `E`.
+    pub fn limit<E: Event>(&self) -> Option<f32> {
+        self.limit_by_id(TypeId::of::<E>())
+    }
+
+    /// Sets the limit for events without a specific limit.
+    pub fn set_default_limit(&mut self, max_per_second: Option<f32>) {
+        self.default_limit = max_per_second;
+    }
+
+    /// Returns the limit for events without a specific limit.
+    pub fn default_limit(&self) -> Option<f32> {
+        self.default_limit
+    }
+
+    fn limit_by_id(&self, event_id: TypeId) -> Option<f32> {
+        self.limits.get(&event_id).copied().or(self.default_limit)
+    }
+
+    /// Consumes a token for an event and returns `true` if the event is allowed.
+    pub(crate) fn try_consume(&mut self, event_id: TypeId, client_id: ClientId) -> bool {
+        let Some(limit) = self.limit_by_id(event_id) else {
+            return true;
+        };
+
+        let tokens = self
+            .buckets
+            .entry((event_id, client_id))
+            .or_insert_with(|| bucket_capacity(limit));
+        if *tokens < 1.0 {
+            return false;
+        }
+
+        *tokens -= 1.0;
+        true
+    }
+
+    /// Restores tokens for all buckets based on the elapsed time.
+    pub(crate) fn refill(&mut self, delta_secs: f32) {
+        let limits = &self.limits;
+        let default_limit = self.default_limit;
+        self.buckets.retain(|(event_id, _), tokens| {
+            let Some(limit) = limits.get(event_id).copied().or(default_limit) else {
+                return false;
+            };
+
+            let capacity = bucket_capacity(limit);
+            *tokens += limit as f64 * delta_secs as f64;
+            *tokens < capacity
+        });
+    }
+
+    /// Removes buckets for a disconnected client.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        self.buckets
+            .retain(|&(_, bucket_client_id), _| bucket_client_id != client_id);
+    }
+
+    /// Removes all buckets.
+    pub(crate) fn clear(&mut self) {
+        self.buckets.clear();
+    }
+}
+
+/// Returns the maximum number of tokens in a bucket for the given limit.
+fn bucket_capacity(limit: f32) -> f64 {
+    (limit as f64).max(1.0)
+}
+
 /// An event indicating that a message from client was received.
 /// Emitted only on server.
 #[derive(Clone, Copy, Event)]
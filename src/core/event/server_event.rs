@@ -18,6 +18,7 @@ use bincode::{DefaultOptions, Options};
 use bytes::Bytes;
 use ordered_multimap::ListOrderedMultimap;
 use serde::{de::DeserializeOwned, Serialize};
+use smallvec::SmallVec;
 
 use super::{
     ctx::{ClientReceiveCtx, ServerSendCtx},
@@ -126,6 +127,48 @@ pub trait ServerEventAppExt {
         deserialize: DeserializeFn<E>,
     ) -> &mut Self;
 
+    /**
+    Same as [`Self::add_server_event_with`], but skips clients for which `filter` returns `false`.
+
+    The filter is checked for each recipient from [`SendMode`] before serialization,
+    so the event won't be serialized at all if it's filtered out for all clients.
+    It's also checked for [`ClientId::SERVER`] when the event is resent locally.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::event::server_event::{default_deserialize, default_serialize},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_server_event_with_filter(
+        ChannelKind::Ordered,
+        default_serialize::<AdminMessage>,
+        default_deserialize::<AdminMessage>,
+        is_admin,
+    );
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct AdminMessage(String);
+
+    fn is_admin(_event: &AdminMessage, client_id: ClientId) -> bool {
+        client_id == ClientId::SERVER
+    }
+    ```
+    */
+    fn add_server_event_with_filter<E: Event>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        serialize: SerializeFn<E>,
+        deserialize: DeserializeFn<E>,
+        filter: FilterFn<E>,
+    ) -> &mut Self;
+
     /// Marks the event `E` as an independent event.
     ///
     /// By default, all server events are buffered on server until server tick
@@ -162,10 +205,14 @@ impl ServerEventAppExt for App {
             .add_event::<ToClients<E>>()
             .init_resource::<ServerEventQueue<E>>();
 
-        let channel_id = self
-            .world_mut()
-            .resource_mut::<RepliconChannels>()
-            .create_server_channel(channel);
+        let mut channels = self.world_mut().resource_mut::<RepliconChannels>();
+        assert!(
+            channels.server_channels().len() < u8::MAX as usize,
+            "unable to create a channel for `{}`: number of server channels shouldn't exceed `u8::MAX`, \
+            consider combining multiple server events into a single enum event",
+            any::type_name::<E>()
+        );
+        let channel_id = channels.create_server_channel(channel);
 
         self.world_mut()
             .resource_scope(|world, mut event_registry: Mut<EventRegistry>| {
@@ -180,6 +227,33 @@ impl ServerEventAppExt for App {
         self
     }
 
+    fn add_server_event_with_filter<E: Event>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+        serialize: SerializeFn<E>,
+        deserialize: DeserializeFn<E>,
+        filter: FilterFn<E>,
+    ) -> &mut Self {
+        self.add_server_event_with(channel, serialize, deserialize);
+
+        let events_id = self
+            .world()
+            .components()
+            .resource_id::<Events<E>>()
+            .expect("event should be registered");
+
+        let mut event_registry = self.world_mut().resource_mut::<EventRegistry>();
+        let event_data = event_registry
+            .iter_server_events_mut()
+            .find(|event| event.events_id() == events_id)
+            .expect("event should be registered as a server event");
+
+        // SAFETY: the found instance was created for `E`.
+        unsafe { event_data.set_filter(filter) };
+
+        self
+    }
+
     fn make_independent<E: Event>(&mut self) -> &mut Self {
         let events_id = self
             .world()
@@ -241,6 +315,7 @@ pub(crate) struct ServerEvent {
     reset: ResetFn,
     serialize: unsafe fn(),
     deserialize: unsafe fn(),
+    filter: Option<unsafe fn()>,
 }
 
 impl ServerEvent {
@@ -288,9 +363,20 @@ impl ServerEvent {
             // SAFETY: these functions won't be called until the type is restored.
             serialize: unsafe { mem::transmute::<SerializeFn<E>, unsafe fn()>(serialize) },
             deserialize: unsafe { mem::transmute::<DeserializeFn<E>, unsafe fn()>(deserialize) },
+            filter: None,
         }
     }
 
+    /// Assigns a function that decides whether the event should be sent to a client.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that this instance was created for `E`.
+    unsafe fn set_filter<E: Event>(&mut self, filter: FilterFn<E>) {
+        // SAFETY: the function won't be called until the type is restored.
+        self.filter = Some(unsafe { mem::transmute::<FilterFn<E>, unsafe fn()>(filter) });
+    }
+
     pub(crate) fn events_id(&self) -> ComponentId {
         self.events_id
     }
@@ -319,6 +405,7 @@ impl ServerEvent {
         server_events: &Ptr,
         server: &mut RepliconServer,
         connected_clients: &ConnectedClients,
+        client_groups: &ClientGroupRegistry,
         buffered_events: &mut BufferedServerEvents,
     ) {
         (self.send_or_buffer)(
@@ -327,6 +414,7 @@ impl ServerEvent {
             server_events,
             server,
             connected_clients,
+            client_groups,
             buffered_events,
         );
     }
@@ -343,6 +431,7 @@ impl ServerEvent {
         server_events: &Ptr,
         server: &mut RepliconServer,
         connected_clients: &ConnectedClients,
+        client_groups: &ClientGroupRegistry,
         buffered_events: &mut BufferedServerEvents,
     ) {
         self.check_type::<E>();
@@ -353,11 +442,42 @@ impl ServerEvent {
         for ToClients { event, mode } in events.get_cursor().read(events) {
             debug!("sending event `{}` with `{mode:?}`", any::type_name::<E>());
 
+            let filtered_mode;
+            let mode = if let Some(filter) = self.filter::<E>() {
+                let client_ids: SmallVec<_> = connected_clients
+                    .iter()
+                    .map(|client| client.id())
+                    .filter(|&client_id| {
+                        mode.includes(client_groups, client_id) && (filter)(event, client_id)
+                    })
+                    .collect();
+
+                if client_ids.is_empty() {
+                    debug!(
+                        "skipping event `{}` filtered out for all clients",
+                        any::type_name::<E>()
+                    );
+                    continue;
+                }
+
+                filtered_mode = SendMode::DirectMultiple(client_ids);
+                &filtered_mode
+            } else {
+                mode
+            };
+
             if self.is_independent() {
-                self.send_independent_event(ctx, event, mode, server, connected_clients)
-                    .expect("independent server event should be serializable");
+                self.send_independent_event(
+                    ctx,
+                    event,
+                    mode,
+                    server,
+                    connected_clients,
+                    client_groups,
+                )
+                .expect("independent server event should be serializable");
             } else {
-                self.buffer_event(ctx, event, *mode, buffered_events)
+                self.buffer_event(ctx, event, mode.clone(), buffered_events)
                     .expect("server event should be serializable");
             }
         }
@@ -377,12 +497,13 @@ impl ServerEvent {
         mode: &SendMode,
         server: &mut RepliconServer,
         connected_clients: &ConnectedClients,
+        client_groups: &ClientGroupRegistry,
     ) -> bincode::Result<()> {
         let mut message = Vec::new();
         self.serialize(ctx, event, &mut message)?;
         let message: Bytes = message.into();
 
-        match *mode {
+        match mode {
             SendMode::Broadcast => {
                 for client in connected_clients.iter() {
                     server.send(client.id(), self.channel_id, message.clone());
@@ -390,14 +511,35 @@ impl ServerEvent {
             }
             SendMode::BroadcastExcept(id) => {
                 for client in connected_clients.iter() {
-                    if client.id() != id {
+                    if client.id() != *id {
+                        server.send(client.id(), self.channel_id, message.clone());
+                    }
+                }
+            }
+            SendMode::BroadcastExceptMultiple(client_ids) => {
+                for client in connected_clients.iter() {
+                    if !client_ids.contains(&client.id()) {
                         server.send(client.id(), self.channel_id, message.clone());
                     }
                 }
             }
             SendMode::Direct(client_id) => {
-                if client_id != ClientId::SERVER {
-                    server.send(client_id, self.channel_id, message.clone());
+                if *client_id != ClientId::SERVER {
+                    server.send(*client_id, self.channel_id, message.clone());
+                }
+            }
+            SendMode::DirectMultiple(client_ids) => {
+                for &client_id in client_ids {
+                    if client_id != ClientId::SERVER {
+                        server.send(client_id, self.channel_id, message.clone());
+                    }
+                }
+            }
+            SendMode::Multicast(group_id) => {
+                for &client_id in client_groups.get(*group_id).iter() {
+                    if client_id != ClientId::SERVER {
+                        server.send(client_id, self.channel_id, message.clone());
+                    }
                 }
             }
         }
@@ -542,8 +684,13 @@ impl ServerEvent {
     ///
     /// The caller must ensure that `events` is [`Events<E>`], `server_events` is [`Events<ToClients<E>>`],
     /// and this instance was created for `E`.
-    pub(crate) unsafe fn resend_locally(&self, server_events: PtrMut, events: PtrMut) {
-        (self.resend_locally)(server_events, events);
+    pub(crate) unsafe fn resend_locally(
+        &self,
+        server_events: PtrMut,
+        events: PtrMut,
+        client_groups: &ClientGroupRegistry,
+    ) {
+        (self.resend_locally)(self, server_events, events, client_groups);
     }
 
     /// Typed version of [`Self::resend_locally`].
@@ -551,10 +698,22 @@ impl ServerEvent {
     /// # Safety
     ///
     /// The caller must ensure that `events` is [`Events<E>`] and `server_events` is [`Events<ToClients<E>>`].
-    unsafe fn resend_locally_typed<E: Event>(server_events: PtrMut, events: PtrMut) {
+    unsafe fn resend_locally_typed<E: Event>(
+        &self,
+        server_events: PtrMut,
+        events: PtrMut,
+        client_groups: &ClientGroupRegistry,
+    ) {
+        self.check_type::<E>();
+
         let server_events: &mut Events<ToClients<E>> = server_events.deref_mut();
         let events: &mut Events<E> = events.deref_mut();
+        let filter = self.filter::<E>();
         for ToClients { event, mode } in server_events.drain() {
+            if filter.is_some_and(|filter| !(filter)(&event, ClientId::SERVER)) {
+                continue;
+            }
+
             debug!("resending event `{}` locally", any::type_name::<E>());
             match mode {
                 SendMode::Broadcast => {
@@ -565,11 +724,26 @@ impl ServerEvent {
                         events.send(event);
                     }
                 }
+                SendMode::BroadcastExceptMultiple(client_ids) => {
+                    if !client_ids.contains(&ClientId::SERVER) {
+                        events.send(event);
+                    }
+                }
                 SendMode::Direct(client_id) => {
                     if client_id == ClientId::SERVER {
                         events.send(event);
                     }
                 }
+                SendMode::DirectMultiple(client_ids) => {
+                    if client_ids.contains(&ClientId::SERVER) {
+                        events.send(event);
+                    }
+                }
+                SendMode::Multicast(group_id) => {
+                    if client_groups.get(group_id).contains(ClientId::SERVER) {
+                        events.send(event);
+                    }
+                }
             }
         }
     }
@@ -617,6 +791,16 @@ impl ServerEvent {
         (serialize)(ctx, event, message)
     }
 
+    /// Returns the assigned filter.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that this instance was created for `E`.
+    unsafe fn filter<E: Event>(&self) -> Option<FilterFn<E>> {
+        self.filter
+            .map(|filter| std::mem::transmute::<unsafe fn(), FilterFn<E>>(filter))
+    }
+
     /// Deserializes an event from a cursor.
     ///
     /// # Safety
@@ -659,6 +843,11 @@ pub type SerializeFn<E> = fn(&mut ServerSendCtx, &E, &mut Vec<u8>) -> bincode::R
 /// Signature of server event deserialization functions.
 pub type DeserializeFn<E> = fn(&mut ClientReceiveCtx, &mut Cursor<&[u8]>) -> bincode::Result<E>;
 
+/// Signature of server event filters.
+///
+/// Returns `true` if the event should be sent to the client.
+pub type FilterFn<E> = fn(&E, ClientId) -> bool;
+
 /// Signature of server event sending functions.
 type SendOrBufferFn = unsafe fn(
     &ServerEvent,
@@ -666,6 +855,7 @@ type SendOrBufferFn = unsafe fn(
     &Ptr,
     &mut RepliconServer,
     &ConnectedClients,
+    &ClientGroupRegistry,
     &mut BufferedServerEvents,
 );
 
@@ -680,7 +870,7 @@ type ReceiveFn = unsafe fn(
 );
 
 /// Signature of server event resending functions.
-type ResendLocallyFn = unsafe fn(PtrMut, PtrMut);
+type ResendLocallyFn = unsafe fn(&ServerEvent, PtrMut, PtrMut, &ClientGroupRegistry);
 
 /// Signature of server event reset functions.
 type ResetFn = unsafe fn(PtrMut);
@@ -732,6 +922,14 @@ impl SerializedMessage {
             }
         }
     }
+
+    /// Returns the message length in bytes, including the tick or its padding.
+    fn len(&self) -> usize {
+        match self {
+            Self::Raw(raw) => raw.len(),
+            Self::Resolved { bytes, .. } => bytes.len(),
+        }
+    }
 }
 
 struct BufferedServerEvent {
@@ -771,8 +969,10 @@ impl BufferedServerEventSet {
 /// This exists because replication does not scan the world every tick. If a server event is sent in the same
 /// tick as a spawn and the event references that spawn, then the server event's update tick needs to be synchronized
 /// with that spawn on the client. We buffer the event until the spawn can be detected.
+///
+/// Can be accessed as a resource to monitor how many events are waiting to be sent.
 #[derive(Resource, Default)]
-pub(crate) struct BufferedServerEvents {
+pub struct BufferedServerEvents {
     buffer: Vec<BufferedServerEventSet>,
 
     /// Caches unused sets to avoid reallocations when pushing into the buffer.
@@ -802,6 +1002,25 @@ impl BufferedServerEvents {
         });
     }
 
+    /// Returns the number of buffered events across all ticks.
+    ///
+    /// Events are buffered until the next replication tick, so a steadily growing count
+    /// means that the server doesn't replicate often enough.
+    pub fn event_count(&self) -> usize {
+        self.buffer.iter().map(|set| set.events.len()).sum()
+    }
+
+    /// Returns the approximate memory used by buffered messages in bytes.
+    ///
+    /// Each event is counted once, regardless of how many clients it will be sent to.
+    pub fn byte_estimate(&self) -> usize {
+        self.buffer
+            .iter()
+            .flat_map(|set| &set.events)
+            .map(|event| event.message.len())
+            .sum()
+    }
+
     /// Used to prevent newly-connected clients from receiving old events.
     pub(crate) fn exclude_client(&mut self, client: ClientId) {
         for set in self.buffer.iter_mut() {
@@ -813,6 +1032,7 @@ impl BufferedServerEvents {
         &mut self,
         server: &mut RepliconServer,
         replicated_clients: &ReplicatedClients,
+        client_groups: &ClientGroupRegistry,
     ) -> bincode::Result<()> {
         for mut set in self.buffer.drain(..) {
             for mut event in set.events.drain(..) {
@@ -836,6 +1056,18 @@ impl BufferedServerEvents {
                             event.send(server, client)?;
                         }
                     }
+                    SendMode::BroadcastExceptMultiple(ref client_ids) => {
+                        let client_ids = client_ids.clone();
+                        for client in replicated_clients
+                            .iter()
+                            .filter(|c| !set.excluded.contains(&c.id()))
+                        {
+                            if client_ids.contains(&client.id()) {
+                                continue;
+                            }
+                            event.send(server, client)?;
+                        }
+                    }
                     SendMode::Direct(client_id) => {
                         if client_id != ClientId::SERVER && !set.excluded.contains(&client_id) {
                             if let Some(client) = replicated_clients.get_client(client_id) {
@@ -843,6 +1075,28 @@ impl BufferedServerEvents {
                             }
                         }
                     }
+                    SendMode::DirectMultiple(ref client_ids) => {
+                        let client_ids = client_ids.clone();
+                        for &client_id in client_ids
+                            .iter()
+                            .filter(|&id| *id != ClientId::SERVER && !set.excluded.contains(id))
+                        {
+                            if let Some(client) = replicated_clients.get_client(client_id) {
+                                event.send(server, client)?;
+                            }
+                        }
+                    }
+                    SendMode::Multicast(group_id) => {
+                        for client_id in client_groups
+                            .get(group_id)
+                            .iter()
+                            .filter(|id| !set.excluded.contains(id))
+                        {
+                            if let Some(client) = replicated_clients.get_client(*client_id) {
+                                event.send(server, client)?;
+                            }
+                        }
+                    }
                 }
             }
             set.clear();
@@ -860,20 +1114,138 @@ impl BufferedServerEvents {
 }
 
 /// An event that will be send to client(s).
-#[derive(Clone, Copy, Debug, Event)]
+///
+/// Dereferences to the inner event.
+#[derive(Clone, Debug, Event, Deref, DerefMut)]
 pub struct ToClients<T> {
     pub mode: SendMode,
+    #[deref]
     pub event: T,
 }
 
 /// Type of server message sending.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum SendMode {
     Broadcast,
     BroadcastExcept(ClientId),
+    /// Like [`Self::BroadcastExcept`], but excludes multiple clients.
+    BroadcastExceptMultiple(SmallVec<[ClientId; 4]>),
     Direct(ClientId),
+    /// Like [`Self::Direct`], but sends to multiple clients.
+    ///
+    /// For large or reusable sets of clients use [`Self::Multicast`].
+    DirectMultiple(SmallVec<[ClientId; 4]>),
+    /// Sends to all clients from the group.
+    ///
+    /// Members are resolved from [`ClientGroupRegistry`] at the time of sending.
+    Multicast(GroupId),
 }
 
+impl SendMode {
+    /// Returns `true` if the client is a recipient of this mode.
+    fn includes(&self, client_groups: &ClientGroupRegistry, client_id: ClientId) -> bool {
+        match self {
+            SendMode::Broadcast => true,
+            SendMode::BroadcastExcept(id) => *id != client_id,
+            SendMode::BroadcastExceptMultiple(client_ids) => !client_ids.contains(&client_id),
+            SendMode::Direct(id) => *id == client_id,
+            SendMode::DirectMultiple(client_ids) => client_ids.contains(&client_id),
+            SendMode::Multicast(group_id) => client_groups.get(*group_id).contains(client_id),
+        }
+    }
+}
+
+/// A set of clients stored in [`ClientGroupRegistry`].
+#[derive(Clone, Debug, Default, Deref)]
+pub struct ClientGroup(HashSet<ClientId>);
+
+impl ClientGroup {
+    /// Creates a new group from the given clients.
+    pub fn new(clients: impl IntoIterator<Item = ClientId>) -> Self {
+        Self(clients.into_iter().collect())
+    }
+
+    /// Returns `true` if the group contains the client.
+    pub fn contains(&self, client_id: ClientId) -> bool {
+        self.0.contains(&client_id)
+    }
+}
+
+/// Stores named client groups.
+///
+/// Members of disconnected clients are automatically removed from all groups.
+/// Groups are resolved when events are sent, so modification of a group
+/// affects buffered events that weren't sent yet.
+#[derive(Resource, Default)]
+pub struct ClientGroupRegistry {
+    groups: Vec<(String, ClientGroup)>,
+}
+
+impl ClientGroupRegistry {
+    /// Registers a new group and returns its ID.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        clients: impl IntoIterator<Item = ClientId>,
+    ) -> GroupId {
+        let group_id = GroupId(self.groups.len());
+        self.groups.push((name.into(), ClientGroup::new(clients)));
+        group_id
+    }
+
+    /// Returns members of the group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group wasn't registered in this registry.
+    pub fn get(&self, group_id: GroupId) -> &ClientGroup {
+        &self.groups[group_id.0].1
+    }
+
+    /// Adds a client to the group.
+    ///
+    /// Returns `true` if the client wasn't a member.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group wasn't registered in this registry.
+    pub fn add_member(&mut self, group_id: GroupId, client_id: ClientId) -> bool {
+        let (_, group) = &mut self.groups[group_id.0];
+        group.0.insert(client_id)
+    }
+
+    /// Removes a client from the group.
+    ///
+    /// Returns `true` if the client was a member.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group wasn't registered in this registry.
+    pub fn remove_member(&mut self, group_id: GroupId, client_id: ClientId) -> bool {
+        let (_, group) = &mut self.groups[group_id.0];
+        group.0.remove(&client_id)
+    }
+
+    /// Removes a client from all groups.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        for (_, group) in &mut self.groups {
+            group.0.remove(&client_id);
+        }
+    }
+
+    /// Returns an iterator over all groups with their IDs and names.
+    pub fn iter_groups(&self) -> impl Iterator<Item = (GroupId, &str, &ClientGroup)> {
+        self.groups
+            .iter()
+            .enumerate()
+            .map(|(index, (name, group))| (GroupId(index), name.as_str(), group))
+    }
+}
+
+/// Identifier of a group in [`ClientGroupRegistry`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GroupId(usize);
+
 /// Stores all received events from server that arrived earlier then replication message with their tick.
 ///
 /// Stores data sorted by ticks and maintains order of arrival.
@@ -934,3 +1306,48 @@ pub fn default_deserialize_mapped<E: Event + DeserializeOwned + MapEntities>(
 
     Ok(event)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffered_stats() {
+        let mut buffered_events = BufferedServerEvents::default();
+        assert_eq!(buffered_events.event_count(), 0);
+        assert_eq!(buffered_events.byte_estimate(), 0);
+
+        buffered_events.start_tick();
+        buffered_events.insert(SendMode::Broadcast, 0, SerializedMessage::Raw(vec![0; 6]));
+        buffered_events.start_tick();
+        buffered_events.insert(SendMode::Broadcast, 0, SerializedMessage::Raw(vec![0; 5]));
+        buffered_events.insert(SendMode::Broadcast, 1, SerializedMessage::Raw(vec![0; 4]));
+        assert_eq!(buffered_events.event_count(), 3);
+        assert_eq!(buffered_events.byte_estimate(), 15);
+
+        buffered_events.clear();
+        assert_eq!(buffered_events.event_count(), 0);
+        assert_eq!(buffered_events.byte_estimate(), 0);
+    }
+
+    #[test]
+    fn group_registry() {
+        const CLIENT_ID: ClientId = ClientId::new(1);
+
+        let mut registry = ClientGroupRegistry::default();
+        let group_id = registry.register("dummy", []);
+
+        assert!(registry.add_member(group_id, CLIENT_ID));
+        assert!(!registry.add_member(group_id, CLIENT_ID));
+        assert!(registry.get(group_id).contains(CLIENT_ID));
+
+        let (id, name, group) = registry.iter_groups().next().unwrap();
+        assert_eq!(id, group_id);
+        assert_eq!(name, "dummy");
+        assert_eq!(group.len(), 1);
+
+        registry.remove_client(CLIENT_ID);
+        assert!(!registry.remove_member(group_id, CLIENT_ID));
+        assert!(registry.get(group_id).is_empty());
+    }
+}
@@ -1,14 +1,90 @@
 pub mod command_markers;
 pub mod deferred_entity;
+pub mod prediction_registry;
 pub mod replicated_clients;
+pub mod replicated_resources;
 pub mod replication_registry;
 pub mod replication_rules;
 pub mod track_mutate_messages;
 pub mod update_message_flags;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 
 /// Marks entity for replication.
 #[derive(Component, Clone, Copy, Default, Reflect, Debug)]
 #[reflect(Component)]
 pub struct Replicated;
+
+/// Excludes a [`Replicated`] entity from change detection for clients that already received it.
+///
+/// Useful for stationary entities that rarely change. Clients that just connected or gained visibility
+/// will still receive the entity. Once the marker is removed, all changes made while it was present will
+/// be sent as regular mutations.
+///
+/// Removals are replicated regardless of this marker.
+///
+/// Can be inserted manually or automatically via
+/// [`AppRuleExt::replicate_with_sleep_threshold`](replication_rules::AppRuleExt::replicate_with_sleep_threshold).
+#[derive(Component, Clone, Copy, Default, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ReplicationSleeping;
+
+/// Temporarily stops replication of a [`Replicated`] entity without despawning it on clients.
+///
+/// Unlike removing [`Replicated`], clients keep the entity, but receive no changes for it.
+/// Once the marker is removed, the entire entity state will be re-sent as insertions.
+///
+/// Removals and despawns are replicated regardless of this marker.
+#[derive(Component, Clone, Copy, Default, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ReplicationPaused;
+
+/// Constrains the order in which a [`Replicated`] entity is written into replication messages.
+///
+/// Clients apply entities in the order they were written, so this can be used to guarantee that
+/// one entity is processed before another (for example, a vehicle before its passengers).
+/// Targets that aren't replicated are ignored. If constraints form a cycle, a warning is logged
+/// and entities are sent in arbitrary order.
+#[derive(Component, Clone, Copy, Default, Reflect, Debug)]
+#[reflect(Component)]
+pub struct EntityReplicationOrder {
+    /// Entity that should be written after this one.
+    pub before: Option<Entity>,
+
+    /// Entity that should be written before this one.
+    pub after: Option<Entity>,
+}
+
+/// A client event that requests the server to re-send the entire state of all visible entities.
+///
+/// Useful to recover from a corrupted client state.
+/// Ignored unless [`ServerPlugin::allow_snapshot_requests`](crate::server::ServerPlugin::allow_snapshot_requests)
+/// is enabled.
+#[derive(Event, Clone, Copy, Default, Debug, Deserialize, Serialize)]
+pub struct ClientSnapshotRequest;
+
+/// Priority of a [`Replicated`] entity for sending mutations under a limited bandwidth.
+///
+/// If [`ServerPlugin::bandwidth_budget_bytes_per_tick`](crate::server::ServerPlugin::bandwidth_budget_bytes_per_tick)
+/// is set, mutations for entities with higher priority are sent first. Entities without this component
+/// have priority 0.
+///
+/// If [`ServerPlugin::entity_replication_limit`](crate::server::ServerPlugin::entity_replication_limit)
+/// is set, entities with higher priority are collected first.
+///
+/// Has no effect if neither the budget nor the limit is set.
+#[derive(Component, Clone, Copy, Default, Reflect, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Component)]
+pub struct ReplicationPriority(pub u32);
+
+/// Tags of a [`Replicated`] entity for [`VisibilityPolicy::TagBased`](replicated_clients::VisibilityPolicy::TagBased).
+///
+/// The entity is visible to a client if any of its tags is present in the client's
+/// [`ClientTagFilter`](replicated_clients::client_tag_filter::ClientTagFilter).
+///
+/// Has no effect with other policies.
+#[derive(Component, Clone, Default, Reflect, Debug, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct ReplicationTags(pub SmallVec<[u64; 4]>);
@@ -27,6 +27,9 @@ pub trait AppMarkerExt {
     fn register_marker<M: Component>(&mut self) -> &mut Self;
 
     /// Same as [`Self::register_marker`], but also accepts marker configuration.
+    ///
+    /// If multiple markers with the same priority are present on an entity, the selected one
+    /// is determined by [`MarkerConfig::tiebreaker`] of the registered marker.
     fn register_marker_with<M: Component>(&mut self, config: MarkerConfig) -> &mut Self;
 
     /**
@@ -84,9 +87,7 @@ pub trait AppMarkerExt {
         if let Some(mut history) = entity.get_mut::<History<C>>() {
             history.insert(ctx.message_tick, component);
         } else {
-            ctx.commands
-                .entity(entity.id())
-                .insert(History([(ctx.message_tick, component)].into()));
+            entity.insert(History([(ctx.message_tick, component)].into()));
         }
 
         Ok(())
@@ -181,12 +182,17 @@ impl CommandMarkers {
     /// May invalidate previously returned [`CommandMarkerIndex`] due to sorting.
     ///
     /// Use [`ReplicationRegistry::register_marker`] to register a slot for command functions for this marker.
+    /// Markers with equal priority are ordered according to [`MarkerConfig::tiebreaker`] of the inserted marker.
     fn insert(&mut self, marker: CommandMarker) -> CommandMarkerIndex {
         let key = Reverse(marker.config.priority);
-        let index = self
-            .0
-            .binary_search_by_key(&key, |marker| Reverse(marker.config.priority))
-            .unwrap_or_else(|index| index);
+        let index = match marker.config.tiebreaker {
+            TiebreakerPolicy::First | TiebreakerPolicy::Error => self
+                .0
+                .partition_point(|other| Reverse(other.config.priority) <= key),
+            TiebreakerPolicy::Last => self
+                .0
+                .partition_point(|other| Reverse(other.config.priority) < key),
+        };
 
         self.0.insert(index, marker);
 
@@ -240,6 +246,30 @@ pub struct MarkerConfig {
     ///
     /// By default set to `false`.
     pub need_history: bool,
+
+    /// Decides which marker will be used if another marker with the same priority is present on an entity.
+    ///
+    /// The policy is applied when the marker is registered, relative to the already registered
+    /// markers with the same priority. So if these markers have different policies, the last
+    /// registered marker with [`TiebreakerPolicy::Last`] wins. If there is no such marker,
+    /// the first registered marker wins.
+    ///
+    /// By default set to [`TiebreakerPolicy::First`].
+    pub tiebreaker: TiebreakerPolicy,
+}
+
+/// Resolves conflicts between markers with the same [`MarkerConfig::priority`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TiebreakerPolicy {
+    /// Use the marker that was registered first.
+    #[default]
+    First,
+    /// Use the marker that was registered last.
+    Last,
+    /// Panic if `debug_assertions` are enabled and another marker with the same priority is present on an entity.
+    ///
+    /// Otherwise behaves like [`Self::First`].
+    Error,
 }
 
 /// Stores which markers are present on an entity.
@@ -258,11 +288,28 @@ impl EntityMarkers {
         self.need_history = false;
 
         let entity = entity.into();
+        let mut last_present: Option<&CommandMarker> = None;
         for marker in &markers.0 {
             let contains = entity.contains_id(marker.component_id);
             self.markers.push(contains);
-            if contains && marker.config.need_history {
-                self.need_history = true;
+            if contains {
+                if marker.config.need_history {
+                    self.need_history = true;
+                }
+
+                if let Some(last_marker) = last_present {
+                    debug_assert!(
+                        last_marker.config.priority != marker.config.priority
+                            || (last_marker.config.tiebreaker != TiebreakerPolicy::Error
+                                && marker.config.tiebreaker != TiebreakerPolicy::Error),
+                        "markers {:?} and {:?} with priority {} can't be present on {:?} at the same time",
+                        last_marker.component_id,
+                        marker.component_id,
+                        marker.config.priority,
+                        entity.id(),
+                    );
+                }
+                last_present = Some(marker);
             }
         }
     }
@@ -340,6 +387,48 @@ mod tests {
         assert_eq!(priorities, [2, 1, 0, 0]);
     }
 
+    #[test]
+    fn tiebreaker() {
+        let mut app = App::new();
+        app.init_resource::<CommandMarkers>()
+            .init_resource::<ReplicationRegistry>()
+            .register_marker::<DummyMarkerA>()
+            .register_marker::<DummyMarkerB>()
+            .register_marker_with::<DummyMarkerC>(MarkerConfig {
+                tiebreaker: TiebreakerPolicy::Last,
+                ..Default::default()
+            })
+            .register_marker::<DummyMarkerD>();
+
+        let marker_a = app.world().component_id::<DummyMarkerA>().unwrap();
+        let marker_b = app.world().component_id::<DummyMarkerB>().unwrap();
+        let marker_c = app.world().component_id::<DummyMarkerC>().unwrap();
+        let marker_d = app.world().component_id::<DummyMarkerD>().unwrap();
+
+        let markers = app.world().resource::<CommandMarkers>();
+        let components: Vec<_> = markers.0.iter().map(|marker| marker.component_id).collect();
+        assert_eq!(components, [marker_c, marker_a, marker_b, marker_d]);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be present")]
+    fn tiebreaker_error() {
+        let mut app = App::new();
+        app.init_resource::<CommandMarkers>()
+            .init_resource::<ReplicationRegistry>()
+            .register_marker_with::<DummyMarkerA>(MarkerConfig {
+                tiebreaker: TiebreakerPolicy::Error,
+                ..Default::default()
+            })
+            .register_marker::<DummyMarkerB>();
+
+        let entity = app.world_mut().spawn((DummyMarkerA, DummyMarkerB)).id();
+
+        let mut entity_markers = EntityMarkers::from_world(app.world_mut());
+        let markers = app.world().resource::<CommandMarkers>();
+        entity_markers.read(markers, app.world().entity(entity));
+    }
+
     #[derive(Component)]
     struct DummyMarkerA;
 
@@ -1,24 +1,45 @@
-use bevy::{ecs::world::CommandQueue, prelude::*};
+use std::{
+    alloc::{self, Layout},
+    mem,
+    ptr::NonNull,
+};
+
+use bevy::{
+    ecs::{component::ComponentId, world::CommandQueue},
+    prelude::*,
+    ptr::OwningPtr,
+};
 
 /// An entity reference that disallows structural ECS changes.
 ///
-/// Similar to [`EntityMut`], but additionally provides a read-only access to the world.
+/// Similar to [`EntityMut`], but additionally provides a read-only access to the world
+/// and buffers component insertions.
 #[derive(Deref, DerefMut)]
 pub struct DeferredEntity<'w> {
     #[deref]
     entity: EntityMut<'w>,
     world: &'w World,
+    changes: &'w mut DeferredChanges,
 }
 
 impl<'w> DeferredEntity<'w> {
-    pub(crate) fn new(world: &'w mut World, entity: Entity) -> Self {
+    pub(crate) fn new(
+        world: &'w mut World,
+        changes: &'w mut DeferredChanges,
+        entity: Entity,
+    ) -> Self {
+        changes.clear();
         let world_cell = world.as_unsafe_world_cell();
         // SAFETY: access split, `EntityMut` cannot make structural ECS changes,
         // and the world cannot be accessed simultaneously with the entity.
         unsafe {
             let entity: EntityMut = world_cell.world_mut().entity_mut(entity).into();
             let world = world_cell.world();
-            Self { entity, world }
+            Self {
+                entity,
+                world,
+                changes,
+            }
         }
     }
 
@@ -26,8 +47,165 @@ impl<'w> DeferredEntity<'w> {
         Commands::new_from_entities(queue, self.world.entities())
     }
 
+    /// Buffers a component insertion.
+    ///
+    /// All buffered components will be inserted together after the entity is processed
+    /// to move the entity into a new archetype only once.
+    /// Buffering the same component twice replaces the previous value.
+    ///
+    /// Buffered components are inserted before commands from [`Commands`] are applied,
+    /// regardless of the call order. So if a command inserts the same component,
+    /// its value will overwrite the buffered one.
+    pub fn insert<C: Component>(&mut self, component: C) -> &mut Self {
+        if let Some(component_id) = self.world.component_id::<C>() {
+            self.changes.insert(component_id, component);
+        } else {
+            // Component is not registered yet, insert it separately.
+            self.changes
+                .unregistered
+                .push(Box::new(move |entity: &mut EntityWorldMut| {
+                    entity.insert(component);
+                }));
+        }
+
+        self
+    }
+
     /// Gets read-only access to the world that the current entity belongs to.
     pub fn world(&self) -> &World {
         self.world
     }
 }
+
+/// Component insertions buffered by [`DeferredEntity`].
+///
+/// Stored separately to reuse allocations between entities.
+#[derive(Default)]
+pub(crate) struct DeferredChanges {
+    ids: Vec<ComponentId>,
+    components: Vec<BoxedComponent>,
+    unregistered: Vec<Box<dyn FnOnce(&mut EntityWorldMut) + Send + Sync>>,
+}
+
+impl DeferredChanges {
+    fn insert<C: Component>(&mut self, component_id: ComponentId, component: C) {
+        let component = BoxedComponent::new(component);
+        if let Some(index) = self.ids.iter().position(|&id| id == component_id) {
+            let old_component = mem::replace(&mut self.components[index], component);
+            // SAFETY: the data wasn't moved out.
+            unsafe { old_component.drop_data() };
+        } else {
+            self.ids.push(component_id);
+            self.components.push(component);
+        }
+    }
+
+    /// Inserts all buffered components into the entity.
+    pub(crate) fn apply(&mut self, world: &mut World, entity: Entity) {
+        if self.components.is_empty() && self.unregistered.is_empty() {
+            return;
+        }
+
+        // Materialize entities reserved by commands before the archetype move.
+        world.flush();
+        let mut entity = world.entity_mut(entity);
+
+        if !self.components.is_empty() {
+            // Take components out first, so they will be leaked instead of dropped twice on panic.
+            let mut components = mem::take(&mut self.components);
+            let ptrs = components.iter().map(|component| {
+                // SAFETY: the pointer is non-null, properly aligned and points to an initialized value
+                // owned by `BoxedComponent`, which won't be accessed after moving out.
+                unsafe { OwningPtr::new(component.ptr) }
+            });
+
+            // SAFETY: each pointer points to a valid value of the component with the matching ID.
+            unsafe { entity.insert_by_ids(&self.ids, ptrs) };
+
+            for component in components.drain(..) {
+                // SAFETY: the data was moved into the entity.
+                unsafe { component.dealloc() };
+            }
+            self.ids.clear();
+            self.components = components;
+        }
+
+        for insert in self.unregistered.drain(..) {
+            (insert)(&mut entity);
+        }
+    }
+
+    /// Drops all buffered components without inserting them.
+    fn clear(&mut self) {
+        for component in self.components.drain(..) {
+            // SAFETY: the data wasn't moved out.
+            unsafe { component.drop_data() };
+        }
+        self.ids.clear();
+        self.unregistered.clear();
+    }
+}
+
+impl Drop for DeferredChanges {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Type-erased heap-allocated component.
+///
+/// Doesn't drop the data automatically because it could be moved out by pointer.
+struct BoxedComponent {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    drop_fn: unsafe fn(NonNull<u8>),
+}
+
+impl BoxedComponent {
+    fn new<C: Component>(component: C) -> Self {
+        Self {
+            // Leaked to manage the allocation manually, it's freed
+            // by either `Self::drop_data` or `Self::dealloc`.
+            ptr: NonNull::from(Box::leak(Box::new(component))).cast(),
+            layout: Layout::new::<C>(),
+            drop_fn: drop_boxed::<C>,
+        }
+    }
+
+    /// Drops the data and deallocates the memory.
+    ///
+    /// # Safety
+    ///
+    /// The data must not be moved out.
+    unsafe fn drop_data(self) {
+        (self.drop_fn)(self.ptr);
+    }
+
+    /// Deallocates the memory without dropping the data.
+    ///
+    /// # Safety
+    ///
+    /// The data must be moved out.
+    unsafe fn dealloc(self) {
+        // Zero-sized values are not allocated by `Box`.
+        if self.layout.size() != 0 {
+            // SAFETY: the memory was allocated by `Box` with the global allocator
+            // and the same layout in `Self::new`.
+            alloc::dealloc(self.ptr.as_ptr(), self.layout);
+        }
+    }
+}
+
+// SAFETY: the data is always a component, which is `Send + Sync`.
+unsafe impl Send for BoxedComponent {}
+unsafe impl Sync for BoxedComponent {}
+
+/// Drops a value of type `C` allocated by `Box`.
+///
+/// # Safety
+///
+/// The pointer must be obtained from [`Box::leak`] for a value of type `C`.
+unsafe fn drop_boxed<C>(ptr: NonNull<u8>) {
+    // SAFETY: the pointer was leaked from a `Box<C>`.
+    drop(Box::from_raw(ptr.cast::<C>().as_ptr()));
+}
@@ -0,0 +1,128 @@
+use std::{
+    any::{self, TypeId},
+    mem,
+};
+
+use bevy::{ecs::component::ComponentId, prelude::*, utils::HashMap};
+
+/// Stores prediction functions for replicated components.
+///
+/// Registered via [`AppRuleExt::replicate_with_prediction_fns`](super::replication_rules::AppRuleExt::replicate_with_prediction_fns).
+/// Doesn't affect replication itself, but lets client-side prediction crates extrapolate
+/// component values without depending on the networking layer.
+#[derive(Resource, Default)]
+pub struct PredictionRegistry {
+    predict_fns: HashMap<ComponentId, UntypedPredictFn>,
+}
+
+impl PredictionRegistry {
+    /// Associates a prediction function with a component.
+    ///
+    /// Calling it again for the same component replaces the previous function.
+    pub(super) fn insert<C: Component>(&mut self, world: &mut World, predict: PredictFn<C>) {
+        let component_id = world.register_component::<C>();
+        self.predict_fns
+            .insert(component_id, UntypedPredictFn::new(predict));
+    }
+
+    /// Returns `true` if a prediction function is registered for the component.
+    pub fn contains(&self, component_id: ComponentId) -> bool {
+        self.predict_fns.contains_key(&component_id)
+    }
+
+    /// Predicts the value of `C` on `entity` after `delta_ticks`.
+    ///
+    /// Returns [`None`] if no prediction function is registered for `C`
+    /// or the entity doesn't have the component.
+    pub fn predict<C: Component>(
+        &self,
+        world: &World,
+        entity: Entity,
+        delta_ticks: u32,
+    ) -> Option<C> {
+        let component_id = world.component_id::<C>()?;
+        let predict_fn = self.predict_fns.get(&component_id)?;
+        let component = world.get::<C>(entity)?;
+
+        // SAFETY: the function was registered for the component ID of `C`.
+        Some(unsafe { predict_fn.predict(component, delta_ticks) })
+    }
+}
+
+/// Type-erased version of [`PredictFn`].
+struct UntypedPredictFn {
+    type_id: TypeId,
+    type_name: &'static str,
+
+    predict: unsafe fn(),
+}
+
+impl UntypedPredictFn {
+    fn new<C: Component>(predict: PredictFn<C>) -> Self {
+        Self {
+            type_id: TypeId::of::<C>(),
+            type_name: any::type_name::<C>(),
+            // SAFETY: the function won't be called until the type is restored.
+            predict: unsafe { mem::transmute::<PredictFn<C>, unsafe fn()>(predict) },
+        }
+    }
+
+    /// Calls the assigned prediction function.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the function is called with the same `C` with which this instance was created.
+    unsafe fn predict<C: Component>(&self, component: &C, delta_ticks: u32) -> C {
+        debug_assert_eq!(
+            self.type_id,
+            TypeId::of::<C>(),
+            "trying to call a prediction function with `{}`, but it was created with `{}`",
+            any::type_name::<C>(),
+            self.type_name,
+        );
+
+        let predict: PredictFn<C> = unsafe { mem::transmute(self.predict) };
+        (predict)(component, delta_ticks)
+    }
+}
+
+/// Signature of component prediction functions.
+///
+/// Receives the last known value and the number of ticks to extrapolate.
+pub type PredictFn<C> = fn(&C, u32) -> C;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predict() {
+        let mut world = World::new();
+        let mut registry = PredictionRegistry::default();
+        registry.insert::<Position>(&mut world, predict_position);
+
+        let entity = world.spawn(Position(1.0)).id();
+        let position = registry.predict::<Position>(&world, entity, 3).unwrap();
+        assert_eq!(position.0, 4.0);
+
+        let empty_entity = world.spawn_empty().id();
+        assert!(registry
+            .predict::<Position>(&world, empty_entity, 3)
+            .is_none());
+
+        let other_entity = world.spawn(Velocity).id();
+        assert!(registry
+            .predict::<Velocity>(&world, other_entity, 3)
+            .is_none());
+    }
+
+    #[derive(Component)]
+    struct Position(f32);
+
+    #[derive(Component)]
+    struct Velocity;
+
+    fn predict_position(position: &Position, delta_ticks: u32) -> Position {
+        Position(position.0 + delta_ticks as f32)
+    }
+}
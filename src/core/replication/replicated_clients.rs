@@ -1,4 +1,6 @@
+pub mod client_tag_filter;
 pub mod client_visibility;
+pub mod replication_queue;
 
 use std::mem;
 
@@ -10,7 +12,9 @@ use bevy::{
 
 use crate::core::{replicon_tick::RepliconTick, ClientId};
 
+use client_tag_filter::ClientTagFilter;
 use client_visibility::ClientVisibility;
+use replication_queue::ReplicationQueue;
 
 /// Stores information about connected clients which are enabled for replication.
 ///
@@ -177,6 +181,9 @@ pub struct ReplicatedClient {
     /// Entity visibility settings.
     visibility: ClientVisibility,
 
+    /// Tags for [`VisibilityPolicy::TagBased`].
+    tag_filter: ClientTagFilter,
+
     /// The last tick in which a replicated entity had an insertion, removal, or gained/lost a component from the
     /// perspective of the client.
     ///
@@ -191,6 +198,20 @@ pub struct ReplicatedClient {
     ///
     /// See also [`Self::register_mutate_message`].
     next_mutate_index: u16,
+
+    /// Statistics for mutate messages sent in the last tick.
+    mutate_stats: MutateSendStats,
+
+    /// Server entity used as the client's point of view.
+    ///
+    /// See also [`Self::set_camera_entity`].
+    camera_entity: Option<Entity>,
+
+    /// Entities deferred by [`ServerPlugin::entity_replication_limit`](crate::server::ServerPlugin::entity_replication_limit).
+    replication_queue: ReplicationQueue,
+
+    /// Indicates that the client just started replication and should receive all replicated resources.
+    resend_resources: bool,
 }
 
 impl ReplicatedClient {
@@ -199,9 +220,14 @@ impl ReplicatedClient {
             id,
             mutation_ticks: Default::default(),
             visibility: ClientVisibility::new(policy),
+            tag_filter: Default::default(),
             update_tick: Default::default(),
             mutations: Default::default(),
             next_mutate_index: Default::default(),
+            mutate_stats: Default::default(),
+            camera_entity: None,
+            replication_queue: Default::default(),
+            resend_resources: true,
         }
     }
 
@@ -220,6 +246,18 @@ impl ReplicatedClient {
         &mut self.visibility
     }
 
+    /// Returns a reference to the client's tags.
+    pub fn tag_filter(&self) -> &ClientTagFilter {
+        &self.tag_filter
+    }
+
+    /// Returns a mutable reference to the client's tags.
+    ///
+    /// Used only with [`VisibilityPolicy::TagBased`].
+    pub fn tag_filter_mut(&mut self) -> &mut ClientTagFilter {
+        &mut self.tag_filter
+    }
+
     /// Sets the client's update tick.
     pub(crate) fn set_update_tick(&mut self, tick: RepliconTick) {
         self.update_tick = tick;
@@ -231,6 +269,51 @@ impl ReplicatedClient {
         self.update_tick
     }
 
+    /// Sets statistics for mutate messages sent in this tick.
+    pub(crate) fn set_mutate_stats(&mut self, stats: MutateSendStats) {
+        self.mutate_stats = stats;
+    }
+
+    /// Returns statistics for mutate messages sent to this client in the last replication tick.
+    ///
+    /// Can be used for per-client bandwidth accounting.
+    pub fn mutate_stats(&self) -> MutateSendStats {
+        self.mutate_stats
+    }
+
+    /// Sets the entity whose [`Transform`] is used as the client's point of view.
+    ///
+    /// Used to select the level of detail for components registered with
+    /// [`AppRuleExt::replicate_with_lod`](crate::core::replication::replication_rules::AppRuleExt::replicate_with_lod).
+    /// Without a camera, the lowest level of detail is used.
+    pub fn set_camera_entity(&mut self, entity: Option<Entity>) {
+        self.camera_entity = entity;
+    }
+
+    /// Returns the entity set with [`Self::set_camera_entity`].
+    pub fn camera_entity(&self) -> Option<Entity> {
+        self.camera_entity
+    }
+
+    /// Returns entities whose replication was deferred to the next ticks.
+    pub fn replication_queue(&self) -> &ReplicationQueue {
+        &self.replication_queue
+    }
+
+    pub(crate) fn replication_queue_mut(&mut self) -> &mut ReplicationQueue {
+        &mut self.replication_queue
+    }
+
+    /// Returns `true` if the client should receive all replicated resources.
+    pub(crate) fn resend_resources(&self) -> bool {
+        self.resend_resources
+    }
+
+    /// Returns `true` if the client should receive all replicated resources and resets the flag.
+    pub(crate) fn take_resend_resources(&mut self) -> bool {
+        mem::take(&mut self.resend_resources)
+    }
+
     /// Clears all entities for unacknowledged mutate messages, returning them as an iterator.
     ///
     /// Keeps the allocated memory for reuse.
@@ -246,9 +329,14 @@ impl ReplicatedClient {
     fn reset(&mut self, id: ClientId) {
         self.id = id;
         self.visibility.clear();
+        self.tag_filter = Default::default();
         self.mutation_ticks.clear();
         self.mutations.clear();
         self.next_mutate_index = 0;
+        self.mutate_stats = Default::default();
+        self.camera_entity = None;
+        self.replication_queue.clear();
+        self.resend_resources = true;
     }
 
     /// Registers mutate message at specified `tick` and `timestamp` and returns its index with entities to fill.
@@ -286,6 +374,17 @@ impl ReplicatedClient {
     /// need to be replicated. Component mutations older than the update tick are assumed to be acked by the client.
     pub(crate) fn set_mutation_tick(&mut self, entity: Entity, tick: Tick) {
         self.mutation_ticks.insert(entity, tick);
+        self.visibility.mark_received(entity);
+    }
+
+    /// Clears the mutation tick for an entity, so its entire state will be sent as insertions.
+    pub(crate) fn clear_mutation_tick(&mut self, entity: Entity) {
+        self.mutation_ticks.remove(&entity);
+    }
+
+    /// Clears mutation ticks for all entities, so their entire state will be sent as insertions.
+    pub(crate) fn clear_mutation_ticks(&mut self) {
+        self.mutation_ticks.clear();
     }
 
     /// Gets the mutation tick for an entity that is replicated to this client.
@@ -391,6 +490,26 @@ struct MutateInfo {
     entities: Vec<Entity>,
 }
 
+/// Statistics for mutate messages sent to a client in a single tick.
+///
+/// See also [`ReplicatedClient::mutate_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MutateSendStats {
+    /// Number of sent mutate messages.
+    pub messages_count: usize,
+
+    /// Total size of sent mutate messages in bytes.
+    pub total_bytes: usize,
+
+    /// Number of mutated entities across all sent mutate messages.
+    pub entities_count: usize,
+
+    /// Number of mutated entities that didn't fit into the bandwidth budget and will be sent later.
+    ///
+    /// See also [`ServerPlugin::bandwidth_budget_bytes_per_tick`](crate::server::ServerPlugin::bandwidth_budget_bytes_per_tick).
+    pub deferred_entities: usize,
+}
+
 /// Controls how visibility will be managed via [`ClientVisibility`].
 #[derive(Default, Debug, Clone, Copy)]
 pub enum VisibilityPolicy {
@@ -401,4 +520,15 @@ pub enum VisibilityPolicy {
     Blacklist,
     /// All entities are hidden by default and should be explicitly registered to be visible.
     Whitelist,
+    /// Same as [`Self::Whitelist`], but children automatically mirror the visibility of their [`Parent`].
+    ///
+    /// Visibility should be set only for root entities. Changes are propagated to all
+    /// descendants before sending replication.
+    Hierarchical,
+    /// Entities are visible to a client if any of their [`ReplicationTags`](super::ReplicationTags)
+    /// is present in the client's [`ClientTagFilter`].
+    ///
+    /// Visibility is computed from tags while collecting changes and can't be changed via [`ClientVisibility`],
+    /// which only tracks entities received by the client. Entities without tags are hidden.
+    TagBased,
 }
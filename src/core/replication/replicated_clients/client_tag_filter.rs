@@ -0,0 +1,72 @@
+use smallvec::SmallVec;
+
+/// Tags that a client is interested in for [`VisibilityPolicy::TagBased`](super::VisibilityPolicy::TagBased).
+///
+/// An entity is visible to the client if any of its [`ReplicationTags`](crate::core::replication::ReplicationTags)
+/// is present in this filter.
+#[derive(Default, Debug, Clone)]
+pub struct ClientTagFilter {
+    tags: SmallVec<[u64; 4]>,
+}
+
+impl ClientTagFilter {
+    /// Adds a tag to the filter.
+    ///
+    /// Does nothing if the tag is already present.
+    pub fn add_tag(&mut self, tag: u64) {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Removes a tag from the filter.
+    ///
+    /// Does nothing if the tag is not present.
+    pub fn remove_tag(&mut self, tag: u64) {
+        if let Some(index) = self.tags.iter().position(|&filter_tag| filter_tag == tag) {
+            self.tags.swap_remove(index);
+        }
+    }
+
+    /// Removes all tags from the filter.
+    pub fn clear(&mut self) {
+        self.tags.clear();
+    }
+
+    /// Returns all tags from the filter.
+    pub fn tags(&self) -> &[u64] {
+        &self.tags
+    }
+
+    /// Returns `true` if any of the `tags` is present in the filter.
+    pub fn intersects(&self, tags: &[u64]) -> bool {
+        tags.iter().any(|tag| self.tags.contains(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersection() {
+        let mut filter = ClientTagFilter::default();
+        assert!(!filter.intersects(&[1, 2]));
+
+        filter.add_tag(2);
+        filter.add_tag(2);
+        assert_eq!(filter.tags(), [2]);
+        assert!(filter.intersects(&[1, 2]));
+        assert!(!filter.intersects(&[3]));
+
+        filter.remove_tag(3);
+        assert_eq!(filter.tags(), [2]);
+
+        filter.remove_tag(2);
+        assert!(!filter.intersects(&[1, 2]));
+
+        filter.add_tag(1);
+        filter.clear();
+        assert!(filter.tags().is_empty());
+    }
+}
@@ -16,16 +16,21 @@ impl ClientVisibility {
     pub(super) fn new(policy: VisibilityPolicy) -> Self {
         match policy {
             VisibilityPolicy::All => Self::with_filter(VisibilityFilter::All),
-            VisibilityPolicy::Blacklist => Self::with_filter(VisibilityFilter::Blacklist {
-                list: Default::default(),
-                added: Default::default(),
-                removed: Default::default(),
+            VisibilityPolicy::TagBased => Self::with_filter(VisibilityFilter::Tags {
+                received: Default::default(),
             }),
-            VisibilityPolicy::Whitelist => Self::with_filter(VisibilityFilter::Whitelist {
+            VisibilityPolicy::Blacklist => Self::with_filter(VisibilityFilter::Blacklist {
                 list: Default::default(),
                 added: Default::default(),
                 removed: Default::default(),
             }),
+            VisibilityPolicy::Whitelist | VisibilityPolicy::Hierarchical => {
+                Self::with_filter(VisibilityFilter::Whitelist {
+                    list: Default::default(),
+                    added: Default::default(),
+                    removed: Default::default(),
+                })
+            }
         }
     }
 
@@ -40,6 +45,7 @@ impl ClientVisibility {
     pub(super) fn clear(&mut self) {
         match &mut self.filter {
             VisibilityFilter::All => (),
+            VisibilityFilter::Tags { received } => received.clear(),
             VisibilityFilter::Blacklist {
                 list,
                 added,
@@ -66,7 +72,7 @@ impl ClientVisibility {
     /// Should be called after each tick.
     pub(crate) fn update(&mut self) {
         match &mut self.filter {
-            VisibilityFilter::All => (),
+            VisibilityFilter::All | VisibilityFilter::Tags { .. } => (),
             VisibilityFilter::Blacklist {
                 list,
                 added,
@@ -96,7 +102,10 @@ impl ClientVisibility {
     /// Removes a despawned entity tracked by this client.
     pub(super) fn remove_despawned(&mut self, entity: Entity) {
         match &mut self.filter {
-            VisibilityFilter::All { .. } => (),
+            VisibilityFilter::All => (),
+            VisibilityFilter::Tags { received } => {
+                received.remove(&entity);
+            }
             VisibilityFilter::Blacklist {
                 list,
                 added,
@@ -123,7 +132,7 @@ impl ClientVisibility {
     /// Drains all entities for which visibility was lost during this tick.
     pub(super) fn drain_lost(&mut self) -> impl Iterator<Item = Entity> + '_ {
         match &mut self.filter {
-            VisibilityFilter::All { .. } => VisibilityLostIter::AllVisible,
+            VisibilityFilter::All | VisibilityFilter::Tags { .. } => VisibilityLostIter::AllVisible,
             VisibilityFilter::Blacklist { added, .. } => VisibilityLostIter::Lost(added.drain()),
             VisibilityFilter::Whitelist { removed, .. } => {
                 VisibilityLostIter::Lost(removed.drain())
@@ -131,12 +140,28 @@ impl ClientVisibility {
         }
     }
 
+    /// Marks an entity as received by the client.
+    ///
+    /// Does nothing if the visibility policy for the server plugin is not [`VisibilityPolicy::TagBased`].
+    pub(super) fn mark_received(&mut self, entity: Entity) {
+        if let VisibilityFilter::Tags { received } = &mut self.filter {
+            received.insert(entity);
+        }
+    }
+
     /// Sets visibility for a specific entity.
     ///
-    /// Does nothing if the visibility policy for the server plugin is set to [`VisibilityPolicy::All`].
+    /// Does nothing if the visibility policy for the server plugin is set to [`VisibilityPolicy::All`]
+    /// or [`VisibilityPolicy::TagBased`].
     pub fn set_visibility(&mut self, entity: Entity, visible: bool) {
         match &mut self.filter {
-            VisibilityFilter::All { .. } => {
+            VisibilityFilter::Tags { .. } => {
+                warn!(
+                    "ignoring visibility change due to {:?}",
+                    VisibilityPolicy::TagBased
+                );
+            }
+            VisibilityFilter::All => {
                 if visible {
                     debug!(
                         "ignoring visibility enable due to {:?}",
@@ -217,7 +242,22 @@ impl ClientVisibility {
         }
     }
 
+    /// Returns an iterator over entities whose visibility changed during this tick.
+    pub(crate) fn iter_changed(&self) -> impl Iterator<Item = Entity> + '_ {
+        let changed = match &self.filter {
+            VisibilityFilter::All | VisibilityFilter::Tags { .. } => None,
+            VisibilityFilter::Blacklist { added, removed, .. }
+            | VisibilityFilter::Whitelist { added, removed, .. } => {
+                Some(added.iter().chain(removed))
+            }
+        };
+
+        changed.into_iter().flatten().copied()
+    }
+
     /// Checks if a specific entity is visible.
+    ///
+    /// For [`VisibilityPolicy::TagBased`] returns `true` if the client received the entity.
     pub fn is_visible(&self, entity: Entity) -> bool {
         match self.state(entity) {
             Visibility::Hidden => false,
@@ -229,6 +269,13 @@ impl ClientVisibility {
     pub(crate) fn state(&self, entity: Entity) -> Visibility {
         match &self.filter {
             VisibilityFilter::All => Visibility::Visible,
+            VisibilityFilter::Tags { received } => {
+                if received.contains(&entity) {
+                    Visibility::Visible
+                } else {
+                    Visibility::Hidden
+                }
+            }
             VisibilityFilter::Blacklist { list, .. } => match list.get(&entity) {
                 Some(BlacklistInfo::QueuedForRemoval) => Visibility::Gained,
                 Some(BlacklistInfo::Hidden) => Visibility::Hidden,
@@ -246,6 +293,13 @@ impl ClientVisibility {
 /// Filter for [`ClientVisibility`] based on [`VisibilityPolicy`].
 enum VisibilityFilter {
     All,
+    Tags {
+        /// All entities received by the client.
+        ///
+        /// Visibility itself is computed from tags during replication, but unlike mutation ticks,
+        /// this set isn't cleared when the state needs to be resent, so despawns and removals are still sent.
+        received: EntityHashSet,
+    },
     Blacklist {
         /// All blacklisted entities and an indicator of whether they are in the queue for deletion
         /// at the end of this tick.
@@ -342,6 +396,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tags() {
+        let mut visibility = ClientVisibility::new(VisibilityPolicy::TagBased);
+        assert!(!visibility.is_visible(Entity::PLACEHOLDER));
+
+        visibility.set_visibility(Entity::PLACEHOLDER, true);
+        assert!(
+            !visibility.is_visible(Entity::PLACEHOLDER),
+            "shouldn't have any effect for this policy"
+        );
+
+        visibility.mark_received(Entity::PLACEHOLDER);
+        assert!(visibility.is_visible(Entity::PLACEHOLDER));
+
+        visibility.remove_despawned(Entity::PLACEHOLDER);
+        assert!(!visibility.is_visible(Entity::PLACEHOLDER));
+    }
+
     #[test]
     fn blacklist_insertion() {
         let mut visibility = ClientVisibility::new(VisibilityPolicy::Blacklist);
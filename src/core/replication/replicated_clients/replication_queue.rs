@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+/// Entities whose replication to a client was deferred to the next ticks.
+///
+/// Filled when the number of entities collected for the client in a tick reaches
+/// [`ServerPlugin::entity_replication_limit`](crate::server::ServerPlugin::entity_replication_limit).
+/// Queued entities are collected first on the next replication tick.
+#[derive(Default, Debug)]
+pub struct ReplicationQueue {
+    entities: VecDeque<Entity>,
+
+    /// Number of entities collected for the client in the current tick.
+    collected: usize,
+}
+
+impl ReplicationQueue {
+    /// Returns the number of deferred entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if there are no deferred entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns an iterator over deferred entities in the order they were queued.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+
+    /// Returns `true` if the number of entities collected in this tick reached `limit`.
+    pub(crate) fn is_full(&self, limit: usize) -> bool {
+        self.collected >= limit
+    }
+
+    /// Counts an entity collected in this tick.
+    pub(crate) fn add_collected(&mut self) {
+        self.collected += 1;
+    }
+
+    /// Defers an entity to the next tick.
+    pub(crate) fn push(&mut self, entity: Entity) {
+        self.entities.push_back(entity);
+    }
+
+    /// Starts a new tick, returning all deferred entities as an iterator.
+    ///
+    /// Keeps the allocated memory for reuse.
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        self.collected = 0;
+        self.entities.drain(..)
+    }
+
+    /// Clears all deferred entities.
+    ///
+    /// Keeps the allocated memory for reuse.
+    pub(crate) fn clear(&mut self) {
+        self.collected = 0;
+        self.entities.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit() {
+        let mut queue = ReplicationQueue::default();
+        assert!(queue.is_full(0));
+        assert!(!queue.is_full(1));
+
+        queue.add_collected();
+        assert!(queue.is_full(1));
+
+        let entity = Entity::from_raw(0);
+        queue.push(entity);
+        assert_eq!(queue.len(), 1);
+
+        let entities: Vec<_> = queue.drain().collect();
+        assert_eq!(entities, [entity]);
+        assert!(queue.is_empty());
+        assert!(!queue.is_full(1), "drain should start a new tick");
+    }
+}
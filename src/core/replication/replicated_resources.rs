@@ -0,0 +1,306 @@
+#[cfg(feature = "client")]
+use std::any;
+use std::io::Cursor;
+#[cfg(feature = "server")]
+use std::ops::Range;
+
+use bevy::prelude::*;
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "client")]
+use crate::core::replicon_tick::RepliconTick;
+#[cfg(feature = "server")]
+use crate::core::{
+    channels::ReplicationChannel, replication::replicated_clients::ReplicatedClients,
+    replicon_server::RepliconServer,
+};
+#[cfg(feature = "client")]
+use bytes::Bytes;
+
+/// Serialization and deserialization functions for a resource.
+///
+/// See also [`AppRuleExt::replicate_resource_with`](super::replication_rules::AppRuleExt::replicate_resource_with).
+pub struct ResourceFns<R> {
+    pub(super) serialize: SerializeResourceFn<R>,
+    pub(super) deserialize: DeserializeResourceFn<R>,
+}
+
+impl<R> ResourceFns<R> {
+    /// Creates a new instance with the given functions.
+    pub fn new(serialize: SerializeResourceFn<R>, deserialize: DeserializeResourceFn<R>) -> Self {
+        Self {
+            serialize,
+            deserialize,
+        }
+    }
+}
+
+impl<R: Serialize + DeserializeOwned> Default for ResourceFns<R> {
+    fn default() -> Self {
+        Self::new(default_serialize::<R>, default_deserialize::<R>)
+    }
+}
+
+impl<R> Clone for ResourceFns<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for ResourceFns<R> {}
+
+/// Signature of resource serialization functions.
+pub type SerializeResourceFn<R> = fn(&R, &mut Vec<u8>) -> bincode::Result<()>;
+
+/// Signature of resource deserialization functions.
+pub type DeserializeResourceFn<R> = fn(&mut Cursor<&[u8]>) -> bincode::Result<R>;
+
+/// Default resource serialization function.
+pub fn default_serialize<R: Serialize>(resource: &R, message: &mut Vec<u8>) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(message, resource)
+}
+
+/// Default resource deserialization function.
+pub fn default_deserialize<R: DeserializeOwned>(cursor: &mut Cursor<&[u8]>) -> bincode::Result<R> {
+    DefaultOptions::new().deserialize_from(cursor)
+}
+
+/// Number of resources registered for replication.
+///
+/// Used to assign indices that identify resources in messages.
+#[derive(Resource, Default)]
+pub(crate) struct ReplicatedResources(usize);
+
+impl ReplicatedResources {
+    /// Returns the index for a new resource.
+    pub(crate) fn register(&mut self) -> usize {
+        let index = self.0;
+        self.0 += 1;
+        index
+    }
+
+    /// Returns the number of registered resources.
+    #[cfg(feature = "client")]
+    pub(crate) fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// Serialized resources that will be sent over [`ReplicationChannel::Resources`].
+///
+/// Filled by systems from [`collect_resource`] and sent by the server replication system right
+/// after update messages of the same tick.
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub(crate) struct ResourceBuffer {
+    /// Serialized resources with their indices.
+    data: Vec<u8>,
+
+    /// Ranges of resources inside [`Self::data`] and whether the resource changed.
+    ///
+    /// Unchanged resources are sent only to clients that just started replication.
+    entries: Vec<(Range<usize>, bool)>,
+
+    /// Buffer for serializing a resource before writing it with its size.
+    scratch: Vec<u8>,
+}
+
+#[cfg(feature = "server")]
+impl ResourceBuffer {
+    fn write(
+        &mut self,
+        index: usize,
+        changed: bool,
+        serialize: impl FnOnce(&mut Vec<u8>) -> bincode::Result<()>,
+    ) -> bincode::Result<()> {
+        self.scratch.clear();
+        (serialize)(&mut self.scratch)?;
+
+        let start = self.data.len();
+        DefaultOptions::new().serialize_into(&mut self.data, &(index, &*self.scratch))?;
+        self.entries.push((start..self.data.len(), changed));
+
+        Ok(())
+    }
+
+    /// Sends written resources to clients.
+    ///
+    /// Clients that just started replication receive all written resources and the rest only changed ones.
+    ///
+    /// Should be called after sending update messages. Each message starts with the client's last update tick,
+    /// so the client applies resources only after receiving all update messages sent before them.
+    pub(crate) fn send(
+        &mut self,
+        server: &mut RepliconServer,
+        replicated_clients: &mut ReplicatedClients,
+    ) -> bincode::Result<()> {
+        let mut all = None;
+        let mut changed = None;
+        for client in replicated_clients.iter_mut() {
+            let resources = if client.take_resend_resources() {
+                all.get_or_insert_with(|| self.collect(|_| true))
+            } else {
+                changed.get_or_insert_with(|| self.collect(|changed| changed))
+            };
+
+            if !resources.is_empty() {
+                let mut message = Vec::new();
+                bincode::serialize_into(&mut message, &client.update_tick())?;
+                message.extend_from_slice(resources);
+                server.send(client.id(), ReplicationChannel::Resources, message);
+            }
+        }
+
+        self.data.clear();
+        self.entries.clear();
+
+        Ok(())
+    }
+
+    fn collect(&self, filter: impl Fn(bool) -> bool) -> Vec<u8> {
+        let mut resources = Vec::new();
+        for (range, _) in self.entries.iter().filter(|&&(_, changed)| filter(changed)) {
+            resources.extend_from_slice(&self.data[range.clone()]);
+        }
+        resources
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.data.clear();
+        self.entries.clear();
+    }
+}
+
+/// Returns a system that writes `R` with `index` into [`ResourceBuffer`].
+///
+/// The value is written for clients that just started replication.
+/// If `once` is `false`, it's also written on each change for all other clients.
+#[cfg(feature = "server")]
+pub(super) fn collect_resource<R: Resource>(
+    index: usize,
+    fns: ResourceFns<R>,
+    once: bool,
+) -> impl FnMut(Option<Res<R>>, Res<ReplicatedClients>, ResMut<ResourceBuffer>) -> bincode::Result<()>
+{
+    move |resource, replicated_clients, mut buffer| {
+        let Some(resource) = resource else {
+            return Ok(());
+        };
+
+        // Resource inserted after clients started replication is considered changed even with `once`.
+        let changed = if once {
+            resource.is_added()
+        } else {
+            resource.is_changed()
+        };
+        if changed
+            || replicated_clients
+                .iter()
+                .any(|client| client.resend_resources())
+        {
+            buffer.write(index, changed, |message| {
+                (fns.serialize)(&resource, message)
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Latest resource values received over [`ReplicationChannel::Resources`](crate::core::channels::ReplicationChannel::Resources) by their indices.
+///
+/// Filled by the client replication system after applying update messages,
+/// values are consumed by systems from [`receive_resource`].
+#[cfg(feature = "client")]
+#[derive(Resource, Default)]
+pub(crate) struct ReceivedResources {
+    /// Messages waiting for update messages with their update ticks.
+    ///
+    /// The channel is ordered, so update ticks are non-decreasing.
+    pending: Vec<(RepliconTick, Bytes)>,
+
+    /// Received values by resource indices.
+    values: Vec<Option<Vec<u8>>>,
+}
+
+#[cfg(feature = "client")]
+impl ReceivedResources {
+    /// Buffers a message until the update message with its tick is applied.
+    pub(crate) fn insert(&mut self, message: Bytes) -> bincode::Result<()> {
+        let mut cursor = Cursor::new(&*message);
+        let update_tick = bincode::deserialize_from(&mut cursor)?;
+        self.pending
+            .push((update_tick, message.slice(cursor.position() as usize..)));
+
+        Ok(())
+    }
+
+    /// Reads all buffered messages whose update message was applied, overwriting previously received values.
+    ///
+    /// Returns an error if a message contains an index of an unregistered resource.
+    pub(crate) fn read_pending(
+        &mut self,
+        update_tick: RepliconTick,
+        registered: usize,
+    ) -> bincode::Result<()> {
+        let ready = self
+            .pending
+            .iter()
+            .position(|&(tick, _)| tick > update_tick)
+            .unwrap_or(self.pending.len());
+
+        for (_, message) in self.pending.drain(..ready) {
+            let mut cursor = Cursor::new(&*message);
+            while cursor.position() < message.len() as u64 {
+                let (index, data): (usize, Vec<u8>) =
+                    DefaultOptions::new().deserialize_from(&mut cursor)?;
+                if index >= registered {
+                    return Err(bincode::ErrorKind::Custom(format!(
+                        "resource index {index} is out of range"
+                    ))
+                    .into());
+                }
+                if index >= self.values.len() {
+                    self.values.resize(index + 1, None);
+                }
+                self.values[index] = Some(data);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn take(&mut self, index: usize) -> Option<Vec<u8>> {
+        self.values.get_mut(index).and_then(Option::take)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+        self.values.clear();
+    }
+}
+
+/// Returns a system that overwrites the local `R` with the received value with `index`.
+///
+/// Only the most recent received value is applied.
+#[cfg(feature = "client")]
+pub(super) fn receive_resource<R: Resource>(
+    index: usize,
+    fns: ResourceFns<R>,
+) -> impl FnMut(Commands, ResMut<ReceivedResources>) {
+    move |mut commands, mut received_resources| {
+        let Some(data) = received_resources.take(index) else {
+            return;
+        };
+
+        let mut cursor = Cursor::new(&*data);
+        match (fns.deserialize)(&mut cursor) {
+            Ok(resource) => commands.insert_resource(resource),
+            Err(e) => error!(
+                "unable to deserialize resource `{}`: {e}",
+                any::type_name::<R>()
+            ),
+        }
+    }
+}
@@ -1,6 +1,9 @@
+pub mod change_filter;
 pub mod command_fns;
 pub mod component_fns;
 pub mod ctx;
+pub mod delta_fns;
+pub mod quantize;
 pub mod rule_fns;
 pub mod test_fns;
 
@@ -8,10 +11,12 @@ use bevy::{ecs::component::ComponentId, prelude::*};
 use serde::{Deserialize, Serialize};
 
 use super::command_markers::CommandMarkerIndex;
+use change_filter::ChangeFilterFn;
 use command_fns::{RemoveFn, UntypedCommandFns, WriteFn};
 use component_fns::ComponentFns;
 use ctx::DespawnCtx;
-use rule_fns::{RuleFns, UntypedRuleFns};
+use delta_fns::Delta;
+use rule_fns::{DeserializeFn, RuleFns, UntypedRuleFns};
 
 /// Stores configurable replication functions.
 #[derive(Resource)]
@@ -97,6 +102,103 @@ impl ReplicationRegistry {
         }
     }
 
+    /// Assigns schema version for a component.
+    ///
+    /// Serialized data will be prefixed with `version`. On deserialization, data with an older version
+    /// will be passed to `deserialize_legacy` and data with the same or newer version to `deserialize_current`.
+    ///
+    /// Replaces the previously assigned schema for this component.
+    pub(super) fn set_schema<C: Component>(
+        &mut self,
+        world: &mut World,
+        version: u32,
+        deserialize_legacy: DeserializeFn<C>,
+        deserialize_current: DeserializeFn<C>,
+    ) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index];
+
+        // SAFETY: `component_fns` was created for `C`.
+        unsafe {
+            component_fns.set_schema(version, deserialize_legacy, deserialize_current);
+        }
+    }
+
+    /// Enables delta encoding for a component.
+    ///
+    /// See also [`AppRuleExt::replicate_with_delta`](super::replication_rules::AppRuleExt::replicate_with_delta).
+    pub(super) fn set_delta_fns<C: Delta>(&mut self, world: &mut World) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index];
+
+        // SAFETY: `component_fns` was created for `C`.
+        unsafe {
+            component_fns.set_delta_fns::<C>();
+        }
+    }
+
+    /// Assigns a change filter for a component.
+    ///
+    /// See also [`AppRuleExt::replicate_with_change_filter`](super::replication_rules::AppRuleExt::replicate_with_change_filter).
+    pub(super) fn set_change_filter<C: Component + Clone>(
+        &mut self,
+        world: &mut World,
+        equal: ChangeFilterFn<C>,
+    ) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index];
+
+        // SAFETY: `component_fns` was created for `C`.
+        unsafe {
+            component_fns.set_change_filter(equal);
+        }
+    }
+
+    /// Assigns the maximum serialized size for a component and functions to use when it's exceeded.
+    ///
+    /// See also [`AppRuleExt::replicate_with_size_limit`](super::replication_rules::AppRuleExt::replicate_with_size_limit).
+    pub(super) fn set_size_limit<C: Component>(
+        &mut self,
+        world: &mut World,
+        max_bytes: usize,
+        fallback_fns: RuleFns<C>,
+    ) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index];
+
+        // SAFETY: `component_fns` was created for `C`.
+        unsafe {
+            component_fns.set_size_limit(max_bytes, fallback_fns);
+        }
+    }
+
+    /// Assigns lower-fidelity functions with their distance thresholds for a component.
+    ///
+    /// See also [`AppRuleExt::replicate_with_lod`](super::replication_rules::AppRuleExt::replicate_with_lod).
+    pub(super) fn set_lods<C: Component>(
+        &mut self,
+        world: &mut World,
+        lods: Vec<(f32, RuleFns<C>)>,
+    ) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index];
+
+        // SAFETY: `component_fns` was created for `C`.
+        unsafe {
+            component_fns.set_lods(lods);
+        }
+    }
+
+    /// Returns the schema version of the component associated with the functions.
+    ///
+    /// Returns 0 if the component is not versioned.
+    ///
+    /// See also [`AppRuleExt::replicate_with_schema_version`](super::replication_rules::AppRuleExt::replicate_with_schema_version).
+    pub fn component_schema_version(&self, fns_id: FnsId) -> u32 {
+        let (_, component_fns, _) = self.get(fns_id);
+        component_fns.schema_version()
+    }
+
     /// Registers serialization/deserialization functions for a component.
     ///
     /// Returned data can be assigned to a
@@ -0,0 +1,170 @@
+use std::{
+    any::{self, Any},
+    mem,
+};
+
+#[cfg(feature = "server")]
+use bevy::{
+    ecs::{
+        component::{ComponentId, ComponentTicks, Tick},
+        entity::EntityHashMap,
+    },
+    utils::HashMap,
+};
+use bevy::{prelude::*, ptr::Ptr};
+
+/// Returns `true` if two component values are semantically equal.
+///
+/// See also [`AppRuleExt::replicate_with_change_filter`](crate::core::replication::replication_rules::AppRuleExt::replicate_with_change_filter).
+pub type ChangeFilterFn<C> = fn(&C, &C) -> bool;
+
+/// Type-erased component value stored for comparison by [`ChangeFilterFn`].
+pub(crate) type FilteredValue = Box<dyn Any + Send + Sync>;
+
+/// Type-erased change filter for a component.
+#[derive(Clone, Copy)]
+pub(crate) struct UntypedChangeFilter {
+    equal: unsafe fn(),
+    compare: unsafe fn(unsafe fn(), Ptr, &FilteredValue) -> bool,
+    clone: unsafe fn(Ptr) -> FilteredValue,
+}
+
+impl UntypedChangeFilter {
+    pub(super) fn new<C: Component + Clone>(equal: ChangeFilterFn<C>) -> Self {
+        // SAFETY: the function won't be called until the type is restored.
+        Self {
+            equal: unsafe { mem::transmute::<ChangeFilterFn<C>, unsafe fn()>(equal) },
+            compare: compare::<C>,
+            clone: clone_value::<C>,
+        }
+    }
+
+    /// Returns `true` if the component is equal to `value` according to the filter.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was created for the same type as this instance.
+    unsafe fn is_equal(&self, ptr: Ptr, value: &FilteredValue) -> bool {
+        (self.compare)(self.equal, ptr, value)
+    }
+
+    /// Clones the component to compare future changes with it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was created for the same type as this instance.
+    unsafe fn clone_value(&self, ptr: Ptr) -> FilteredValue {
+        (self.clone)(ptr)
+    }
+}
+
+/// Last sent values of components with a change filter.
+///
+/// Stores a single value per entity component, shared between all clients,
+/// with the tick on which the value actually changed. Writes that the filter considers equal
+/// to the stored value don't update the tick, so they aren't sent.
+/// Used on server.
+///
+/// See also [`ChangeFilterFn`].
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub(crate) struct LastSentValues(EntityHashMap<HashMap<ComponentId, LastSentValue>>);
+
+#[cfg(feature = "server")]
+impl LastSentValues {
+    /// Returns the tick on which the component last changed according to `filter`.
+    ///
+    /// Stores the current value if it differs from the last sent one.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was created for the same type as `filter`.
+    pub(crate) unsafe fn changed_tick(
+        &mut self,
+        filter: &UntypedChangeFilter,
+        entity: Entity,
+        component_id: ComponentId,
+        ptr: Ptr,
+        ticks: &ComponentTicks,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Tick {
+        let values = self.0.entry(entity).or_default();
+        match values.get_mut(&component_id) {
+            Some(last_sent) if !ticks.is_added(last_run, this_run) => {
+                if ticks.changed.is_newer_than(last_sent.tick, this_run)
+                    && !filter.is_equal(ptr, &last_sent.value)
+                {
+                    last_sent.value = filter.clone_value(ptr);
+                    last_sent.tick = ticks.changed;
+                }
+                last_sent.tick
+            }
+            _ => {
+                values.insert(
+                    component_id,
+                    LastSentValue {
+                        tick: ticks.changed,
+                        value: filter.clone_value(ptr),
+                    },
+                );
+                ticks.changed
+            }
+        }
+    }
+
+    /// Removes values of components that are no longer present in the world.
+    pub(crate) fn retain_existing(&mut self, world: &World) {
+        self.0.retain(|&entity, values| {
+            let Ok(entity) = world.get_entity(entity) else {
+                return false;
+            };
+            values.retain(|&component_id, _| entity.contains_id(component_id));
+            !values.is_empty()
+        });
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// A component value that was sent to clients.
+#[cfg(feature = "server")]
+struct LastSentValue {
+    /// System tick on which the value was changed.
+    ///
+    /// Compared with client mutation ticks instead of the component change tick.
+    tick: Tick,
+
+    value: FilteredValue,
+}
+
+/// Restores the filter function and compares `C` from a pointer with `value`.
+///
+/// # Safety
+///
+/// The caller must ensure that `equal` and `ptr` were created for `C`.
+unsafe fn compare<C: Component + Clone>(
+    equal: unsafe fn(),
+    ptr: Ptr,
+    value: &FilteredValue,
+) -> bool {
+    let equal = unsafe { mem::transmute::<unsafe fn(), ChangeFilterFn<C>>(equal) };
+    let value = value.downcast_ref::<C>().unwrap_or_else(|| {
+        panic!(
+            "last sent value should have the same type as `{}`",
+            any::type_name::<C>()
+        )
+    });
+    (equal)(ptr.deref::<C>(), value)
+}
+
+/// Clones `C` from a pointer.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` was created for `C`.
+unsafe fn clone_value<C: Component + Clone>(ptr: Ptr) -> FilteredValue {
+    Box::new(ptr.deref::<C>().clone())
+}
@@ -78,7 +78,8 @@ pub type RemoveFn = fn(&mut RemoveCtx, &mut DeferredEntity);
 
 /// Default component writing function.
 ///
-/// If the component does not exist on the entity, it will be deserialized with [`RuleFns::deserialize`] and inserted via [`Commands`].
+/// If the component does not exist on the entity, it will be deserialized with [`RuleFns::deserialize`] and inserted via
+/// [`DeferredEntity::insert`].
 /// If the component exists on the entity, [`RuleFns::deserialize_in_place`] will be used directly on the entity's component.
 pub fn default_write<C: Component>(
     ctx: &mut WriteCtx,
@@ -90,7 +91,7 @@ pub fn default_write<C: Component>(
         rule_fns.deserialize_in_place(ctx, &mut *component, cursor)?;
     } else {
         let component: C = rule_fns.deserialize(ctx, cursor)?;
-        ctx.commands.entity(entity.id()).insert(component);
+        entity.insert(component);
     }
 
     Ok(())
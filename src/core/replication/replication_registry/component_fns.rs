@@ -1,16 +1,21 @@
-use std::io::Cursor;
+use std::{io::Cursor, mem};
 
 use bevy::{prelude::*, ptr::Ptr};
+use bincode::{DefaultOptions, Options};
+use integer_encoding::{VarIntReader, VarIntWriter};
 
 use super::{
+    change_filter::{ChangeFilterFn, UntypedChangeFilter},
     command_fns::UntypedCommandFns,
     ctx::{RemoveCtx, SerializeCtx, WriteCtx},
-    rule_fns::UntypedRuleFns,
+    delta_fns::{Delta, DeltaBaseCache, DeltaValue, UntypedDeltaFns},
+    rule_fns::{DeserializeFn, RuleFns, UntypedRuleFns},
 };
 use crate::core::replication::{
     command_markers::{CommandMarkerIndex, CommandMarkers, EntityMarkers},
     deferred_entity::DeferredEntity,
 };
+use crate::core::replicon_tick::RepliconTick;
 
 /// Type-erased functions for a component.
 ///
@@ -19,8 +24,14 @@ pub(crate) struct ComponentFns {
     serialize: UntypedSerializeFn,
     write: UntypedWriteFn,
     consume: UntypedConsumeFn,
+    condition: UntypedConditionFn,
     commands: UntypedCommandFns,
     markers: Vec<Option<UntypedCommandFns>>,
+    schema: Option<ComponentSchema>,
+    delta: Option<UntypedDeltaFns>,
+    change_filter: Option<UntypedChangeFilter>,
+    size_limit: Option<SizeLimit>,
+    lods: Vec<Lod>,
 }
 
 impl ComponentFns {
@@ -30,8 +41,14 @@ impl ComponentFns {
             serialize: untyped_serialize::<C>,
             write: untyped_write::<C>,
             consume: untyped_consume::<C>,
+            condition: untyped_condition::<C>,
             commands: UntypedCommandFns::default_fns::<C>(),
             markers: vec![None; marker_slots],
+            schema: None,
+            delta: None,
+            change_filter: None,
+            size_limit: None,
+            lods: Vec::new(),
         }
     }
 
@@ -78,8 +95,134 @@ impl ComponentFns {
         self.commands = command_fns;
     }
 
+    /// Assigns schema version and deserialization functions for it.
+    ///
+    /// Replaces the previously assigned schema.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `C` is the same type for which this instance was created.
+    pub(super) unsafe fn set_schema<C: Component>(
+        &mut self,
+        version: u32,
+        deserialize_legacy: DeserializeFn<C>,
+        deserialize_current: DeserializeFn<C>,
+    ) {
+        // SAFETY: these functions won't be called until the type is restored.
+        self.schema = Some(ComponentSchema {
+            version,
+            deserialize_legacy: unsafe {
+                mem::transmute::<DeserializeFn<C>, unsafe fn()>(deserialize_legacy)
+            },
+            deserialize_current: unsafe {
+                mem::transmute::<DeserializeFn<C>, unsafe fn()>(deserialize_current)
+            },
+        });
+    }
+
+    /// Enables delta encoding for the component.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `C` is the same type for which this instance was created.
+    pub(super) unsafe fn set_delta_fns<C: Delta>(&mut self) {
+        self.delta = Some(UntypedDeltaFns::new::<C>());
+    }
+
+    /// Assigns a filter that suppresses mutations that don't change the value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `C` is the same type for which this instance was created.
+    pub(super) unsafe fn set_change_filter<C: Component + Clone>(
+        &mut self,
+        equal: ChangeFilterFn<C>,
+    ) {
+        self.change_filter = Some(UntypedChangeFilter::new(equal));
+    }
+
+    /// Assigns the maximum size of serialized data and functions that will be used
+    /// to serialize the component if the data exceeds it.
+    ///
+    /// Replaces the previously assigned limit.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `C` is the same type for which this instance was created.
+    pub(super) unsafe fn set_size_limit<C: Component>(
+        &mut self,
+        max_bytes: usize,
+        fallback_fns: RuleFns<C>,
+    ) {
+        self.size_limit = Some(SizeLimit {
+            max_bytes,
+            fallback_fns: fallback_fns.into(),
+        });
+    }
+
+    /// Assigns lower-fidelity functions with their distance thresholds.
+    ///
+    /// Thresholds should be sorted in descending order. Functions passed for the rule
+    /// are used for distances below all thresholds.
+    /// Replaces the previously assigned levels of detail.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `C` is the same type for which this instance was created.
+    pub(super) unsafe fn set_lods<C: Component>(&mut self, lods: Vec<(f32, RuleFns<C>)>) {
+        self.lods = lods
+            .into_iter()
+            .map(|(threshold, rule_fns)| Lod {
+                threshold,
+                rule_fns: rule_fns.into(),
+            })
+            .collect();
+    }
+
+    /// Returns `true` if levels of detail are assigned and the component isn't delta-encoded.
+    pub(crate) fn has_lods(&self) -> bool {
+        !self.lods.is_empty() && self.delta.is_none()
+    }
+
+    /// Returns the level of detail for the squared distance to the client's camera.
+    ///
+    /// Selects the first level whose threshold is exceeded or the highest fidelity if none is exceeded.
+    /// Without a distance, the lowest fidelity is selected.
+    pub(crate) fn select_lod(&self, distance_squared: Option<f32>) -> usize {
+        let Some(distance_squared) = distance_squared else {
+            return 0;
+        };
+
+        self.lods
+            .iter()
+            .position(|lod| distance_squared > lod.threshold * lod.threshold)
+            .unwrap_or(self.lods.len())
+    }
+
+    /// Returns the assigned change filter.
+    ///
+    /// Mutations of such components should be checked with
+    /// [`LastSentValues`](super::change_filter::LastSentValues) before sending.
+    pub(crate) fn change_filter(&self) -> Option<&UntypedChangeFilter> {
+        self.change_filter.as_ref()
+    }
+
+    /// Returns `true` if the component is delta-encoded.
+    ///
+    /// Such components should be serialized for each client using [`Self::write_delta`].
+    pub(crate) fn is_delta(&self) -> bool {
+        self.delta.is_some()
+    }
+
+    /// Returns the assigned schema version or 0 if the component is not versioned.
+    pub(super) fn schema_version(&self) -> u32 {
+        self.schema.map_or(0, |schema| schema.version)
+    }
+
     /// Restores erased type from `ptr` and `rule_fns` to the type for which this instance was created.
     ///
+    /// If levels of detail are assigned, serializes with functions for `lod` from [`Self::select_lod`].
+    ///
     /// # Safety
     ///
     /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance.
@@ -88,9 +231,98 @@ impl ComponentFns {
         ctx: &SerializeCtx,
         rule_fns: &UntypedRuleFns,
         ptr: Ptr,
+        lod: usize,
         message: &mut Vec<u8>,
     ) -> bincode::Result<()> {
-        (self.serialize)(ctx, rule_fns, ptr, message)
+        self.serialize_with(ctx, rule_fns, ptr, None, lod, message)
+    }
+
+    /// Same as [`Self::serialize`], but serializes a delta-encoded component as a diff from `base`
+    /// if it's smaller than the full value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance.
+    pub(crate) unsafe fn write_delta(
+        &self,
+        ctx: &SerializeCtx,
+        rule_fns: &UntypedRuleFns,
+        ptr: Ptr,
+        base: Option<(RepliconTick, &DeltaValue)>,
+        message: &mut Vec<u8>,
+    ) -> bincode::Result<()> {
+        self.serialize_with(ctx, rule_fns, ptr, base, 0, message)
+    }
+
+    /// Writes the schema version, level of detail and the component data.
+    unsafe fn serialize_with(
+        &self,
+        ctx: &SerializeCtx,
+        rule_fns: &UntypedRuleFns,
+        ptr: Ptr,
+        base: Option<(RepliconTick, &DeltaValue)>,
+        lod: usize,
+        message: &mut Vec<u8>,
+    ) -> bincode::Result<()> {
+        if let Some(schema) = self.schema {
+            message.write_varint(schema.version)?;
+        }
+
+        let rule_fns = if self.has_lods() {
+            message.write_varint(lod)?;
+            self.lods.get(lod).map_or(rule_fns, |lod| &lod.rule_fns)
+        } else {
+            rule_fns
+        };
+
+        if let Some(delta) = self.delta {
+            delta.serialize(ctx, rule_fns, ptr, base, message)
+        } else if let Some(size_limit) = self.size_limit {
+            let start = message.len();
+            DefaultOptions::new().serialize_into(&mut *message, &false)?;
+            (self.serialize)(ctx, rule_fns, ptr, message)?;
+
+            let size = message.len() - start - 1;
+            if size > size_limit.max_bytes {
+                trace!(
+                    "using fallback functions for {} bytes exceeding the limit of {}",
+                    size,
+                    size_limit.max_bytes
+                );
+                message.truncate(start);
+                DefaultOptions::new().serialize_into(&mut *message, &true)?;
+                (self.serialize)(ctx, &size_limit.fallback_fns, ptr, message)?;
+            }
+
+            Ok(())
+        } else {
+            (self.serialize)(ctx, rule_fns, ptr, message)
+        }
+    }
+
+    /// Clones a delta-encoded component to use it as a base for [`Self::write_delta`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was created for the same type as this instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component is not delta-encoded.
+    pub(crate) unsafe fn clone_delta(&self, ptr: Ptr) -> DeltaValue {
+        let delta = self
+            .delta
+            .expect("component should be registered with delta encoding");
+        delta.clone_value(ptr)
+    }
+
+    /// Restores erased type from `ptr` and `rule_fns` and checks if the component mutation should be sent.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance.
+    pub(crate) unsafe fn should_replicate(&self, rule_fns: &UntypedRuleFns, ptr: Ptr) -> bool {
+        (self.condition)(rule_fns, ptr)
     }
 
     /// Calls the assigned writing function based on entity markers.
@@ -99,6 +331,8 @@ impl ComponentFns {
     /// (the functions are sorted by priority).
     /// If there is no such function, it will use the default function.
     ///
+    /// Returns `false` if the component failed validation and was skipped.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that `rule_fns` was created for the same type as this instance.
@@ -107,9 +341,10 @@ impl ComponentFns {
         ctx: &mut WriteCtx,
         rule_fns: &UntypedRuleFns,
         entity_markers: &EntityMarkers,
+        delta_bases: &mut DeltaBaseCache,
         entity: &mut DeferredEntity,
         cursor: &mut Cursor<&[u8]>,
-    ) -> bincode::Result<()> {
+    ) -> bincode::Result<bool> {
         let command_fns = self
             .markers
             .iter()
@@ -118,7 +353,11 @@ impl ComponentFns {
             .find_map(|(&fns, _)| fns)
             .unwrap_or(self.commands);
 
-        (self.write)(ctx, &command_fns, rule_fns, entity, cursor)
+        let rule_fns = self.read_schema(rule_fns, cursor)?;
+        let rule_fns = self.read_lod(rule_fns, cursor)?;
+        let rule_fns = self.read_size_limit(rule_fns, cursor)?;
+        let result = self.write_with(ctx, &command_fns, &rule_fns, delta_bases, entity, cursor);
+        finish_write(ctx, entity.id(), result)
     }
 
     /// Calls the assigned writing or consuming function based on entity markers.
@@ -126,6 +365,8 @@ impl ComponentFns {
     /// Selects the first-found write function like [`Self::write`], but if its marker doesn't require history,
     /// the consume function will be used instead.
     ///
+    /// Returns `false` if the component failed validation and was skipped.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that `rule_fns` was created for the same type as this instance.
@@ -135,10 +376,14 @@ impl ComponentFns {
         rule_fns: &UntypedRuleFns,
         entity_markers: &EntityMarkers,
         command_markers: &CommandMarkers,
+        delta_bases: &mut DeltaBaseCache,
         entity: &mut DeferredEntity,
         cursor: &mut Cursor<&[u8]>,
-    ) -> bincode::Result<()> {
-        if let Some(command_fns) = self
+    ) -> bincode::Result<bool> {
+        let rule_fns = self.read_schema(rule_fns, cursor)?;
+        let rule_fns = self.read_lod(rule_fns, cursor)?;
+        let rule_fns = &self.read_size_limit(rule_fns, cursor)?;
+        let result = if let Some(command_fns) = self
             .markers
             .iter()
             .zip(entity_markers.markers())
@@ -147,9 +392,98 @@ impl ComponentFns {
             .find_map(|((&fns, _), need_history)| fns.map(|fns| (fns, need_history)))
             .and_then(|(fns, need_history)| need_history.then_some(fns))
         {
-            (self.write)(ctx, &command_fns, rule_fns, entity, cursor)
+            self.write_with(ctx, &command_fns, rule_fns, delta_bases, entity, cursor)
+        } else if let Some(delta) = self.delta {
+            delta.consume(ctx, rule_fns, cursor)
         } else {
             (self.consume)(ctx, rule_fns, cursor)
+        };
+        finish_write(ctx, entity.id(), result)
+    }
+
+    /// Calls the writing function with `command_fns`.
+    ///
+    /// Delta-encoded components are reconstructed first.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `rule_fns` was created for the same type as this instance.
+    unsafe fn write_with(
+        &self,
+        ctx: &mut WriteCtx,
+        command_fns: &UntypedCommandFns,
+        rule_fns: &UntypedRuleFns,
+        delta_bases: &mut DeltaBaseCache,
+        entity: &mut DeferredEntity,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        let Some(delta) = self.delta else {
+            return (self.write)(ctx, command_fns, rule_fns, entity, cursor);
+        };
+
+        if let Some(data) = delta.read(ctx, rule_fns, delta_bases, entity.id(), cursor)? {
+            (self.write)(ctx, command_fns, rule_fns, entity, &mut Cursor::new(&data))?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the schema version if the component is versioned and returns `rule_fns`
+    /// with the matching deserialization function.
+    ///
+    /// Versions older than the current one are deserialized with the legacy function.
+    fn read_schema(
+        &self,
+        rule_fns: &UntypedRuleFns,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<UntypedRuleFns> {
+        let Some(schema) = self.schema else {
+            return Ok(*rule_fns);
+        };
+
+        let version: u32 = cursor.read_varint()?;
+        let deserialize = if version < schema.version {
+            schema.deserialize_legacy
+        } else {
+            schema.deserialize_current
+        };
+
+        Ok(rule_fns.with_deserialize(deserialize))
+    }
+
+    /// Reads the level of detail written for components with assigned levels
+    /// and returns its functions or `rule_fns` for the highest fidelity.
+    fn read_lod(
+        &self,
+        rule_fns: UntypedRuleFns,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<UntypedRuleFns> {
+        if !self.has_lods() {
+            return Ok(rule_fns);
+        }
+
+        let lod: usize = cursor.read_varint()?;
+        Ok(self.lods.get(lod).map_or(rule_fns, |lod| lod.rule_fns))
+    }
+
+    /// Reads the flag written for components with a size limit and returns `rule_fns`
+    /// or the fallback functions if the flag is set.
+    ///
+    /// Delta-encoded components are serialized without the flag.
+    fn read_size_limit(
+        &self,
+        rule_fns: UntypedRuleFns,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<UntypedRuleFns> {
+        let Some(size_limit) = self.size_limit.filter(|_| self.delta.is_none()) else {
+            return Ok(rule_fns);
+        };
+
+        let fallback: bool = DefaultOptions::new().deserialize_from(cursor)?;
+        if fallback {
+            Ok(size_limit.fallback_fns)
+        } else {
+            Ok(rule_fns)
         }
     }
 
@@ -172,6 +506,56 @@ impl ComponentFns {
     }
 }
 
+/// Type-erased schema version of a component and its deserialization functions.
+///
+/// See also [`AppRuleExt::replicate_with_schema_version`](crate::core::replication::replication_rules::AppRuleExt::replicate_with_schema_version).
+#[derive(Clone, Copy)]
+struct ComponentSchema {
+    version: u32,
+    deserialize_legacy: unsafe fn(),
+    deserialize_current: unsafe fn(),
+}
+
+/// Maximum size of serialized component data and functions to use when it's exceeded.
+///
+/// See also [`AppRuleExt::replicate_with_size_limit`](crate::core::replication::replication_rules::AppRuleExt::replicate_with_size_limit).
+#[derive(Clone, Copy)]
+struct SizeLimit {
+    max_bytes: usize,
+    fallback_fns: UntypedRuleFns,
+}
+
+/// Distance threshold and functions for a reduced level of detail.
+///
+/// See also [`AppRuleExt::replicate_with_lod`](crate::core::replication::replication_rules::AppRuleExt::replicate_with_lod).
+struct Lod {
+    threshold: f32,
+    rule_fns: UntypedRuleFns,
+}
+
+/// Despawns entities spawned by a failed write and skips the component if it failed validation.
+///
+/// Returns `false` if the component was skipped.
+///
+/// See also [`RuleFns::with_validate`](super::rule_fns::RuleFns::with_validate).
+fn finish_write(
+    ctx: &mut WriteCtx,
+    entity: Entity,
+    result: bincode::Result<()>,
+) -> bincode::Result<bool> {
+    let Err(e) = result else {
+        return Ok(true);
+    };
+
+    ctx.despawn_spawned();
+    if mem::take(&mut ctx.invalid) {
+        error!("skipping component for `{entity}`: {e}");
+        return Ok(false);
+    }
+
+    Err(e)
+}
+
 /// Signature of component serialization functions that restore the original type.
 type UntypedSerializeFn =
     unsafe fn(&SerializeCtx, &UntypedRuleFns, Ptr, &mut Vec<u8>) -> bincode::Result<()>;
@@ -189,6 +573,9 @@ type UntypedWriteFn = unsafe fn(
 type UntypedConsumeFn =
     unsafe fn(&mut WriteCtx, &UntypedRuleFns, &mut Cursor<&[u8]>) -> bincode::Result<()>;
 
+/// Signature of component mutation conditions that restore the original type.
+type UntypedConditionFn = unsafe fn(&UntypedRuleFns, Ptr) -> bool;
+
 /// Dereferences a component from a pointer and calls the passed serialization function.
 ///
 /// # Safety
@@ -204,6 +591,15 @@ unsafe fn untyped_serialize<C: Component>(
     rule_fns.serialize(ctx, ptr.deref::<C>(), message)
 }
 
+/// Resolves `rule_fns` and `ptr` to `C` and calls the mutation condition for `C`.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` and `rule_fns` were created for `C`.
+unsafe fn untyped_condition<C: Component>(rule_fns: &UntypedRuleFns, ptr: Ptr) -> bool {
+    rule_fns.typed::<C>().should_replicate(ptr.deref::<C>())
+}
+
 /// Resolves `rule_fns` to `C` and calls [`UntypedCommandFns::write`] for `C`.
 ///
 /// # Safety
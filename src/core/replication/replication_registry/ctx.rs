@@ -1,5 +1,6 @@
 use bevy::{ecs::component::ComponentId, prelude::*};
 
+use super::rule_fns::Validation;
 use crate::core::{
     replication::Replicated, replicon_tick::RepliconTick, server_entity_map::ServerEntityMap,
 };
@@ -31,6 +32,15 @@ pub struct WriteCtx<'a, 'w, 's> {
 
     /// Disables mapping logic to avoid spawning entities for consume functions.
     pub(super) ignore_mapping: bool,
+
+    /// Functions to validate values deserialized by in-place and consume functions.
+    pub(super) validation: Option<Validation>,
+
+    /// Indicates that the written component failed validation.
+    pub(super) invalid: bool,
+
+    /// Server entities for which client entities were spawned by [`Self::spawn_mapped_entity`].
+    spawned: Vec<Entity>,
 }
 
 impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
@@ -46,6 +56,45 @@ impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
             component_id,
             message_tick,
             ignore_mapping: false,
+            validation: None,
+            invalid: false,
+            spawned: Default::default(),
+        }
+    }
+
+    /// Spawns a new replicated entity for `server_entity` and maps it.
+    ///
+    /// Useful for deserialization functions that need to spawn entities referenced by a component.
+    /// Spawned entities will be despawned and unmapped if writing the component fails.
+    ///
+    /// If the server entity is already mapped, returns the existing client entity instead.
+    pub fn spawn_mapped_entity(&mut self, server_entity: Entity) -> Entity {
+        if self.ignore_mapping {
+            return server_entity;
+        }
+
+        let mut spawned = false;
+        let client_entity = self.entity_map.get_by_server_or_insert(server_entity, || {
+            spawned = true;
+            self.commands.spawn_empty().insert(Replicated).id()
+        });
+
+        if spawned {
+            self.spawned.push(server_entity);
+        }
+
+        client_entity
+    }
+
+    /// Despawns and unmaps all entities spawned by [`Self::spawn_mapped_entity`].
+    pub(super) fn despawn_spawned(&mut self) {
+        for server_entity in self.spawned.drain(..) {
+            if let Some(client_entity) = self.entity_map.remove_by_server(server_entity) {
+                debug!(
+                    "despawning `{client_entity}` mapped to `{server_entity}` after failed write"
+                );
+                self.commands.entity(client_entity).despawn();
+            }
         }
     }
 }
@@ -80,3 +129,34 @@ pub struct DespawnCtx {
     /// Tick for the currently processing message.
     pub message_tick: RepliconTick,
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::world::CommandQueue;
+
+    use super::*;
+
+    #[test]
+    fn spawned_cleanup() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut entity_map = ServerEntityMap::default();
+        let mut ctx = WriteCtx::new(
+            &mut commands,
+            &mut entity_map,
+            ComponentId::new(0),
+            RepliconTick::default(),
+        );
+
+        let server_entity = Entity::from_raw(0);
+        let client_entity = ctx.spawn_mapped_entity(server_entity);
+        assert_eq!(ctx.spawn_mapped_entity(server_entity), client_entity);
+
+        ctx.despawn_spawned();
+        queue.apply(&mut world);
+
+        assert!(world.get_entity(client_entity).is_err());
+        assert_eq!(entity_map.get_by_server(server_entity), None);
+    }
+}
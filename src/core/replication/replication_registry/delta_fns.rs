@@ -0,0 +1,466 @@
+use std::{
+    any::{self, Any},
+    collections::VecDeque,
+    io::{Cursor, Read},
+};
+
+#[cfg(feature = "server")]
+use bevy::ecs::component::Tick;
+use bevy::{
+    ecs::{component::ComponentId, entity::EntityHashMap},
+    prelude::*,
+    ptr::Ptr,
+    utils::HashMap,
+};
+use bincode::{DefaultOptions, Options};
+use integer_encoding::{VarIntReader, VarIntWriter};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::core::replicon_tick::RepliconTick;
+#[cfg(feature = "server")]
+use crate::core::ClientId;
+
+use super::{
+    ctx::{SerializeCtx, WriteCtx},
+    rule_fns::UntypedRuleFns,
+};
+
+/// Prefix for a component serialized as a full value.
+const FULL: u8 = 0;
+
+/// Prefix for a component serialized as a diff from a previously sent value.
+const DELTA: u8 = 1;
+
+/// Describes how to encode a component as a difference from its previous value.
+///
+/// See also [`AppRuleExt::replicate_with_delta`](crate::core::replication::replication_rules::AppRuleExt::replicate_with_delta).
+pub trait Delta: Component + Clone {
+    /// Serializable difference between two values.
+    type Diff: Serialize + DeserializeOwned;
+
+    /// Returns the difference between `old` and `new`.
+    fn diff(old: &Self, new: &Self) -> Self::Diff;
+
+    /// Applies `diff` to `base` and returns the resulting value.
+    fn patch(base: &Self, diff: &Self::Diff) -> Self;
+}
+
+/// Type-erased component value stored as a delta base.
+pub(crate) type DeltaValue = Box<dyn Any + Send + Sync>;
+
+/// Type-erased delta functions for a component.
+#[derive(Clone, Copy)]
+pub(super) struct UntypedDeltaFns {
+    serialize: UntypedSerializeDeltaFn,
+    read: UntypedReadDeltaFn,
+    consume: UntypedConsumeDeltaFn,
+    clone: unsafe fn(Ptr) -> DeltaValue,
+}
+
+impl UntypedDeltaFns {
+    pub(super) fn new<C: Delta>() -> Self {
+        Self {
+            serialize: serialize_delta::<C>,
+            read: read_delta::<C>,
+            consume: consume_delta::<C>,
+            clone: clone_value::<C>,
+        }
+    }
+
+    /// Serializes a component as a diff from `base` or as a full value, whichever is smaller.
+    ///
+    /// If `base` is `None`, the full value will be written.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` and `rule_fns` were created for the same type as this instance.
+    pub(super) unsafe fn serialize(
+        &self,
+        ctx: &SerializeCtx,
+        rule_fns: &UntypedRuleFns,
+        ptr: Ptr,
+        base: Option<(RepliconTick, &DeltaValue)>,
+        message: &mut Vec<u8>,
+    ) -> bincode::Result<()> {
+        (self.serialize)(ctx, rule_fns, ptr, base, message)
+    }
+
+    /// Reads a value written by [`Self::serialize`] and returns it fully serialized with `rule_fns`.
+    ///
+    /// Returns `None` if the base for the diff is missing.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `rule_fns` was created for the same type as this instance.
+    pub(super) unsafe fn read(
+        &self,
+        ctx: &mut WriteCtx,
+        rule_fns: &UntypedRuleFns,
+        delta_bases: &mut DeltaBaseCache,
+        entity: Entity,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<Option<Vec<u8>>> {
+        (self.read)(ctx, rule_fns, delta_bases, entity, cursor)
+    }
+
+    /// Skips a value written by [`Self::serialize`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `rule_fns` was created for the same type as this instance.
+    pub(super) unsafe fn consume(
+        &self,
+        ctx: &mut WriteCtx,
+        rule_fns: &UntypedRuleFns,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        (self.consume)(ctx, rule_fns, cursor)
+    }
+
+    /// Clones the component to store it as a delta base.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was created for the same type as this instance.
+    pub(super) unsafe fn clone_value(&self, ptr: Ptr) -> DeltaValue {
+        (self.clone)(ptr)
+    }
+}
+
+/// Last sent values of delta-encoded components for each client.
+///
+/// Values are stored until the client acknowledges a newer one.
+/// Used on server.
+///
+/// See also [`Delta`].
+#[cfg(feature = "server")]
+#[derive(Resource, Default)]
+pub(crate) struct DeltaCache(EntityHashMap<HashMap<(ClientId, ComponentId), ServerDeltaBases>>);
+
+#[cfg(feature = "server")]
+impl DeltaCache {
+    /// Returns sent values of a component for a client.
+    pub(crate) fn bases_mut(
+        &mut self,
+        client_id: ClientId,
+        entity: Entity,
+        component_id: ComponentId,
+    ) -> &mut ServerDeltaBases {
+        self.0
+            .entry(entity)
+            .or_default()
+            .entry((client_id, component_id))
+            .or_default()
+    }
+
+    /// Removes all values sent to a client.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        for bases in self.0.values_mut() {
+            bases.retain(|&(id, _), _| id != client_id);
+        }
+    }
+
+    /// Removes values of components that are no longer present in the world.
+    pub(crate) fn retain_existing(&mut self, world: &World) {
+        self.0.retain(|&entity, bases| {
+            let Ok(entity) = world.get_entity(entity) else {
+                return false;
+            };
+            bases.retain(|&(_, component_id), _| entity.contains_id(component_id));
+            !bases.is_empty()
+        });
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Values of a component sent to a client.
+#[cfg(feature = "server")]
+#[derive(Default)]
+pub(crate) struct ServerDeltaBases(VecDeque<ServerDeltaBase>);
+
+#[cfg(feature = "server")]
+impl ServerDeltaBases {
+    /// Returns the latest sent value that is not newer than `acked_tick`.
+    ///
+    /// All older values will be removed since the client will never use them as a base.
+    pub(crate) fn acked(
+        &mut self,
+        acked_tick: Tick,
+        this_run: Tick,
+    ) -> Option<(RepliconTick, &DeltaValue)> {
+        let index = self
+            .0
+            .iter()
+            .rposition(|base| !base.tick.is_newer_than(acked_tick, this_run))?;
+        self.0.drain(..index);
+
+        self.0.front().map(|base| (base.server_tick, &base.value))
+    }
+
+    /// Stores a sent value.
+    pub(crate) fn push(&mut self, tick: Tick, server_tick: RepliconTick, value: DeltaValue) {
+        self.0.push_back(ServerDeltaBase {
+            tick,
+            server_tick,
+            value,
+        });
+    }
+}
+
+/// A component value sent to a client.
+#[cfg(feature = "server")]
+struct ServerDeltaBase {
+    /// System tick on which the value was sent.
+    ///
+    /// Compared with client mutation ticks.
+    tick: Tick,
+
+    /// Server tick with which the value was sent.
+    ///
+    /// Used by client to find the base.
+    server_tick: RepliconTick,
+
+    value: DeltaValue,
+}
+
+/// Received values of delta-encoded components for reconstruction.
+///
+/// Used on client.
+///
+/// See also [`Delta`].
+#[derive(Resource, Default)]
+pub(crate) struct DeltaBaseCache(
+    EntityHashMap<HashMap<ComponentId, VecDeque<(RepliconTick, DeltaValue)>>>,
+);
+
+impl DeltaBaseCache {
+    /// Returns the value received on `tick`.
+    ///
+    /// All older values will be removed since the server will never use them as a base.
+    fn base(
+        &mut self,
+        entity: Entity,
+        component_id: ComponentId,
+        tick: RepliconTick,
+    ) -> Option<&DeltaValue> {
+        let bases = self.0.get_mut(&entity)?.get_mut(&component_id)?;
+        let index = bases.iter().position(|&(base_tick, _)| base_tick == tick)?;
+        bases.drain(..index);
+
+        bases.front().map(|(_, value)| value)
+    }
+
+    /// Stores a received value.
+    ///
+    /// Ignores values older than the last stored since they were received out of order.
+    fn push(
+        &mut self,
+        entity: Entity,
+        component_id: ComponentId,
+        tick: RepliconTick,
+        value: DeltaValue,
+    ) {
+        let bases = self
+            .0
+            .entry(entity)
+            .or_default()
+            .entry(component_id)
+            .or_default();
+
+        if bases.back().is_none_or(|&(last_tick, _)| tick > last_tick) {
+            bases.push_back((tick, value));
+        }
+    }
+
+    /// Removes values of components that are no longer present in the world.
+    pub(crate) fn retain_existing(&mut self, world: &World) {
+        self.0.retain(|&entity, bases| {
+            let Ok(entity) = world.get_entity(entity) else {
+                return false;
+            };
+            bases.retain(|&component_id, _| entity.contains_id(component_id));
+            !bases.is_empty()
+        });
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Signature of delta serialization functions that restore the original type.
+type UntypedSerializeDeltaFn = unsafe fn(
+    &SerializeCtx,
+    &UntypedRuleFns,
+    Ptr,
+    Option<(RepliconTick, &DeltaValue)>,
+    &mut Vec<u8>,
+) -> bincode::Result<()>;
+
+/// Signature of delta reading functions that restore the original type.
+type UntypedReadDeltaFn = unsafe fn(
+    &mut WriteCtx,
+    &UntypedRuleFns,
+    &mut DeltaBaseCache,
+    Entity,
+    &mut Cursor<&[u8]>,
+) -> bincode::Result<Option<Vec<u8>>>;
+
+/// Signature of delta consuming functions that restore the original type.
+type UntypedConsumeDeltaFn =
+    unsafe fn(&mut WriteCtx, &UntypedRuleFns, &mut Cursor<&[u8]>) -> bincode::Result<()>;
+
+/// Serializes `C` as a diff with a [`DELTA`] prefix if it's smaller than the full value.
+/// Otherwise serializes the full value with a [`FULL`] prefix.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` and `rule_fns` were created for `C`.
+unsafe fn serialize_delta<C: Delta>(
+    ctx: &SerializeCtx,
+    rule_fns: &UntypedRuleFns,
+    ptr: Ptr,
+    base: Option<(RepliconTick, &DeltaValue)>,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    let rule_fns = rule_fns.typed::<C>();
+    let component = ptr.deref::<C>();
+
+    let mut full = Vec::new();
+    rule_fns.serialize(ctx, component, &mut full)?;
+
+    if let Some((base_tick, base)) = base {
+        let base = base
+            .downcast_ref::<C>()
+            .expect("delta base should have the same type as the component");
+        let diff = C::diff(base, component);
+
+        let mut delta = Vec::new();
+        delta.write_varint(base_tick.get())?;
+        DefaultOptions::new().serialize_into(&mut delta, &diff)?;
+
+        if delta.len() < full.len() {
+            message.push(DELTA);
+            message.extend_from_slice(&delta);
+            return Ok(());
+        }
+    }
+
+    message.push(FULL);
+    message.extend_from_slice(&full);
+
+    Ok(())
+}
+
+/// Reads `C` written by [`serialize_delta`], stores it as a base and returns it fully serialized.
+///
+/// The stored base keeps server entities to match the server's value.
+///
+/// # Safety
+///
+/// The caller must ensure that `rule_fns` was created for `C`.
+unsafe fn read_delta<C: Delta>(
+    ctx: &mut WriteCtx,
+    rule_fns: &UntypedRuleFns,
+    delta_bases: &mut DeltaBaseCache,
+    entity: Entity,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<Option<Vec<u8>>> {
+    let rule_fns = rule_fns.typed::<C>();
+    match read_prefix(cursor)? {
+        FULL => {
+            let start = cursor.position() as usize;
+            ctx.ignore_mapping = true;
+            let result = rule_fns.deserialize(ctx, cursor);
+            ctx.ignore_mapping = false;
+            let component = result?;
+            let end = cursor.position() as usize;
+
+            delta_bases.push(
+                entity,
+                ctx.component_id,
+                ctx.message_tick,
+                Box::new(component),
+            );
+
+            Ok(Some(cursor.get_ref()[start..end].to_vec()))
+        }
+        DELTA => {
+            let base_tick = RepliconTick::new(cursor.read_varint()?);
+            let diff: C::Diff = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+            let Some(base) = delta_bases.base(entity, ctx.component_id, base_tick) else {
+                debug!(
+                    "ignoring `{}` diff for `{entity}` with missing base for {base_tick:?}",
+                    any::type_name::<C>()
+                );
+                return Ok(None);
+            };
+            let base = base
+                .downcast_ref::<C>()
+                .expect("delta base should have the same type as the component");
+            let component = C::patch(base, &diff);
+
+            let serialize_ctx = SerializeCtx {
+                server_tick: ctx.message_tick,
+                component_id: ctx.component_id,
+            };
+            let mut data = Vec::new();
+            rule_fns.serialize(&serialize_ctx, &component, &mut data)?;
+
+            delta_bases.push(
+                entity,
+                ctx.component_id,
+                ctx.message_tick,
+                Box::new(component),
+            );
+
+            Ok(Some(data))
+        }
+        prefix => Err(invalid_prefix(prefix)),
+    }
+}
+
+/// Skips `C` written by [`serialize_delta`].
+///
+/// # Safety
+///
+/// The caller must ensure that `rule_fns` was created for `C`.
+unsafe fn consume_delta<C: Delta>(
+    ctx: &mut WriteCtx,
+    rule_fns: &UntypedRuleFns,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    match read_prefix(cursor)? {
+        FULL => rule_fns.typed::<C>().consume(ctx, cursor),
+        DELTA => {
+            let _: u32 = cursor.read_varint()?;
+            let _: C::Diff = DefaultOptions::new().deserialize_from(cursor)?;
+            Ok(())
+        }
+        prefix => Err(invalid_prefix(prefix)),
+    }
+}
+
+/// Clones `C` from a pointer.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` was created for `C`.
+unsafe fn clone_value<C: Delta>(ptr: Ptr) -> DeltaValue {
+    Box::new(ptr.deref::<C>().clone())
+}
+
+fn read_prefix(cursor: &mut Cursor<&[u8]>) -> bincode::Result<u8> {
+    let mut prefix = [0];
+    cursor.read_exact(&mut prefix)?;
+    Ok(prefix[0])
+}
+
+fn invalid_prefix(prefix: u8) -> bincode::Error {
+    Box::new(bincode::ErrorKind::Custom(format!(
+        "unknown delta prefix {prefix}"
+    )))
+}
@@ -0,0 +1,183 @@
+use std::{
+    io::Cursor,
+    ops::{Deref, DerefMut},
+};
+
+use bevy::prelude::*;
+use bincode::{DefaultOptions, Options};
+
+use super::ctx::{SerializeCtx, WriteCtx};
+
+/// Lossy serialization for a component.
+///
+/// Used to reduce bandwidth for numeric components that don't need the full precision on clients,
+/// such as positions, velocities or angles.
+///
+/// See also [`AppRuleExt::replicate_with_quantization`](crate::core::replication::replication_rules::AppRuleExt::replicate_with_quantization).
+pub trait Quantize<C> {
+    /// Writes a reduced precision representation of the value.
+    fn quantize(value: &C, message: &mut Vec<u8>) -> bincode::Result<()>;
+
+    /// Restores the value from its reduced precision representation.
+    fn dequantize(cursor: &mut Cursor<&[u8]>) -> bincode::Result<C>;
+}
+
+/// Serialization function that uses quantizer `Q`.
+pub fn serialize_quantized<C: Component, Q: Quantize<C>>(
+    _ctx: &SerializeCtx,
+    component: &C,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    Q::quantize(component, message)
+}
+
+/// Deserialization function that uses quantizer `Q`.
+pub fn deserialize_quantized<C: Component, Q: Quantize<C>>(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    Q::dequantize(cursor)
+}
+
+/// Quantizes a component that dereferences into [`f32`] to `BITS` bits in the range from `MIN` to `MAX`.
+///
+/// Values outside the range are clamped.
+/// The maximum error is `(MAX - MIN) / (2^BITS - 1) / 2`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     core::replication::replication_registry::quantize::F32Quantizer, prelude::*,
+/// };
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(RepliconPlugins);
+/// // Precision of ~0.006 degrees.
+/// app.replicate_with_quantization::<Angle, F32Quantizer<0, 360, 16>>();
+///
+/// #[derive(Component, Default, Deref, DerefMut)]
+/// struct Angle(f32);
+/// ```
+pub struct F32Quantizer<const MIN: i32, const MAX: i32, const BITS: u8>;
+
+impl<C, const MIN: i32, const MAX: i32, const BITS: u8> Quantize<C> for F32Quantizer<MIN, MAX, BITS>
+where
+    C: Component + Deref<Target = f32> + DerefMut + Default,
+{
+    fn quantize(value: &C, message: &mut Vec<u8>) -> bincode::Result<()> {
+        let quantized = quantize_f32(**value, MIN as f32, MAX as f32, BITS);
+        DefaultOptions::new().serialize_into(message, &quantized)
+    }
+
+    fn dequantize(cursor: &mut Cursor<&[u8]>) -> bincode::Result<C> {
+        let quantized = DefaultOptions::new().deserialize_from(cursor)?;
+        let mut component = C::default();
+        *component = dequantize_f32(quantized, MIN as f32, MAX as f32, BITS);
+        Ok(component)
+    }
+}
+
+/// Quantizes [`Transform::translation`] components to `BITS` bits in the range from `MIN` to `MAX`.
+///
+/// Rotation and scale are serialized as is.
+/// See [`F32Quantizer`] for details about precision.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::{
+///     core::replication::replication_registry::quantize::Vec3Quantizer, prelude::*,
+/// };
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(RepliconPlugins);
+/// // Precision of ~0.015 units for a map of 1000x1000 units.
+/// app.replicate_with_quantization::<Transform, Vec3Quantizer<-500, 500, 16>>();
+/// ```
+pub struct Vec3Quantizer<const MIN: i32, const MAX: i32, const BITS: u8>;
+
+impl<const MIN: i32, const MAX: i32, const BITS: u8> Quantize<Transform>
+    for Vec3Quantizer<MIN, MAX, BITS>
+{
+    fn quantize(transform: &Transform, message: &mut Vec<u8>) -> bincode::Result<()> {
+        for value in transform.translation.to_array() {
+            let quantized = quantize_f32(value, MIN as f32, MAX as f32, BITS);
+            DefaultOptions::new().serialize_into(&mut *message, &quantized)?;
+        }
+        DefaultOptions::new().serialize_into(&mut *message, &transform.rotation)?;
+        DefaultOptions::new().serialize_into(message, &transform.scale)
+    }
+
+    fn dequantize(cursor: &mut Cursor<&[u8]>) -> bincode::Result<Transform> {
+        let mut translation = [0.0; 3];
+        for value in &mut translation {
+            let quantized = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+            *value = dequantize_f32(quantized, MIN as f32, MAX as f32, BITS);
+        }
+        let rotation = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+        let scale = DefaultOptions::new().deserialize_from(cursor)?;
+
+        Ok(Transform {
+            translation: translation.into(),
+            rotation,
+            scale,
+        })
+    }
+}
+
+/// Maps `value` from the range between `min` and `max` into an integer with `bits` bits.
+///
+/// Values outside the range are clamped.
+pub fn quantize_f32(value: f32, min: f32, max: f32, bits: u8) -> u32 {
+    debug_assert!(min < max, "`min` should be less than `max`");
+    let steps = max_steps(bits);
+    let normalized = (value.clamp(min, max) as f64 - min as f64) / (max as f64 - min as f64);
+    (normalized * steps as f64).round() as u32
+}
+
+/// Restores a value quantized with [`quantize_f32`].
+pub fn dequantize_f32(quantized: u32, min: f32, max: f32, bits: u8) -> f32 {
+    debug_assert!(min < max, "`min` should be less than `max`");
+    let steps = max_steps(bits);
+    let normalized = quantized.min(steps) as f64 / steps as f64;
+    (min as f64 + normalized * (max as f64 - min as f64)) as f32
+}
+
+/// Returns the maximum integer representable with `bits` bits.
+fn max_steps(bits: u8) -> u32 {
+    debug_assert!(
+        (1..=32).contains(&bits),
+        "number of bits should be between 1 and 32"
+    );
+    u32::MAX >> (32 - bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f32_precision() {
+        for value in [-10.0, -3.3, 0.0, 0.1, 7.77, 10.0] {
+            let quantized = quantize_f32(value, -10.0, 10.0, 16);
+            let restored = dequantize_f32(quantized, -10.0, 10.0, 16);
+            assert!((value - restored).abs() <= 20.0 / u16::MAX as f32 / 2.0);
+        }
+    }
+
+    #[test]
+    fn f32_clamping() {
+        assert_eq!(quantize_f32(-20.0, -10.0, 10.0, 8), 0);
+        assert_eq!(quantize_f32(20.0, -10.0, 10.0, 8), u8::MAX.into());
+    }
+
+    #[test]
+    fn f32_full_bits() {
+        let quantized = quantize_f32(1.0, 0.0, 1.0, 32);
+        assert_eq!(quantized, u32::MAX);
+        assert_eq!(dequantize_f32(quantized, 0.0, 1.0, 32), 1.0);
+    }
+}
@@ -1,11 +1,14 @@
 use std::{
     any::{self, TypeId},
+    fmt::Debug,
     io::Cursor,
     mem,
 };
 
 use bevy::{ecs::entity::MapEntities, prelude::*};
 use bincode::{DefaultOptions, Options};
+#[cfg(feature = "compression")]
+use integer_encoding::{VarIntReader, VarIntWriter};
 use serde::{de::DeserializeOwned, Serialize};
 
 use super::ctx::{SerializeCtx, WriteCtx};
@@ -13,6 +16,7 @@ use super::ctx::{SerializeCtx, WriteCtx};
 /// Type-erased version of [`RuleFns`].
 ///
 /// Stored inside [`ReplicationRegistry`](super::ReplicationRegistry) after registration.
+#[derive(Clone, Copy)]
 pub(crate) struct UntypedRuleFns {
     type_id: TypeId,
     type_name: &'static str,
@@ -21,9 +25,23 @@ pub(crate) struct UntypedRuleFns {
     deserialize: unsafe fn(),
     deserialize_in_place: unsafe fn(),
     consume: unsafe fn(),
+    condition: Option<unsafe fn()>,
+    validate: Option<unsafe fn()>,
+    trace: Option<unsafe fn()>,
+    #[cfg(feature = "compression")]
+    compression: Option<Compression>,
 }
 
 impl UntypedRuleFns {
+    /// Replaces the type-erased deserialization function.
+    ///
+    /// The function must be created for the same type as this instance.
+    /// Used to switch between schema versions.
+    pub(super) fn with_deserialize(mut self, deserialize: unsafe fn()) -> Self {
+        self.deserialize = deserialize;
+        self
+    }
+
     /// Restores the original [`RuleFns`] from which this type was created.
     ///
     /// # Safety
@@ -47,6 +65,17 @@ impl UntypedRuleFns {
                 mem::transmute::<unsafe fn(), DeserializeInPlaceFn<C>>(self.deserialize_in_place)
             },
             consume: unsafe { mem::transmute::<unsafe fn(), ConsumeFn<C>>(self.consume) },
+            condition: self.condition.map(|condition| unsafe {
+                mem::transmute::<unsafe fn(), ConditionFn<C>>(condition)
+            }),
+            validate: self
+                .validate
+                .map(|validate| unsafe { mem::transmute::<unsafe fn(), ValidateFn<C>>(validate) }),
+            trace: self
+                .trace
+                .map(|trace| unsafe { mem::transmute::<unsafe fn(), TraceFn<C>>(trace) }),
+            #[cfg(feature = "compression")]
+            compression: self.compression,
         }
     }
 }
@@ -65,6 +94,17 @@ impl<C: Component> From<RuleFns<C>> for UntypedRuleFns {
                 mem::transmute::<DeserializeInPlaceFn<C>, unsafe fn()>(value.deserialize_in_place)
             },
             consume: unsafe { mem::transmute::<ConsumeFn<C>, unsafe fn()>(value.consume) },
+            condition: value.condition.map(|condition| unsafe {
+                mem::transmute::<ConditionFn<C>, unsafe fn()>(condition)
+            }),
+            validate: value
+                .validate
+                .map(|validate| unsafe { mem::transmute::<ValidateFn<C>, unsafe fn()>(validate) }),
+            trace: value
+                .trace
+                .map(|trace| unsafe { mem::transmute::<TraceFn<C>, unsafe fn()>(trace) }),
+            #[cfg(feature = "compression")]
+            compression: value.compression,
         }
     }
 }
@@ -78,6 +118,11 @@ pub struct RuleFns<C> {
     deserialize: DeserializeFn<C>,
     deserialize_in_place: DeserializeInPlaceFn<C>,
     consume: ConsumeFn<C>,
+    condition: Option<ConditionFn<C>>,
+    validate: Option<ValidateFn<C>>,
+    trace: Option<TraceFn<C>>,
+    #[cfg(feature = "compression")]
+    compression: Option<Compression>,
 }
 
 impl<C: Component> RuleFns<C> {
@@ -90,6 +135,27 @@ impl<C: Component> RuleFns<C> {
             deserialize,
             deserialize_in_place: in_place_as_deserialize::<C>,
             consume: consume_as_deserialize,
+            condition: None,
+            validate: None,
+            trace: None,
+            #[cfg(feature = "compression")]
+            compression: None,
+        }
+    }
+
+    /// Same as [`Self::new`], but logs component values passed to `serialize` and returned
+    /// from `deserialize` at the trace level.
+    ///
+    /// Useful for protocol debugging without adding temporary logging to the functions.
+    /// Values are formatted only if the trace level is enabled for this crate,
+    /// for example with `RUST_LOG=bevy_replicon=trace`.
+    pub fn new_debug(serialize: SerializeFn<C>, deserialize: DeserializeFn<C>) -> Self
+    where
+        C: Debug,
+    {
+        Self {
+            trace: Some(trace_component::<C>),
+            ..Self::new(serialize, deserialize)
         }
     }
 
@@ -120,6 +186,89 @@ impl<C: Component> RuleFns<C> {
         self
     }
 
+    /// Sets a function that decides whether a changed component should be sent as a mutation.
+    ///
+    /// If the function returns `false`, the component will be treated as unchanged even if it was
+    /// mutated according to change detection. Insertions are always sent.
+    ///
+    /// Useful for components that change frequently, but whose meaningful value stays the same.
+    pub fn with_condition(mut self, condition: ConditionFn<C>) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Sets a function that checks a received component before writing it on client.
+    ///
+    /// Called on each deserialized value, including values deserialized by in-place and consume functions.
+    /// If the function returns an error, [`Self::deserialize`] returns an error too, which stops the write.
+    /// The error will be logged and the component will be skipped instead of failing the whole message.
+    ///
+    /// Useful to reject values that were parsed successfully but are semantically invalid,
+    /// such as negative health or an out-of-bounds index.
+    pub fn with_validate(mut self, validate: ValidateFn<C>) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
+    /// Compresses serialized data with [zstd](https://facebook.github.io/zstd) at the given level.
+    ///
+    /// Compression is applied on top of the serialization functions, so custom functions can be used too.
+    /// Useful for large components with repetitive data, such as voxel chunks or paths.
+    /// For small components the compression overhead usually exceeds the savings.
+    /// Serialized data shouldn't exceed [`MAX_DECOMPRESSED_SIZE`], otherwise clients will reject it.
+    ///
+    /// Replaces compression set by [`Self::with_lz4`].
+    #[cfg(feature = "compression")]
+    pub fn with_compression(mut self, level: i32) -> Self {
+        self.compression = Some(Compression::Zstd(level));
+        self
+    }
+
+    /// Like [`Self::with_compression`], but uses [LZ4](https://lz4.org) instead.
+    ///
+    /// Compresses worse than zstd, but is significantly faster.
+    #[cfg(feature = "compression")]
+    pub fn with_lz4(mut self) -> Self {
+        self.compression = Some(Compression::Lz4);
+        self
+    }
+
+    /// Returns `true` if the component mutation should be sent.
+    ///
+    /// Always returns `true` if there is no condition.
+    pub(super) fn should_replicate(&self, component: &C) -> bool {
+        self.condition
+            .is_none_or(|condition| (condition)(component))
+    }
+
+    /// Calls `f` with the deserialization function that should be passed to in-place and consume functions.
+    ///
+    /// If there is a validation function, passes [`deserialize_validated`] to check values
+    /// before they are written.
+    fn with_validation<R>(
+        &self,
+        ctx: &mut WriteCtx,
+        f: impl FnOnce(DeserializeFn<C>, &mut WriteCtx) -> R,
+    ) -> R {
+        let Some(validate) = self.validate else {
+            return f(self.deserialize, ctx);
+        };
+
+        // SAFETY: these functions will be restored only for `C`.
+        let validation = unsafe {
+            Validation {
+                type_id: TypeId::of::<C>(),
+                deserialize: mem::transmute::<DeserializeFn<C>, unsafe fn()>(self.deserialize),
+                validate: mem::transmute::<ValidateFn<C>, unsafe fn()>(validate),
+            }
+        };
+        let previous = ctx.validation.replace(validation);
+        let result = f(deserialize_validated::<C>, ctx);
+        ctx.validation = previous;
+
+        result
+    }
+
     /// Serializes a component into a cursor.
     pub(super) fn serialize(
         &self,
@@ -127,6 +276,17 @@ impl<C: Component> RuleFns<C> {
         component: &C,
         message: &mut Vec<u8>,
     ) -> bincode::Result<()> {
+        if let Some(trace) = self.trace {
+            (trace)("serializing", component);
+        }
+
+        #[cfg(feature = "compression")]
+        if let Some(compression) = self.compression {
+            let mut data = Vec::new();
+            (self.serialize)(ctx, component, &mut data)?;
+            return compression.compress(&data, message);
+        }
+
         (self.serialize)(ctx, component, message)
     }
 
@@ -138,7 +298,25 @@ impl<C: Component> RuleFns<C> {
         ctx: &mut WriteCtx,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<C> {
-        (self.deserialize)(ctx, cursor)
+        #[cfg(feature = "compression")]
+        let component = if let Some(compression) = self.compression {
+            let data = compression.decompress(cursor)?;
+            (self.deserialize)(ctx, &mut Cursor::new(&data))?
+        } else {
+            (self.deserialize)(ctx, cursor)?
+        };
+        #[cfg(not(feature = "compression"))]
+        let component = (self.deserialize)(ctx, cursor)?;
+
+        if let Some(trace) = self.trace {
+            (trace)("deserialized", &component);
+        }
+
+        if let Some(validate) = self.validate {
+            check(validate, ctx, &component)?;
+        }
+
+        Ok(component)
     }
 
     /// Same as [`Self::deserialize`], but instead of returning a component, it updates the passed reference.
@@ -150,7 +328,26 @@ impl<C: Component> RuleFns<C> {
         component: &mut C,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
-        (self.deserialize_in_place)(self.deserialize, ctx, component, cursor)
+        self.with_validation(ctx, |deserialize, ctx| {
+            #[cfg(feature = "compression")]
+            if let Some(compression) = self.compression {
+                let data = compression.decompress(cursor)?;
+                return (self.deserialize_in_place)(
+                    deserialize,
+                    ctx,
+                    component,
+                    &mut Cursor::new(&data),
+                );
+            }
+
+            (self.deserialize_in_place)(deserialize, ctx, component, cursor)
+        })?;
+
+        if let Some(trace) = self.trace {
+            (trace)("deserialized in place", component);
+        }
+
+        Ok(())
     }
 
     /// Consumes a component from a cursor.
@@ -159,7 +356,15 @@ impl<C: Component> RuleFns<C> {
         ctx: &mut WriteCtx,
         cursor: &mut Cursor<&[u8]>,
     ) -> bincode::Result<()> {
-        (self.consume)(self.deserialize, ctx, cursor)
+        self.with_validation(ctx, |deserialize, ctx| {
+            #[cfg(feature = "compression")]
+            if let Some(compression) = self.compression {
+                let data = compression.decompress(cursor)?;
+                return (self.consume)(deserialize, ctx, &mut Cursor::new(&data));
+            }
+
+            (self.consume)(deserialize, ctx, cursor)
+        })
     }
 }
 
@@ -186,6 +391,120 @@ impl<C: Component + Serialize + DeserializeOwned> Default for RuleFns<C> {
     }
 }
 
+/// Logs a component value for [`RuleFns::new_debug`].
+fn trace_component<C: Debug>(action: &str, component: &C) {
+    trace!("{action} `{}`: {component:?}", any::type_name::<C>());
+}
+
+/// Type-erased functions for [`deserialize_validated`].
+#[derive(Clone, Copy)]
+pub(super) struct Validation {
+    type_id: TypeId,
+    deserialize: unsafe fn(),
+    validate: unsafe fn(),
+}
+
+/// Deserializes a component with the original function and checks it with the validation function.
+///
+/// Passed by [`RuleFns`] to in-place and consume functions instead of the original
+/// deserialization function if [`RuleFns::with_validate`] was set.
+fn deserialize_validated<C: Component>(
+    ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<C> {
+    let validation = ctx
+        .validation
+        .filter(|validation| validation.type_id == TypeId::of::<C>())
+        .unwrap_or_else(|| {
+            panic!(
+                "validated deserialization for `{}` should be called only from its rule functions",
+                any::type_name::<C>()
+            )
+        });
+
+    // SAFETY: the functions were created for `C`, which was checked above.
+    let (deserialize, validate) = unsafe {
+        (
+            mem::transmute::<unsafe fn(), DeserializeFn<C>>(validation.deserialize),
+            mem::transmute::<unsafe fn(), ValidateFn<C>>(validation.validate),
+        )
+    };
+
+    let component = (deserialize)(ctx, cursor)?;
+    check(validate, ctx, &component)?;
+
+    Ok(component)
+}
+
+/// Checks a deserialized component with the validation function.
+///
+/// On failure marks the write as invalid and returns an error to stop it.
+fn check<C>(validate: ValidateFn<C>, ctx: &mut WriteCtx, component: &C) -> bincode::Result<()> {
+    (validate)(component).map_err(|e| {
+        ctx.invalid = true;
+        bincode::ErrorKind::Custom(format!("invalid `{}`: {e}", any::type_name::<C>())).into()
+    })
+}
+
+/// Maximum size of decompressed component data.
+///
+/// The size is read from the message, so received data above it will be rejected
+/// to avoid allocating arbitrary amounts of memory.
+#[cfg(feature = "compression")]
+pub const MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+/// Compression algorithm applied on top of component serialization.
+#[cfg(feature = "compression")]
+#[derive(Clone, Copy)]
+enum Compression {
+    Zstd(i32),
+    Lz4,
+}
+
+#[cfg(feature = "compression")]
+impl Compression {
+    /// Compresses `data` and writes it into `message` prefixed with uncompressed and compressed sizes.
+    fn compress(self, data: &[u8], message: &mut Vec<u8>) -> bincode::Result<()> {
+        let compressed = match self {
+            Compression::Zstd(level) => zstd::bulk::compress(data, level)?,
+            Compression::Lz4 => lz4_flex::compress(data),
+        };
+
+        message.write_varint(data.len())?;
+        message.write_varint(compressed.len())?;
+        message.extend_from_slice(&compressed);
+
+        Ok(())
+    }
+
+    /// Reads data written by [`Self::compress`] and returns it decompressed.
+    fn decompress(self, cursor: &mut Cursor<&[u8]>) -> bincode::Result<Vec<u8>> {
+        let uncompressed_size: usize = cursor.read_varint()?;
+        if uncompressed_size > MAX_DECOMPRESSED_SIZE {
+            return Err(bincode::ErrorKind::Custom(format!(
+                "decompressed size {uncompressed_size} exceeds {MAX_DECOMPRESSED_SIZE}"
+            ))
+            .into());
+        }
+        let compressed_size: usize = cursor.read_varint()?;
+        let start = cursor.position() as usize;
+        let compressed = cursor
+            .get_ref()
+            .get(start..)
+            .and_then(|remaining| remaining.get(..compressed_size))
+            .ok_or_else(|| bincode::ErrorKind::Custom("compressed data is truncated".into()))?;
+        cursor.set_position((start + compressed_size) as u64);
+
+        let data = match self {
+            Compression::Zstd(_) => zstd::bulk::decompress(compressed, uncompressed_size)?,
+            Compression::Lz4 => lz4_flex::decompress(compressed, uncompressed_size)
+                .map_err(|e| bincode::ErrorKind::Custom(e.to_string()))?,
+        };
+
+        Ok(data)
+    }
+}
+
 /// Signature of component serialization functions.
 pub type SerializeFn<C> = fn(&SerializeCtx, &C, &mut Vec<u8>) -> bincode::Result<()>;
 
@@ -196,10 +515,19 @@ pub type DeserializeFn<C> = fn(&mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Re
 pub type DeserializeInPlaceFn<C> =
     fn(DeserializeFn<C>, &mut WriteCtx, &mut C, &mut Cursor<&[u8]>) -> bincode::Result<()>;
 
+/// Signature of component mutation conditions.
+pub type ConditionFn<C> = fn(&C) -> bool;
+
+/// Signature of component validation functions.
+pub type ValidateFn<C> = fn(&C) -> Result<(), String>;
+
 /// Signature of component consume functions.
 pub type ConsumeFn<C> =
     fn(DeserializeFn<C>, &mut WriteCtx, &mut Cursor<&[u8]>) -> bincode::Result<()>;
 
+/// Signature of functions that log component values for [`RuleFns::new_debug`].
+type TraceFn<C> = fn(&str, &C);
+
 /// Default component serialization function.
 pub fn default_serialize<C: Component + Serialize>(
     _ctx: &SerializeCtx,
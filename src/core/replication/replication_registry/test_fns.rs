@@ -4,12 +4,13 @@ use bevy::{ecs::world::CommandQueue, prelude::*};
 
 use super::{
     ctx::{DespawnCtx, RemoveCtx, SerializeCtx, WriteCtx},
+    delta_fns::DeltaBaseCache,
     FnsId, ReplicationRegistry,
 };
 use crate::core::{
     replication::{
         command_markers::{CommandMarkers, EntityMarkers},
-        deferred_entity::DeferredEntity,
+        deferred_entity::{DeferredChanges, DeferredEntity},
     },
     replicon_tick::RepliconTick,
     server_entity_map::ServerEntityMap,
@@ -108,7 +109,13 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
 
         unsafe {
             component_fns
-                .serialize(&ctx, rule_fns, ptr, &mut message)
+                .serialize(
+                    &ctx,
+                    rule_fns,
+                    ptr,
+                    component_fns.select_lod(None),
+                    &mut message,
+                )
                 .expect("serialization into memory should never fail");
         }
 
@@ -124,9 +131,13 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
         self.world_scope(|world| {
             world.resource_scope(|world, mut entity_map: Mut<ServerEntityMap>| {
                 world.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
+                    let mut delta_bases = world
+                        .remove_resource::<DeltaBaseCache>()
+                        .unwrap_or_default();
                     let mut queue = CommandQueue::default();
-                    let mut entity = DeferredEntity::new(world, entity);
-                    let mut commands = entity.commands(&mut queue);
+                    let mut changes = DeferredChanges::default();
+                    let mut deferred_entity = DeferredEntity::new(world, &mut changes, entity);
+                    let mut commands = deferred_entity.commands(&mut queue);
 
                     let (component_id, component_fns, rule_fns) = registry.get(fns_id);
                     let mut cursor = Cursor::new(data);
@@ -139,13 +150,16 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
                                 &mut ctx,
                                 rule_fns,
                                 &entity_markers,
-                                &mut entity,
+                                &mut delta_bases,
+                                &mut deferred_entity,
                                 &mut cursor,
                             )
                             .expect("writing data into an entity shouldn't fail");
                     }
 
+                    changes.apply(world, entity);
                     queue.apply(world);
+                    world.insert_resource(delta_bases);
                 })
             })
         });
@@ -162,8 +176,9 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
         self.world_scope(|world| {
             world.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
                 let mut queue = CommandQueue::default();
-                let mut entity = DeferredEntity::new(world, entity);
-                let mut commands = entity.commands(&mut queue);
+                let mut changes = DeferredChanges::default();
+                let mut deferred_entity = DeferredEntity::new(world, &mut changes, entity);
+                let mut commands = deferred_entity.commands(&mut queue);
 
                 let (component_id, component_fns, _) = registry.get(fns_id);
                 let mut ctx = RemoveCtx {
@@ -172,7 +187,7 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
                     component_id,
                 };
 
-                component_fns.remove(&mut ctx, &entity_markers, &mut entity);
+                component_fns.remove(&mut ctx, &entity_markers, &mut deferred_entity);
 
                 queue.apply(world);
             })
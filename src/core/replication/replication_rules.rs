@@ -7,13 +7,50 @@ use bevy::{
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::replication_registry::{rule_fns::RuleFns, FnsId, ReplicationRegistry};
+use super::{
+    prediction_registry::{PredictFn, PredictionRegistry},
+    replicated_resources::{ReplicatedResources, ResourceFns},
+    replication_registry::{
+        change_filter::ChangeFilterFn,
+        delta_fns::Delta,
+        quantize::{self, Quantize},
+        rule_fns::{ConditionFn, DeserializeFn, RuleFns, SerializeFn},
+        FnsId, ReplicationRegistry,
+    },
+};
+#[cfg(feature = "server")]
+use super::{Replicated, ReplicationSleeping};
+#[cfg(feature = "client")]
+use crate::{
+    client::{ClientPlugin, ClientSet},
+    core::common_conditions::client_connected,
+};
+#[cfg(feature = "server")]
+use crate::{
+    core::common_conditions::server_running,
+    server::{server_tick::ServerTick, ServerPlugin, ServerSet},
+};
+#[cfg(feature = "server")]
+use bevy::ecs::entity::EntityHashMap;
+
+/// Helper bound for components replicated as-is with [`AppRuleExt::replicate`].
+///
+/// Implemented for all types that implement [`Serialize`] and [`DeserializeOwned`].
+/// Exists only to provide a readable error message at the call site when one of them is missing.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` must implement `serde::Serialize` and `serde::Deserialize` to be replicated",
+    label = "missing serde implementation",
+    note = "derive them or use `replicate_with` to provide custom serialization functions"
+)]
+pub trait Replicable: Serialize + DeserializeOwned {}
+
+impl<T: Serialize + DeserializeOwned> Replicable for T {}
 
 /// Replication functions for [`App`].
 pub trait AppRuleExt {
     /// Creates a replication rule for a single component.
     ///
-    /// The component will be replicated if its entity contains the [`Replicated`](super::Replicated)
+    /// The component will be replicated if its entity contains the [`Replicated`]
     /// marker component.
     ///
     /// Component will be serialized and deserialized as-is using bincode.
@@ -25,7 +62,7 @@ pub trait AppRuleExt {
     /// from the quick start guide.
     fn replicate<C>(&mut self) -> &mut Self
     where
-        C: Component + Serialize + DeserializeOwned,
+        C: Component + Replicable,
     {
         self.replicate_with::<C>(RuleFns::default())
     }
@@ -59,11 +96,43 @@ pub trait AppRuleExt {
     **/
     fn replicate_mapped<C>(&mut self) -> &mut Self
     where
-        C: Component + Serialize + DeserializeOwned + MapEntities,
+        C: Component + Replicable + MapEntities,
     {
         self.replicate_with::<C>(RuleFns::default_mapped())
     }
 
+    /**
+    Same as [`Self::replicate`], but sends mutations only if `condition` returns `true`.
+
+    The condition is checked on the server only for components that were changed according to change detection.
+    If it returns `false`, the component will be treated as unchanged.
+    Insertions are always sent.
+
+    See also [`RuleFns::with_condition`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_if::<Health>(|health| health.0 % 10 == 0);
+
+    /// Clients need health only with the precision of 10 points.
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+    ```
+    **/
+    fn replicate_if<C>(&mut self, condition: ConditionFn<C>) -> &mut Self
+    where
+        C: Component + Replicable,
+    {
+        self.replicate_with::<C>(RuleFns::default().with_condition(condition))
+    }
+
     /**
     Same as [`Self::replicate`], but uses the specified functions for serialization and deserialization.
 
@@ -117,6 +186,72 @@ pub trait AppRuleExt {
     where
         C: Component;
 
+    /**
+    Same as [`Self::replicate_with`], but tags the serialized component with a schema `version`.
+
+    Useful for live updates, when the server may run a newer schema while some clients still use the old one.
+
+    The version is written before the component data. On deserialization, data with an older version
+    will be passed to `deserialize_legacy` and data with the same or newer version to `deserialize_current`.
+    Calling it again for the same component replaces the previous version and functions,
+    so a newer release can register the next migration step while keeping the wire format compatible.
+
+    The version can be obtained with [`ReplicationRegistry::component_schema_version`].
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::replication_registry::ctx::{SerializeCtx, WriteCtx},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_schema_version(2, serialize_health, deserialize_legacy, deserialize_health);
+
+    /// Previously health was stored as `u8`.
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(u32);
+
+    fn serialize_health(
+        _ctx: &SerializeCtx,
+        health: &Health,
+        message: &mut Vec<u8>,
+    ) -> bincode::Result<()> {
+        bincode::serialize_into(message, health)
+    }
+
+    fn deserialize_legacy(
+        _ctx: &mut WriteCtx,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<Health> {
+        let health: u8 = bincode::deserialize_from(cursor)?;
+        Ok(Health(health.into()))
+    }
+
+    fn deserialize_health(
+        _ctx: &mut WriteCtx,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<Health> {
+        bincode::deserialize_from(cursor)
+    }
+    ```
+    */
+    fn replicate_with_schema_version<C>(
+        &mut self,
+        version: u32,
+        serialize: SerializeFn<C>,
+        deserialize_legacy: DeserializeFn<C>,
+        deserialize_current: DeserializeFn<C>,
+    ) -> &mut Self
+    where
+        C: Component;
+
     /**
     Creates a replication rule for a group of components.
 
@@ -157,6 +292,426 @@ pub trait AppRuleExt {
     ```
     **/
     fn replicate_group<C: GroupReplication>(&mut self) -> &mut Self;
+
+    /**
+    Same as [`Self::replicate_with`], but sends mutations as a diff from the value
+    the client has already received.
+
+    Useful for large components where only a small part changes, such as pathfinding grids or height maps.
+
+    On mutation, the server computes [`Delta::diff`] against the last value acknowledged by the client.
+    If the serialized diff is smaller than the full value, the diff is sent. Otherwise the full value is sent.
+    Insertions are always sent as full values. The client reconstructs the value with [`Delta::patch`].
+
+    Since the server needs to remember sent values until the client acknowledges newer ones,
+    this trades memory for bandwidth. Don't combine it with [`RuleFns::with_condition`] because
+    the client won't be able to reconstruct values that were skipped.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::replication_registry::{delta_fns::Delta, rule_fns::RuleFns},
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_delta(RuleFns::<HeightMap>::default());
+
+    #[derive(Component, Clone, Deserialize, Serialize)]
+    struct HeightMap(Vec<u8>);
+
+    impl Delta for HeightMap {
+        /// Changed heights with their indices.
+        type Diff = Vec<(usize, u8)>;
+
+        fn diff(old: &Self, new: &Self) -> Self::Diff {
+            old.0
+                .iter()
+                .zip(&new.0)
+                .enumerate()
+                .filter(|(_, (old, new))| old != new)
+                .map(|(index, (_, &new))| (index, new))
+                .collect()
+        }
+
+        fn patch(base: &Self, diff: &Self::Diff) -> Self {
+            let mut heights = base.0.clone();
+            for &(index, height) in diff {
+                heights[index] = height;
+            }
+            Self(heights)
+        }
+    }
+    ```
+    **/
+    fn replicate_with_delta<C: Delta>(&mut self, rule_fns: RuleFns<C>) -> &mut Self;
+
+    /**
+    Same as [`Self::replicate_with`], but doesn't send mutations that don't change the value.
+
+    Bevy marks a component as changed on any mutable access, even if the written value is the same.
+    With this rule, the server stores the last sent value of the component for each entity and
+    compares it with the current one using `equal`. If it returns `true`, the mutation is skipped.
+
+    The comparison is shared between all clients and happens only for mutations;
+    insertions are always sent.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::replication_registry::rule_fns::RuleFns, prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_change_filter(RuleFns::<Position>::default(), |a, b| {
+        a.0.distance_squared(b.0) < 0.0001
+    });
+
+    #[derive(Component, Clone, Deserialize, Serialize)]
+    struct Position(Vec2);
+    ```
+    **/
+    fn replicate_with_change_filter<C>(
+        &mut self,
+        rule_fns: RuleFns<C>,
+        equal: ChangeFilterFn<C>,
+    ) -> &mut Self
+    where
+        C: Component + Clone;
+
+    /**
+    Same as [`Self::replicate_with`], but switches to `fallback_fns` if the serialized component exceeds `max_bytes`.
+
+    Useful for components with variable-size payloads, such as procedural meshes or encoded audio,
+    that can be sent in lower quality when the full representation is too large.
+
+    The component is serialized with `rule_fns` first. If the result exceeds the limit,
+    it's discarded and the component is serialized with `fallback_fns` instead.
+    A flag byte is written before the data to tell clients which functions to use for deserialization.
+    Calling it again for the same component replaces the previous limit and fallback functions.
+
+    Not applied to components registered with [`Self::replicate_with_delta`].
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::replication_registry::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+        prelude::*,
+    };
+    use bincode::{DefaultOptions, Options};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_size_limit(
+        1024,
+        RuleFns::<Heights>::default(),
+        RuleFns::new(serialize_downsampled, deserialize_downsampled),
+    );
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Heights(Vec<f32>);
+
+    /// Sends every second height.
+    fn serialize_downsampled(
+        _ctx: &SerializeCtx,
+        heights: &Heights,
+        message: &mut Vec<u8>,
+    ) -> bincode::Result<()> {
+        let downsampled: Vec<_> = heights.0.iter().step_by(2).collect();
+        DefaultOptions::new().serialize_into(message, &downsampled)
+    }
+
+    /// Restores skipped heights by duplicating the received ones.
+    fn deserialize_downsampled(
+        _ctx: &mut WriteCtx,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<Heights> {
+        let downsampled: Vec<f32> = DefaultOptions::new().deserialize_from(cursor)?;
+        let heights = downsampled
+            .into_iter()
+            .flat_map(|height| [height, height])
+            .collect();
+        Ok(Heights(heights))
+    }
+    ```
+    **/
+    fn replicate_with_size_limit<C>(
+        &mut self,
+        max_bytes: usize,
+        rule_fns: RuleFns<C>,
+        fallback_fns: RuleFns<C>,
+    ) -> &mut Self
+    where
+        C: Component;
+
+    /**
+    Same as [`Self::replicate_with`], but selects functions based on the distance to the client's camera.
+
+    Useful for reducing precision for entities that are far away from the client.
+
+    Each level of detail is a pair of distance threshold and functions. For each client,
+    the server computes the distance between [`Transform`] of the entity and [`Transform`] of the
+    camera entity assigned with [`ReplicatedClient::set_camera_entity`](super::replicated_clients::ReplicatedClient::set_camera_entity).
+    Levels are checked from the largest threshold to the smallest, and the first level whose threshold
+    is exceeded by the distance is selected. If no threshold is exceeded, the level with the smallest
+    threshold is used. If the client has no camera or one of the entities has no [`Transform`],
+    the level with the largest threshold is used.
+
+    The index of the selected level is written before the data to tell clients which functions to use
+    for deserialization. Since levels are selected on each send, a component that moved closer
+    will be sent in higher fidelity only on its next change.
+    Calling it again for the same component replaces the previous levels.
+
+    Not applied to components registered with [`Self::replicate_with_delta`].
+
+    # Panics
+
+    Panics if `lods` is empty.
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::replication_registry::{
+            ctx::{SerializeCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+        prelude::*,
+    };
+    use bincode::{DefaultOptions, Options};
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_lod(vec![
+        (50.0, RuleFns::new(serialize_rounded, deserialize_rounded)),
+        (0.0, RuleFns::<Health>::default()),
+    ]);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Health(f32);
+
+    /// Sends health rounded to an integer.
+    fn serialize_rounded(
+        _ctx: &SerializeCtx,
+        health: &Health,
+        message: &mut Vec<u8>,
+    ) -> bincode::Result<()> {
+        DefaultOptions::new().serialize_into(message, &(health.0.round() as u16))
+    }
+
+    fn deserialize_rounded(
+        _ctx: &mut WriteCtx,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<Health> {
+        let health: u16 = DefaultOptions::new().deserialize_from(cursor)?;
+        Ok(Health(health.into()))
+    }
+    ```
+    **/
+    fn replicate_with_lod<C>(&mut self, lods: Vec<(f32, RuleFns<C>)>) -> &mut Self
+    where
+        C: Component;
+
+    /**
+    Same as [`Self::replicate`], but serializes the component with reduced precision using quantizer `Q`.
+
+    Useful for floating-point components that don't need the full precision on clients.
+    See [`quantize`] for the built-in quantizers.
+
+    # Examples
+
+    ```
+    use std::io::Cursor;
+
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::replication_registry::quantize::Quantize, prelude::*,
+    };
+    use bincode::{DefaultOptions, Options};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_quantization::<Velocity, VelocityQuantizer>();
+
+    #[derive(Component)]
+    struct Velocity(Vec2);
+
+    /// Sends velocity with the precision of 0.01 units.
+    struct VelocityQuantizer;
+
+    impl Quantize<Velocity> for VelocityQuantizer {
+        fn quantize(velocity: &Velocity, message: &mut Vec<u8>) -> bincode::Result<()> {
+            let quantized = (velocity.0 * 100.0).round().as_ivec2();
+            DefaultOptions::new().serialize_into(message, &quantized)
+        }
+
+        fn dequantize(cursor: &mut Cursor<&[u8]>) -> bincode::Result<Velocity> {
+            let quantized: IVec2 = DefaultOptions::new().deserialize_from(cursor)?;
+            Ok(Velocity(quantized.as_vec2() / 100.0))
+        }
+    }
+    ```
+    **/
+    fn replicate_with_quantization<C, Q>(&mut self) -> &mut Self
+    where
+        C: Component,
+        Q: Quantize<C>,
+    {
+        self.replicate_with(RuleFns::new(
+            quantize::serialize_quantized::<C, Q>,
+            quantize::deserialize_quantized::<C, Q>,
+        ))
+    }
+
+    /**
+    Same as [`Self::replicate`], but also puts entities to sleep when the component doesn't change.
+
+    After `no_change_ticks` server ticks without changes to `C`, [`ReplicationSleeping`]
+    will be inserted into the entity. As soon as `C` changes, the marker will be removed
+    before sending replication for this tick.
+
+    Only changes to `C` wake up the entity. Changes to other components will be delayed until it wakes up.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_sleep_threshold::<Rock>(30);
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Rock(Vec3);
+    ```
+    **/
+    #[cfg(feature = "server")]
+    fn replicate_with_sleep_threshold<C>(&mut self, no_change_ticks: u32) -> &mut Self
+    where
+        C: Component + Replicable;
+
+    /**
+    Same as [`Self::replicate_with`], but also registers a `predict` function for the component
+    in [`PredictionRegistry`].
+
+    The function receives the last known value and the number of ticks to extrapolate.
+    It's not used by replication itself and exists so client-side prediction crates can obtain
+    predicted values via [`PredictionRegistry::predict`] without coupling to the network layer.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::{
+        core::replication::{
+            prediction_registry::PredictionRegistry, replication_registry::rule_fns::RuleFns,
+        },
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_with_prediction_fns(RuleFns::<Projectile>::default(), predict_projectile);
+
+    # let entity = app.world_mut().spawn(Projectile { position: Vec2::ZERO, velocity: Vec2::X }).id();
+    let registry = app.world().resource::<PredictionRegistry>();
+    let projectile = registry
+        .predict::<Projectile>(app.world(), entity, 2)
+        .unwrap();
+    assert_eq!(projectile.position, Vec2::new(2.0, 0.0));
+
+    #[derive(Component, Deserialize, Serialize)]
+    struct Projectile {
+        position: Vec2,
+        velocity: Vec2,
+    }
+
+    fn predict_projectile(projectile: &Projectile, delta_ticks: u32) -> Projectile {
+        Projectile {
+            position: projectile.position + projectile.velocity * delta_ticks as f32,
+            velocity: projectile.velocity,
+        }
+    }
+    ```
+    **/
+    fn replicate_with_prediction_fns<C>(
+        &mut self,
+        rule_fns: RuleFns<C>,
+        predict: PredictFn<C>,
+    ) -> &mut Self
+    where
+        C: Component;
+
+    /**
+    Replicates resource `R` from server to clients.
+
+    Clients receive the current value after the server starts replicating to them and after each change.
+    The received value overwrites the client's resource or inserts it if it's missing.
+
+    All resources are sent over [`ReplicationChannel::Resources`](crate::core::channels::ReplicationChannel::Resources)
+    right before entity updates from the same tick and applied on client in the same order.
+    Make sure that the registration order is the same on the client and server.
+
+    Resource will be serialized and deserialized as-is using bincode.
+    To customize it, use [`Self::replicate_resource_with`].
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    # let mut app = App::new();
+    # app.add_plugins(RepliconPlugins);
+    app.replicate_resource::<MatchSettings>();
+
+    #[derive(Resource, Deserialize, Serialize)]
+    struct MatchSettings {
+        max_score: u32,
+    }
+    ```
+    **/
+    fn replicate_resource<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Replicable,
+    {
+        self.replicate_resource_with(ResourceFns::<R>::default())
+    }
+
+    /// Same as [`Self::replicate_resource`], but uses the specified functions for serialization and deserialization.
+    fn replicate_resource_with<R: Resource>(&mut self, fns: ResourceFns<R>) -> &mut Self;
+
+    /// Same as [`Self::replicate_resource`], but sends the resource only once after the server
+    /// starts replicating to a client.
+    ///
+    /// Useful for values that don't change during a session, like a map seed.
+    fn replicate_resource_once<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Replicable;
 }
 
 impl AppRuleExt for App {
@@ -178,6 +733,83 @@ impl AppRuleExt for App {
         self
     }
 
+    fn replicate_with_schema_version<C>(
+        &mut self,
+        version: u32,
+        serialize: SerializeFn<C>,
+        deserialize_legacy: DeserializeFn<C>,
+        deserialize_current: DeserializeFn<C>,
+    ) -> &mut Self
+    where
+        C: Component,
+    {
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.set_schema(world, version, deserialize_legacy, deserialize_current);
+            });
+
+        self.replicate_with(RuleFns::new(serialize, deserialize_current))
+    }
+
+    fn replicate_with_delta<C: Delta>(&mut self, rule_fns: RuleFns<C>) -> &mut Self {
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.set_delta_fns::<C>(world);
+            });
+
+        self.replicate_with(rule_fns)
+    }
+
+    fn replicate_with_change_filter<C>(
+        &mut self,
+        rule_fns: RuleFns<C>,
+        equal: ChangeFilterFn<C>,
+    ) -> &mut Self
+    where
+        C: Component + Clone,
+    {
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.set_change_filter(world, equal);
+            });
+
+        self.replicate_with(rule_fns)
+    }
+
+    fn replicate_with_size_limit<C>(
+        &mut self,
+        max_bytes: usize,
+        rule_fns: RuleFns<C>,
+        fallback_fns: RuleFns<C>,
+    ) -> &mut Self
+    where
+        C: Component,
+    {
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.set_size_limit(world, max_bytes, fallback_fns);
+            });
+
+        self.replicate_with(rule_fns)
+    }
+
+    fn replicate_with_lod<C>(&mut self, mut lods: Vec<(f32, RuleFns<C>)>) -> &mut Self
+    where
+        C: Component,
+    {
+        lods.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        let (_, rule_fns) = lods
+            .pop()
+            .expect("at least one level of detail should be specified");
+
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.set_lods(world, lods);
+            });
+
+        self.replicate_with(rule_fns)
+    }
+
     fn replicate_group<C: GroupReplication>(&mut self) -> &mut Self {
         let rule =
             self.world_mut()
@@ -191,6 +823,115 @@ impl AppRuleExt for App {
 
         self
     }
+
+    #[cfg(feature = "server")]
+    fn replicate_with_sleep_threshold<C>(&mut self, no_change_ticks: u32) -> &mut Self
+    where
+        C: Component + Replicable,
+    {
+        self.replicate::<C>().add_systems(
+            PostUpdate,
+            update_sleeping::<C>(no_change_ticks)
+                .in_set(ServerSet::Send)
+                .before(ServerPlugin::send_replication)
+                .run_if(server_running)
+                .run_if(resource_changed::<ServerTick>),
+        )
+    }
+
+    fn replicate_with_prediction_fns<C>(
+        &mut self,
+        rule_fns: RuleFns<C>,
+        predict: PredictFn<C>,
+    ) -> &mut Self
+    where
+        C: Component,
+    {
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<PredictionRegistry>| {
+                registry.insert(world, predict);
+            });
+
+        self.replicate_with(rule_fns)
+    }
+
+    fn replicate_resource_with<R: Resource>(&mut self, fns: ResourceFns<R>) -> &mut Self {
+        add_resource_replication(self, fns, false)
+    }
+
+    fn replicate_resource_once<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Replicable,
+    {
+        add_resource_replication(self, ResourceFns::<R>::default(), true)
+    }
+}
+
+/// Assigns an index for `R` and adds systems to send and receive it.
+#[cfg_attr(not(feature = "server"), allow(unused_variables))]
+fn add_resource_replication<R: Resource>(
+    app: &mut App,
+    fns: ResourceFns<R>,
+    once: bool,
+) -> &mut App {
+    let index = app
+        .world_mut()
+        .resource_mut::<ReplicatedResources>()
+        .register();
+
+    #[cfg(feature = "server")]
+    app.add_systems(
+        PostUpdate,
+        super::replicated_resources::collect_resource(index, fns, once)
+            .map(Result::unwrap)
+            .in_set(ServerSet::Send)
+            .before(ServerPlugin::send_replication)
+            .run_if(server_running)
+            .run_if(resource_changed::<ServerTick>),
+    );
+
+    #[cfg(feature = "client")]
+    app.add_systems(
+        ClientPlugin::receive_schedule(app.world()),
+        super::replicated_resources::receive_resource(index, fns)
+            .after(ClientPlugin::receive_replication)
+            .in_set(ClientSet::Receive)
+            .run_if(client_connected),
+    );
+
+    app
+}
+
+/// Returns a system that inserts [`ReplicationSleeping`] after `no_change_ticks` without changes to `C`
+/// and removes it once `C` changes.
+#[cfg(feature = "server")]
+fn update_sleeping<C: Component>(
+    no_change_ticks: u32,
+) -> impl FnMut(
+    Commands,
+    Local<EntityHashMap<u32>>,
+    Query<(Entity, Ref<C>, Has<ReplicationSleeping>), With<Replicated>>,
+) {
+    move |mut commands, mut quiet_ticks, components| {
+        // Counters are tracked only for awake entities.
+        quiet_ticks.retain(|&entity, _| components.contains(entity));
+
+        for (entity, component, sleeping) in &components {
+            if component.is_changed() {
+                quiet_ticks.remove(&entity);
+                if sleeping {
+                    commands.entity(entity).remove::<ReplicationSleeping>();
+                }
+            } else if !sleeping {
+                let ticks = quiet_ticks.entry(entity).or_default();
+                *ticks += 1;
+                if *ticks >= no_change_ticks {
+                    quiet_ticks.remove(&entity);
+                    commands.entity(entity).insert(ReplicationSleeping);
+                }
+            }
+        }
+    }
 }
 
 /// All registered rules for components replication.
@@ -206,6 +947,43 @@ impl ReplicationRules {
 
         self.0.insert(index, rule);
     }
+
+    /// Returns all rules that match the current archetype of an entity in priority order.
+    ///
+    /// Returns an empty iterator if the entity doesn't exist.
+    /// Doesn't check for the [`Replicated`] marker.
+    pub fn rule_for_entity<'a>(
+        &'a self,
+        world: &'a World,
+        entity: Entity,
+    ) -> impl Iterator<Item = &'a ReplicationRule> {
+        let archetype = world
+            .entities()
+            .get(entity)
+            .map(|location| &world.archetypes()[location.archetype_id]);
+
+        self.iter()
+            .filter(move |rule| archetype.is_some_and(|archetype| rule.matches(archetype)))
+    }
+
+    /// Returns components of an entity that will be serialized according to the matching rules.
+    ///
+    /// Components present in multiple rules are returned only once,
+    /// in the order of the first rule with the highest priority.
+    pub fn components_for_entity<'a>(
+        &'a self,
+        world: &'a World,
+        entity: Entity,
+    ) -> impl Iterator<Item = ComponentId> + 'a {
+        let mut returned = HashSet::new();
+        self.rule_for_entity(world, entity)
+            .flat_map(|rule| {
+                rule.components
+                    .iter()
+                    .map(|&(component_id, _)| component_id)
+            })
+            .filter(move |&component_id| returned.insert(component_id))
+    }
 }
 
 /// Describes a replicated component or a group of components.
@@ -369,6 +1147,72 @@ mod tests {
         assert_eq!(priorities, [2, 2, 1, 1, 1, 1]);
     }
 
+    #[test]
+    fn entity_rules() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationRegistry>()
+            .replicate::<ComponentA>()
+            .replicate_group::<(ComponentA, ComponentB)>()
+            .replicate_group::<(ComponentB, ComponentC)>()
+            .replicate::<ComponentD>();
+
+        let entity = app.world_mut().spawn((ComponentA, ComponentB)).id();
+
+        let world = app.world();
+        let replication_rules = world.resource::<ReplicationRules>();
+        let priorities: Vec<_> = replication_rules
+            .rule_for_entity(world, entity)
+            .map(|rule| rule.priority)
+            .collect();
+        assert_eq!(priorities, [2, 1]);
+
+        let component_a = world.component_id::<ComponentA>().unwrap();
+        let component_b = world.component_id::<ComponentB>().unwrap();
+        let components: Vec<_> = replication_rules
+            .components_for_entity(world, entity)
+            .collect();
+        assert_eq!(components, [component_a, component_b]);
+    }
+
+    #[test]
+    fn overlapping_entity_rules() {
+        let mut app = App::new();
+        app.init_resource::<ReplicationRules>()
+            .init_resource::<ReplicationRegistry>()
+            .replicate_group::<(ComponentA, ComponentB)>()
+            .replicate_group::<(ComponentB, ComponentC)>()
+            .replicate::<ComponentD>();
+
+        let entity = app
+            .world_mut()
+            .spawn((ComponentA, ComponentB, ComponentC))
+            .id();
+
+        let world = app.world();
+        let replication_rules = world.resource::<ReplicationRules>();
+        assert_eq!(replication_rules.rule_for_entity(world, entity).count(), 2);
+
+        let component_a = world.component_id::<ComponentA>().unwrap();
+        let component_b = world.component_id::<ComponentB>().unwrap();
+        let component_c = world.component_id::<ComponentC>().unwrap();
+        let mut components: Vec<_> = replication_rules
+            .components_for_entity(world, entity)
+            .collect();
+        components.sort();
+        assert_eq!(components, [component_a, component_b, component_c]);
+
+        let despawned = app.world_mut().spawn(ComponentD).id();
+        app.world_mut().despawn(despawned);
+
+        let world = app.world();
+        let replication_rules = world.resource::<ReplicationRules>();
+        assert_eq!(
+            replication_rules.rule_for_entity(world, despawned).count(),
+            0
+        );
+    }
+
     #[derive(Serialize, Deserialize, Component)]
     struct ComponentA;
 
@@ -10,6 +10,7 @@ bitflags! {
         const DESPAWNS = 0b00000010;
         const REMOVALS = 0b00000100;
         const CHANGES = 0b00001000;
+        const METADATA = 0b00010000;
     }
 }
 
@@ -38,7 +39,11 @@ mod tests {
         );
         assert_eq!(
             UpdateMessageFlags::all().last(),
-            UpdateMessageFlags::CHANGES
+            UpdateMessageFlags::METADATA
+        );
+        assert_eq!(
+            (UpdateMessageFlags::CHANGES | UpdateMessageFlags::METADATA).last(),
+            UpdateMessageFlags::METADATA
         );
         assert_eq!(
             (UpdateMessageFlags::DESPAWNS | UpdateMessageFlags::REMOVALS).last(),
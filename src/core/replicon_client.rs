@@ -1,3 +1,5 @@
+use std::mem;
+
 use bevy::prelude::*;
 use bytes::Bytes;
 
@@ -30,6 +32,12 @@ pub struct RepliconClient {
     /// List of sent messages and their channels since the last tick.
     sent_messages: Vec<(u8, Bytes)>,
 
+    /// Total size of [`Self::sent_messages`] in bytes.
+    sent_bytes: usize,
+
+    /// Statistics from the last [`Self::drain_sent`] call.
+    last_drain_stats: Option<DrainStats>,
+
     rtt: f64,
     packet_loss: f64,
     sent_bps: f64,
@@ -42,19 +50,6 @@ impl RepliconClient {
         self.received_messages.resize(channels_count, Vec::new());
     }
 
-    /// Returns number of received messages for a channel.
-    ///
-    /// See also [`Self::receive`].
-    pub(crate) fn received_count<I: Into<u8>>(&self, channel_id: I) -> usize {
-        let channel_id = channel_id.into();
-        let channel_messages = self
-            .received_messages
-            .get(channel_id as usize)
-            .unwrap_or_else(|| panic!("client should have a receive channel with id {channel_id}"));
-
-        channel_messages.len()
-    }
-
     /// Receives all available messages from the server over a channel.
     ///
     /// All messages will be drained.
@@ -102,6 +97,7 @@ impl RepliconClient {
 
         trace!("sending {} bytes over channel {channel_id}", message.len());
 
+        self.sent_bytes += message.len();
         self.sent_messages.push((channel_id, message));
     }
 
@@ -110,19 +106,49 @@ impl RepliconClient {
     /// Discards all messages if the state changes from [`RepliconClientStatus::Connected`].
     /// See also [`Self::status`].
     ///
+    /// The status should go from [`RepliconClientStatus::Disconnected`] to [`RepliconClientStatus::Connecting`],
+    /// then to [`RepliconClientStatus::Connected`] and back to [`RepliconClientStatus::Disconnected`].
+    /// Going to [`RepliconClientStatus::Disconnected`] is allowed from any status.
+    /// Setting [`RepliconClientStatus::Connecting`] again while connecting is ignored.
+    /// Any other transition, such as going from [`RepliconClientStatus::Disconnected`] directly
+    /// to [`RepliconClientStatus::Connected`], is considered a backend bug. It panics if `debug_assertions`
+    /// are enabled and logs a warning otherwise.
+    ///
     /// <div class="warning">
     ///
     /// Should only be called from the messaging backend when the client status changes.
     ///
     /// </div>
     pub fn set_status(&mut self, status: RepliconClientStatus) {
+        if self.status == RepliconClientStatus::Connecting
+            && status == RepliconClientStatus::Connecting
+        {
+            return;
+        }
+
         debug!("changing `RepliconClient` status to `{status:?}`");
 
+        if !self.status.can_change_to(status) {
+            if cfg!(debug_assertions) {
+                panic!(
+                    "`RepliconClient` status can't be changed from `{:?}` to `{status:?}`",
+                    self.status
+                );
+            } else {
+                warn!(
+                    "`RepliconClient` status can't be changed from `{:?}` to `{status:?}`",
+                    self.status
+                );
+            }
+        }
+
         if self.is_connected() && !matches!(status, RepliconClientStatus::Connected { .. }) {
             for channel_messages in &mut self.received_messages {
                 channel_messages.clear();
             }
             self.sent_messages.clear();
+            self.sent_bytes = 0;
+            self.last_drain_stats = None;
 
             self.rtt = 0.0;
             self.packet_loss = 0.0;
@@ -180,15 +206,28 @@ impl RepliconClient {
 
     /// Removes all sent messages, returning them as an iterator with channel.
     ///
+    /// Statistics about drained messages will be available via [`Self::last_drain_stats`].
+    ///
     /// <div class="warning">
     ///
     /// Should only be called from the messaging backend.
     ///
     /// </div>
     pub fn drain_sent(&mut self) -> impl Iterator<Item = (u8, Bytes)> + '_ {
+        self.last_drain_stats = Some(DrainStats {
+            total_bytes: mem::take(&mut self.sent_bytes),
+            message_count: self.sent_messages.len(),
+        });
         self.sent_messages.drain(..)
     }
 
+    /// Returns statistics about messages returned by the last [`Self::drain_sent`] call.
+    ///
+    /// Returns [`None`] if nothing was drained since the last disconnect.
+    pub fn last_drain_stats(&self) -> Option<DrainStats> {
+        self.last_drain_stats
+    }
+
     /// Adds a message from the server to the list of received messages.
     ///
     /// <div class="warning">
@@ -284,6 +323,16 @@ impl RepliconClient {
     }
 }
 
+/// Statistics about messages drained with [`RepliconClient::drain_sent`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrainStats {
+    /// Total size of all drained messages in bytes.
+    pub total_bytes: usize,
+
+    /// Number of drained messages.
+    pub message_count: usize,
+}
+
 /// Connection status of the [`RepliconClient`].
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum RepliconClientStatus {
@@ -298,3 +347,15 @@ pub enum RepliconClientStatus {
     /// Needed only for users to access ID independent from messaging library.
     Connected { client_id: Option<ClientId> },
 }
+
+impl RepliconClientStatus {
+    /// Returns `true` if a backend is allowed to change the status from `self` to `status`.
+    fn can_change_to(self, status: Self) -> bool {
+        matches!(
+            (self, status),
+            (_, Self::Disconnected)
+                | (Self::Disconnected, Self::Connecting)
+                | (Self::Connecting, Self::Connected { .. })
+        )
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bytes::Bytes;
+use serde::{Deserialize, Serialize};
 
 use crate::core::ClientId;
 
@@ -11,6 +12,8 @@ use crate::core::ClientId;
 ///   A system to forward messages from the backend to Replicon should run in [`ServerSet::ReceivePackets`](crate::server::ServerSet::ReceivePackets).
 /// - For sending messages, [`Self::drain_sent`] should be used to drain all sent messages.
 ///   A system to forward messages from Replicon to the backend should run in [`ServerSet::SendPackets`](crate::server::ServerSet::SendPackets).
+/// - For disconnecting clients, [`Self::drain_disconnect_requests`] should be used after draining
+///   sent messages to disconnect the requested clients.
 ///
 /// Inserted as resource by [`ServerPlugin`](crate::server::ServerPlugin).
 #[derive(Resource, Default)]
@@ -28,6 +31,14 @@ pub struct RepliconServer {
 
     /// List of sent messages for each channel since the last tick.
     sent_messages: Vec<(ClientId, u8, Bytes)>,
+
+    /// Clients requested to be kicked with their reasons.
+    ///
+    /// Drained by [`ServerPlugin`](crate::server::ServerPlugin) to send [`KickReason`].
+    pending_kicks: Vec<(ClientId, String)>,
+
+    /// Clients that the backend should disconnect.
+    disconnect_requests: Vec<ClientId>,
 }
 
 impl RepliconServer {
@@ -43,6 +54,10 @@ impl RepliconServer {
         }
         self.sent_messages
             .retain(|&(sender_id, ..)| sender_id != client_id);
+        self.pending_kicks
+            .retain(|&(kicked_id, _)| kicked_id != client_id);
+        self.disconnect_requests
+            .retain(|&disconnect_id| disconnect_id != client_id);
     }
 
     /// Receives all available messages from clients over a channel.
@@ -118,11 +133,47 @@ impl RepliconServer {
                 receive_channel.clear();
             }
             self.sent_messages.clear();
+            self.pending_kicks.clear();
+            self.disconnect_requests.clear();
         }
 
         self.running = running;
     }
 
+    /// Disconnects a client, informing it about the reason.
+    ///
+    /// The client will receive [`KickReason`] as a server event before the disconnection.
+    /// The disconnection itself is performed by the messaging backend after the current tick's messages are sent.
+    pub fn kick_with_reason(&mut self, client_id: ClientId, reason: &str) {
+        if !self.running {
+            warn!("trying to kick `{client_id:?}` when the server is not running");
+            return;
+        }
+
+        info!("kicking `{client_id:?}`: {reason}");
+        self.pending_kicks.push((client_id, reason.to_string()));
+    }
+
+    /// Removes all pending kicks, returning them as an iterator with client ID and reason.
+    ///
+    /// Requests the messaging backend to disconnect all kicked clients.
+    pub(crate) fn drain_kicks(&mut self) -> impl Iterator<Item = (ClientId, String)> + '_ {
+        self.disconnect_requests
+            .extend(self.pending_kicks.iter().map(|&(client_id, _)| client_id));
+        self.pending_kicks.drain(..)
+    }
+
+    /// Removes all disconnect requests, returning them as an iterator with client IDs.
+    ///
+    /// <div class="warning">
+    ///
+    /// Should only be called from the messaging backend after [`Self::drain_sent`].
+    ///
+    /// </div>
+    pub fn drain_disconnect_requests(&mut self) -> impl Iterator<Item = ClientId> + '_ {
+        self.disconnect_requests.drain(..)
+    }
+
     /// Returns `true` if the server is running.
     #[inline]
     pub fn is_running(&self) -> bool {
@@ -177,3 +228,9 @@ impl RepliconServer {
         receive_channel.push((client_id, message.into()));
     }
 }
+
+/// A server event with the reason for disconnecting the client.
+///
+/// Sent by [`RepliconServer::kick_with_reason`] and registered automatically as an independent event.
+#[derive(Event, Clone, Debug, Deserialize, Serialize)]
+pub struct KickReason(pub String);
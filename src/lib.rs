@@ -301,6 +301,12 @@ This is also where you want to add all component registrations.
 
 </div>
 
+#### Resources
+
+Resources can be replicated from server to clients with [`AppRuleExt::replicate_resource()`].
+Clients will receive the current value once the server starts replicating to them and after each change.
+If the resource doesn't change during a session, you can use [`AppRuleExt::replicate_resource_once()`] instead.
+
 ### Mapping to existing client entities
 
 If you want the server to replicate an entity into a client entity that was already spawned on a client, see [`ClientEntityMap`].
@@ -359,7 +365,8 @@ struct NotReplicatedComponent;
 
 This pairs nicely with server state serialization and keeps saves clean.
 You can use [`replicate_into`](scene::replicate_into) to
-fill [`DynamicScene`] with replicated entities and their components.
+fill [`DynamicScene`] with replicated entities and their components
+and [`replicate_from`](scene::replicate_from) to restore them.
 On deserialization all missing required components will be inserted, and initialization
 systems will restore the correct game state.
 
@@ -530,6 +537,8 @@ Just like with components, all networked events should be registered in the same
 
 You can control which parts of the world are visible for each client by setting visibility policy
 in [`ServerPlugin`] to [`VisibilityPolicy::Whitelist`] or [`VisibilityPolicy::Blacklist`].
+With [`VisibilityPolicy::Hierarchical`] you only need to set visibility for root entities,
+their children will inherit it automatically.
 
 In order to set which entity is visible, you need to use the [`ReplicatedClients`] resource
 to obtain the [`ReplicatedClient`] for a specific client and get its [`ClientVisibility`]:
@@ -610,6 +619,7 @@ But on server we use `debug` for it to avoid flooding server logs with errors ca
 */
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+pub mod bundles;
 #[cfg(feature = "client")]
 pub mod client;
 pub mod core;
@@ -630,19 +640,23 @@ pub mod prelude {
             connected_clients::ConnectedClients,
             event::{
                 client_event::{ClientEventAppExt, FromClient},
-                server_event::{SendMode, ServerEventAppExt, ToClients},
+                server_event::{
+                    BufferedServerEvents, ClientGroup, ClientGroupRegistry, SendMode,
+                    ServerEventAppExt, ToClients,
+                },
             },
             replication::{
                 command_markers::AppMarkerExt,
                 replicated_clients::{
-                    client_visibility::ClientVisibility, ReplicatedClient, ReplicatedClients,
-                    VisibilityPolicy,
+                    client_tag_filter::ClientTagFilter, client_visibility::ClientVisibility,
+                    ReplicatedClient, ReplicatedClients, VisibilityPolicy,
                 },
                 replication_rules::AppRuleExt,
-                Replicated,
+                ClientSnapshotRequest, EntityReplicationOrder, Replicated, ReplicationPaused,
+                ReplicationPriority, ReplicationSleeping, ReplicationTags,
             },
             replicon_client::{RepliconClient, RepliconClientStatus},
-            replicon_server::RepliconServer,
+            replicon_server::{KickReason, RepliconServer},
             ClientId, RepliconCorePlugin,
         },
         RepliconPlugins,
@@ -650,20 +664,23 @@ pub mod prelude {
 
     #[cfg(feature = "client")]
     pub use super::client::{
-        event::ClientEventPlugin, ClientPlugin, ClientReplicationStats, ClientSet,
+        event::ClientEventPlugin, ClientPlugin, ClientReplicationStats, ClientSet, DropPolicy,
+        ReceiveOrder,
     };
 
     #[cfg(feature = "server")]
     pub use super::server::{
         client_entity_map::{ClientEntityMap, ClientMapping},
         event::ServerEventPlugin,
-        ServerEvent, ServerPlugin, ServerSet, StartReplication, TickPolicy,
+        ServerEvent, ServerPlugin, ServerReplicationStats, ServerSet, StartReplication, TickPolicy,
     };
 
     #[cfg(feature = "client_diagnostics")]
     pub use super::client::diagnostics::ClientDiagnosticsPlugin;
     #[cfg(feature = "parent_sync")]
     pub use super::parent_sync::{ParentSync, ParentSyncPlugin};
+    #[cfg(feature = "server_diagnostics")]
+    pub use super::server::diagnostics::ServerDiagnosticsPlugin;
 }
 
 pub use bincode;
@@ -681,6 +698,7 @@ use prelude::*;
 /// * [`ClientEventPlugin`] - with feature `client`.
 /// * [`ParentSyncPlugin`] - with feature `parent_sync`.
 /// * [`ClientDiagnosticsPlugin`] - with feature `client_diagnostics`.
+/// * [`ServerDiagnosticsPlugin`] - with feature `server_diagnostics`.
 pub struct RepliconPlugins;
 
 impl PluginGroup for RepliconPlugins {
@@ -695,7 +713,7 @@ impl PluginGroup for RepliconPlugins {
 
         #[cfg(feature = "client")]
         {
-            group = group.add(ClientPlugin).add(ClientEventPlugin);
+            group = group.add(ClientPlugin::default()).add(ClientEventPlugin);
         }
 
         #[cfg(feature = "parent_sync")]
@@ -708,6 +726,74 @@ impl PluginGroup for RepliconPlugins {
             group = group.add(ClientDiagnosticsPlugin);
         }
 
+        #[cfg(feature = "server_diagnostics")]
+        {
+            group = group.add(ServerDiagnosticsPlugin);
+        }
+
         group
     }
 }
+
+impl RepliconPlugins {
+    /**
+    Builds the group with the specified [`ServerPlugin`] settings.
+
+    Shorthand for `RepliconPlugins.build().set(settings)`.
+
+    Returns [`PluginGroupBuilder`] instead of `Self` because [`RepliconPlugins`] is a unit struct
+    and can't store the settings. To customize other plugins, including [`ClientPlugin`],
+    call [`PluginGroupBuilder::set`] on the result.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    app.add_plugins(RepliconPlugins.with_server_settings(ServerPlugin {
+        tick_policy: TickPolicy::EveryFrame,
+        ..Default::default()
+    }));
+    ```
+    */
+    #[cfg(feature = "server")]
+    pub fn with_server_settings(self, settings: ServerPlugin) -> PluginGroupBuilder {
+        self.build().set(settings)
+    }
+
+    /**
+    Builds the group with the specified [`ClientPlugin`] settings.
+
+    Shorthand for `RepliconPlugins.build().set(settings)`.
+
+    Like [`Self::with_server_settings`], returns [`PluginGroupBuilder`] instead of `Self`.
+    To customize other plugins, including [`ServerPlugin`], call [`PluginGroupBuilder::set`]
+    on the result.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+
+    # let mut app = App::new();
+    app.add_plugins(
+        RepliconPlugins
+            .with_client_settings(ClientPlugin {
+                detailed_replicated_events: true,
+                ..Default::default()
+            })
+            .set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+    );
+    ```
+    */
+    #[cfg(feature = "client")]
+    pub fn with_client_settings(self, settings: ClientPlugin) -> PluginGroupBuilder {
+        self.build().set(settings)
+    }
+}
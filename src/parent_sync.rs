@@ -8,7 +8,7 @@ use bevy::{
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "client")]
-use crate::client::ClientSet;
+use crate::client::{ClientPlugin, ClientSet};
 use crate::core::{
     common_conditions::*, replication::replication_rules::AppRuleExt,
     replicon_client::RepliconClient,
@@ -34,7 +34,7 @@ impl Plugin for ParentSyncPlugin {
 
         #[cfg(feature = "client")]
         app.add_systems(
-            PreUpdate,
+            ClientPlugin::receive_schedule(app.world()),
             Self::sync_hierarchy.in_set(ClientSet::SyncHierarchy),
         );
 
@@ -131,7 +131,7 @@ impl ParentSyncPlugin {
 /// ```
 #[derive(Component, Default, Reflect, Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
 #[reflect(Component, MapEntities)]
-pub struct ParentSync(Option<Entity>);
+pub struct ParentSync(pub(crate) Option<Entity>);
 
 impl MapEntities for ParentSync {
     fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
@@ -1,6 +1,15 @@
-use bevy::{ecs::entity::EntityHashMap, prelude::*, scene::DynamicEntity};
+use bevy::{
+    ecs::{component::ComponentId, entity::EntityHashMap},
+    prelude::*,
+    scene::{DynamicEntity, SceneSpawnError},
+};
 
-use crate::{core::replication::replication_rules::ReplicationRules, Replicated};
+#[cfg(feature = "parent_sync")]
+use crate::parent_sync::ParentSync;
+use crate::{
+    core::{replication::replication_rules::ReplicationRules, server_entity_map::ServerEntityMap},
+    Replicated,
+};
 
 /**
 Fills scene with all replicated entities and their components.
@@ -44,6 +53,39 @@ for entity in &mut scene.entities {
 ```
 */
 pub fn replicate_into(scene: &mut DynamicScene, world: &World) {
+    replicate_into_filtered(scene, world, |_, _| true);
+}
+
+/**
+Same as [`replicate_into`], but includes only components for which `filter` returns `true`.
+
+The filter is called for each replicated entity and component ID pair.
+Useful to exclude session-specific state, like connection data or temporary effects, from saves.
+Entities are still included even if all their components were filtered out.
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, scene};
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+
+let stun_id = app.world_mut().register_component::<Stun>();
+let mut scene = DynamicScene::default();
+scene::replicate_into_filtered(&mut scene, app.world(), |_, component_id| {
+    component_id != stun_id
+});
+
+#[derive(Component)]
+struct Stun;
+```
+*/
+pub fn replicate_into_filtered(
+    scene: &mut DynamicScene,
+    world: &World,
+    filter: impl Fn(Entity, ComponentId) -> bool,
+) {
     let Some(marker_id) = world.component_id::<Replicated>() else {
         // Components are initialized lazily.
         // If there is no replication marker, then we have nothing to replicate.
@@ -95,6 +137,11 @@ pub fn replicate_into(scene: &mut DynamicScene, world: &World) {
                     .unwrap_or_else(|| panic!("`{type_name}` should reflect `FromReflect`"));
 
                 for entity in archetype.entities() {
+                    if !(filter)(entity.id(), component_id) {
+                        debug!("filtering out `{type_name}` from `{}`", entity.id());
+                        continue;
+                    }
+
                     let component = reflect_component
                         .reflect(world.entity(entity.id()))
                         .unwrap_or_else(|| panic!("entity should have `{type_name}`"));
@@ -122,3 +169,92 @@ pub fn replicate_into(scene: &mut DynamicScene, world: &World) {
         .map(|(entity, components)| DynamicEntity { entity, components });
     scene.entities.extend(dyn_entities_iter);
 }
+
+/**
+Writes a scene created by [`replicate_into`] back into the world as replicated entities.
+
+Spawns entities using [`DynamicScene::write_to_world`] and inserts [`Replicated`] into them.
+Scene entities are treated as server entities, so if [`ServerEntityMap`] is present (on client),
+each spawned entity will be mapped to its scene entity to continue receiving replication for it.
+If a scene entity is already mapped, for example when the same scene is loaded twice,
+the previous mapping will be replaced.
+
+`entity_map` works the same way as in [`DynamicScene::write_to_world`]. After the call it will
+contain mappings from scene entities to world entities.
+*/
+pub fn replicate_from(
+    world: &mut World,
+    scene: &DynamicScene,
+    entity_map: &mut EntityHashMap<Entity>,
+) -> Result<(), SceneSpawnError> {
+    scene.write_to_world(world, entity_map)?;
+
+    for dyn_entity in &scene.entities {
+        let entity = entity_map[&dyn_entity.entity];
+        world.entity_mut(entity).insert(Replicated);
+
+        if let Some(mut server_map) = world.get_resource_mut::<ServerEntityMap>() {
+            if server_map.to_client().get(&dyn_entity.entity) != Some(&entity) {
+                // The scene could be loaded multiple times, replace the previous mappings.
+                if let Some(old_entity) = server_map.remove_by_server(dyn_entity.entity) {
+                    debug!(
+                        "replacing mapping from `{}` to `{old_entity}`",
+                        dyn_entity.entity
+                    );
+                }
+                server_map.remove_by_client(entity);
+
+                debug!("mapping `{}` to `{entity}`", dyn_entity.entity);
+                server_map.insert(dyn_entity.entity, entity);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/**
+Same as [`replicate_into`], but also preserves hierarchy between replicated entities.
+
+For each replicated entity with a replicated [`Parent`], [`ParentSync`] will be added
+to the scene unless the entity already has it. On deserialization [`ParentSyncPlugin`](crate::parent_sync::ParentSyncPlugin)
+will restore the hierarchy, so [`Parent`] doesn't need to be registered for replication.
+
+Parents without [`Replicated`] are skipped since they won't be present in the scene.
+*/
+#[cfg(feature = "parent_sync")]
+pub fn replicate_into_with_hierarchy(scene: &mut DynamicScene, world: &World) {
+    replicate_into(scene, world);
+
+    if world.component_id::<Parent>().is_none() {
+        // Hierarchy components are initialized lazily.
+        return;
+    }
+
+    for dyn_entity in &mut scene.entities {
+        let Ok(entity) = world.get_entity(dyn_entity.entity) else {
+            continue;
+        };
+        if entity.contains::<ParentSync>() {
+            continue;
+        }
+        let Some(parent) = entity.get::<Parent>() else {
+            continue;
+        };
+        if !world
+            .get_entity(**parent)
+            .is_ok_and(|parent| parent.contains::<Replicated>())
+        {
+            debug!(
+                "ignoring parent of `{}` because `{}` is not replicated",
+                dyn_entity.entity, **parent
+            );
+            continue;
+        }
+
+        debug!("adding `ParentSync` to `{}`", dyn_entity.entity);
+        dyn_entity
+            .components
+            .push(Box::new(ParentSync(Some(**parent))));
+    }
+}
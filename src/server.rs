@@ -1,49 +1,67 @@
 pub mod client_entity_map;
-pub(super) mod despawn_buffer;
+pub mod despawn_buffer;
+#[cfg(feature = "server_diagnostics")]
+pub mod diagnostics;
 pub mod event;
+pub mod hot_join_snapshot;
+pub mod network_stats_history;
+pub(super) mod ordered_entities;
 pub(super) mod removal_buffer;
 pub(super) mod replicated_archetypes;
 pub(super) mod replication_messages;
 pub mod server_tick;
 
-use std::{io::Cursor, mem, ops::Range, time::Duration};
+use std::{cmp::Reverse, io::Cursor, mem, ops::Range, time::Duration};
 
 use bevy::{
     ecs::{
         archetype::ArchetypeEntity,
-        component::{ComponentId, ComponentTicks, StorageType},
+        component::{ComponentId, ComponentTicks, StorageType, Tick},
+        entity::{EntityHashMap, EntityHashSet},
         storage::{SparseSets, Table},
         system::SystemChangeTick,
     },
     prelude::*,
     ptr::Ptr,
     time::common_conditions::on_timer,
+    utils::HashMap,
 };
+use smallvec::SmallVec;
 
 use crate::core::{
     channels::{ReplicationChannel, RepliconChannels},
     common_conditions::{server_just_stopped, server_running},
     connected_clients::ConnectedClients,
-    event::server_event::BufferedServerEvents,
+    event::{
+        client_event::{ClientEventRateLimits, FromClient},
+        server_event::{BufferedServerEvents, ClientGroupRegistry, SendMode, ToClients},
+    },
     replication::{
         replicated_clients::{
-            client_visibility::Visibility, ClientBuffers, ReplicatedClients, VisibilityPolicy,
+            client_visibility::Visibility, ClientBuffers, ReplicatedClient, ReplicatedClients,
+            VisibilityPolicy,
         },
+        replicated_resources::ResourceBuffer,
         replication_registry::{
-            component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns,
-            ReplicationRegistry,
+            change_filter::LastSentValues, component_fns::ComponentFns, ctx::SerializeCtx,
+            delta_fns::DeltaCache, rule_fns::UntypedRuleFns, ReplicationRegistry,
         },
         replication_rules::ReplicationRules,
         track_mutate_messages::TrackMutateMessages,
+        ClientSnapshotRequest, Replicated, ReplicationPaused, ReplicationPriority,
+        ReplicationSleeping, ReplicationTags,
     },
-    replicon_server::RepliconServer,
+    replicon_server::{KickReason, RepliconServer},
     replicon_tick::RepliconTick,
     ClientId,
 };
 use client_entity_map::ClientEntityMap;
 use despawn_buffer::{DespawnBuffer, DespawnBufferPlugin};
+use hot_join_snapshot::HotJoinSnapshot;
+use network_stats_history::NetworkStatsHistory;
+use ordered_entities::OrderedEntities;
 use removal_buffer::{RemovalBuffer, RemovalBufferPlugin};
-use replicated_archetypes::{ReplicatedArchetypes, ReplicatedComponent};
+use replicated_archetypes::{ReplicatedArchetype, ReplicatedArchetypes, ReplicatedComponent};
 use replication_messages::{serialized_data::SerializedData, ReplicationMessages};
 use server_tick::ServerTick;
 
@@ -67,6 +85,99 @@ pub struct ServerPlugin {
     /// All events from server will be buffered on client until replication starts, except the ones marked as independent.
     /// See also [`ServerEventAppExt::make_independent`](crate::core::event::server_event::ServerEventAppExt::make_independent).
     pub replicate_after_connect: bool,
+
+    /// Maximum number of bytes with mutations that will be sent to each client per tick.
+    ///
+    /// Once the budget is exhausted, mutations for the remaining entities are deferred. They will be
+    /// detected as changed and sent on the next ticks since the client didn't acknowledge them.
+    /// Entities with higher [`ReplicationPriority`] are sent first.
+    /// At least one entity is always sent to avoid starving entities with large mutations.
+    ///
+    /// Insertions, removals and despawns are not affected by the budget.
+    ///
+    /// By default set to [`None`], which means that all mutations are sent every tick.
+    pub bandwidth_budget_bytes_per_tick: Option<usize>,
+
+    /// Maximum number of entities with changes that will be collected for each client per tick.
+    ///
+    /// Useful to avoid serialization spikes when a lot of entities are spawned at once.
+    /// Once the limit is reached, the remaining entities are deferred into the client's
+    /// [`ReplicationQueue`](crate::core::replication::replicated_clients::replication_queue::ReplicationQueue)
+    /// and collected first on the next tick. Entities with higher [`ReplicationPriority`] are collected first.
+    ///
+    /// Despawns and removals are not affected by the limit.
+    ///
+    /// By default set to [`None`], which means that all entities are collected every tick.
+    pub entity_replication_limit: Option<usize>,
+
+    /// If enabled, clients can request the entire state of visible entities via [`ClientSnapshotRequest`].
+    ///
+    /// Disabled by default since re-sending everything is expensive and could be abused.
+    pub allow_snapshot_requests: bool,
+
+    /// Minimum time between two accepted snapshot requests from the same client.
+    ///
+    /// Requests that arrive earlier are ignored.
+    /// Has no effect if [`Self::allow_snapshot_requests`] is disabled.
+    pub snapshot_request_cooldown: Duration,
+
+    /// If set, the state of all replicated entities will be pre-serialized into [`HotJoinSnapshot`]
+    /// and reused for the specified number of ticks.
+    ///
+    /// Clients that start replication will receive the snapshot instead of having their state collected
+    /// separately, which speeds up connections of multiple clients to worlds with a lot of replicated entities.
+    /// The snapshot is rebuilt only when a client starts replication and costs a full serialization of the world.
+    ///
+    /// By default set to [`None`], which disables the snapshot.
+    pub hot_join_cache: Option<u32>,
+
+    /// Maximum number of events per second that each client can send for events without a specific limit.
+    ///
+    /// See [`ClientEventAppExt::add_client_event_with_rate_limit`](crate::core::event::client_event::ClientEventAppExt::add_client_event_with_rate_limit)
+    /// for per-event limits.
+    ///
+    /// By default set to [`None`], which means that events are not limited.
+    pub default_event_rate_limit: Option<f32>,
+
+    /// Number of ticks to accumulate component removals before sending them.
+    ///
+    /// Useful to reduce the number of messages for bursty removal patterns, such as swapping
+    /// equipment or status effects. Removals are sent together once the oldest of them was buffered
+    /// for the specified number of ticks. Removals of components that are re-added within the window
+    /// are not sent at all.
+    ///
+    /// By default set to 0, which means that removals are sent on the tick they happen.
+    pub removal_coalescing_window: u32,
+
+    /// Number of unreliable channels to split mutations across.
+    ///
+    /// A single channel may saturate with a large number of entities, causing mutations
+    /// for all of them to be dropped. With multiple channels each entity is assigned
+    /// to a channel by its index. Acknowledgments are still sent over
+    /// [`ReplicationChannel::Updates`] and cover all channels.
+    ///
+    /// The channels are created in [`RepliconChannels`] during the plugin build.
+    /// See [`RepliconChannels::set_mutation_channels`] for client-only apps.
+    /// The client discards mutate messages received over the wrong channel if its number
+    /// of mutation channels doesn't match the server.
+    ///
+    /// By default set to 1, which means that only [`ReplicationChannel::Mutations`] is used.
+    pub mutation_channels: usize,
+
+    /// If enabled, [`ServerReplicationStats`] and [`NetworkStatsHistory`] will be added
+    /// and updated on each sent replication.
+    ///
+    /// Not needed with [`ServerDiagnosticsPlugin`](diagnostics::ServerDiagnosticsPlugin)
+    /// if only [`ServerReplicationStats`] is needed, since the plugin adds it automatically.
+    pub track_stats: bool,
+
+    /// Number of replication ticks stored in [`NetworkStatsHistory`] for each client.
+    ///
+    /// Capped to [`NetworkStatsHistory::MAX_WINDOW`].
+    /// Has no effect if [`Self::track_stats`] is disabled.
+    ///
+    /// By default set to 60.
+    pub stats_history_window: usize,
 }
 
 impl Default for ServerPlugin {
@@ -76,6 +187,16 @@ impl Default for ServerPlugin {
             visibility_policy: Default::default(),
             mutations_timeout: Duration::from_secs(10),
             replicate_after_connect: true,
+            bandwidth_budget_bytes_per_tick: None,
+            entity_replication_limit: None,
+            allow_snapshot_requests: false,
+            snapshot_request_cooldown: Duration::from_secs(5),
+            hot_join_cache: None,
+            default_event_rate_limit: None,
+            removal_coalescing_window: 0,
+            mutation_channels: 1,
+            track_stats: false,
+            stats_history_window: 60,
         }
     }
 }
@@ -96,6 +217,16 @@ impl Plugin for ServerPlugin {
                 self.replicate_after_connect,
             ))
             .init_resource::<BufferedServerEvents>()
+            .init_resource::<ClientGroupRegistry>()
+            .init_resource::<DeltaCache>()
+            .init_resource::<LastSentValues>()
+            .init_resource::<PendingUpdateMetadata>()
+            .init_resource::<ResourceBuffer>()
+            .insert_resource(RemovalBuffer::new(self.removal_coalescing_window))
+            .insert_resource(SendSettings {
+                bandwidth_budget: self.bandwidth_budget_bytes_per_tick,
+                entity_limit: self.entity_replication_limit,
+            })
             .configure_sets(
                 PreUpdate,
                 (ServerSet::ReceivePackets, ServerSet::Receive).chain(),
@@ -104,13 +235,16 @@ impl Plugin for ServerPlugin {
                 PostUpdate,
                 (
                     ServerSet::StoreHierarchy,
+                    ServerSet::BeforeSend,
                     ServerSet::Send,
-                    ServerSet::SendPackets,
+                    ServerSet::AfterSend,
+                    (ServerSet::Diagnostics, ServerSet::SendPackets),
                 )
                     .chain(),
             )
             .add_observer(Self::handle_connections)
             .add_observer(Self::enable_replication)
+            .add_observer(Self::pause_replication)
             .add_systems(Startup, Self::setup_channels)
             .add_systems(
                 PreUpdate,
@@ -135,6 +269,66 @@ impl Plugin for ServerPlugin {
                 ),
             );
 
+        if self.track_stats {
+            app.init_resource::<ServerReplicationStats>()
+                .insert_resource(NetworkStatsHistory::new(self.stats_history_window))
+                .add_systems(
+                    PostUpdate,
+                    Self::record_stats_history
+                        .in_set(ServerSet::AfterSend)
+                        .run_if(server_running)
+                        .run_if(resource_changed::<ServerTick>),
+                );
+        }
+
+        app.world_mut()
+            .get_resource_or_init::<RepliconChannels>()
+            .set_mutation_channels(self.mutation_channels);
+
+        app.world_mut()
+            .get_resource_or_init::<ClientEventRateLimits>()
+            .set_default_limit(self.default_event_rate_limit);
+
+        if let Some(interval) = self.hot_join_cache {
+            assert_ne!(interval, 0, "hot join cache interval should be positive");
+            app.insert_resource(HotJoinSnapshot::new(interval))
+                .add_observer(Self::invalidate_hot_join_snapshot);
+        }
+
+        app.add_systems(
+            PostUpdate,
+            Self::send_kicks
+                .in_set(ServerSet::BeforeSend)
+                .run_if(server_running),
+        );
+
+        if self.allow_snapshot_requests {
+            app.add_systems(
+                PostUpdate,
+                Self::handle_snapshot_requests(self.snapshot_request_cooldown)
+                    .in_set(ServerSet::Send)
+                    .before(Self::send_replication)
+                    .run_if(server_running),
+            );
+        }
+
+        match self.visibility_policy {
+            VisibilityPolicy::Hierarchical => {
+                app.add_systems(
+                    PostUpdate,
+                    Self::propagate_hierarchical_visibility
+                        .in_set(ServerSet::Send)
+                        .before(Self::send_replication)
+                        .run_if(server_running)
+                        .run_if(resource_changed::<ServerTick>),
+                );
+            }
+            VisibilityPolicy::All
+            | VisibilityPolicy::Blacklist
+            | VisibilityPolicy::Whitelist
+            | VisibilityPolicy::TagBased => (),
+        }
+
         match self.tick_policy {
             TickPolicy::MaxTickRate(max_tick_rate) => {
                 let tick_time = Duration::from_millis(1000 / max_tick_rate as u64);
@@ -157,9 +351,29 @@ impl Plugin for ServerPlugin {
             TickPolicy::Manual => (),
         }
     }
+
+    fn finish(&self, app: &mut App) {
+        let channels_count = app
+            .world()
+            .get_resource::<RepliconChannels>()
+            .map(|channels| channels.client_channels().len())
+            .unwrap_or_default();
+        if channels_count == 0 {
+            warn!(
+                "`ServerPlugin` has no client channels to receive from, \
+                make sure that `RepliconCorePlugin` is added before it"
+            );
+        }
+    }
 }
 
 impl ServerPlugin {
+    /// Sets [`Self::mutation_channels`].
+    pub fn per_mutation_channel(mut self, count: usize) -> Self {
+        self.mutation_channels = count;
+        self
+    }
+
     fn setup_channels(mut server: ResMut<RepliconServer>, channels: Res<RepliconChannels>) {
         server.setup_client_channels(channels.client_channels().len());
     }
@@ -179,12 +393,18 @@ impl ServerPlugin {
         mut server: ResMut<RepliconServer>,
         mut client_buffers: ResMut<ClientBuffers>,
         mut buffered_events: ResMut<BufferedServerEvents>,
+        mut client_groups: ResMut<ClientGroupRegistry>,
+        mut delta_cache: ResMut<DeltaCache>,
+        mut rate_limits: ResMut<ClientEventRateLimits>,
     ) {
         match *trigger.event() {
             ServerEvent::ClientDisconnected { client_id, .. } => {
-                entity_map.0.remove(&client_id);
+                entity_map.remove_client(client_id);
                 connected_clients.remove(client_id);
                 replicated_clients.remove(&mut client_buffers, client_id);
+                client_groups.remove_client(client_id);
+                delta_cache.remove_client(client_id);
+                rate_limits.remove_client(client_id);
                 server.remove_client(client_id);
             }
             ServerEvent::ClientConnected { client_id } => {
@@ -201,8 +421,75 @@ impl ServerPlugin {
         trigger: Trigger<StartReplication>,
         mut replicated_clients: ResMut<ReplicatedClients>,
         mut client_buffers: ResMut<ClientBuffers>,
+        hot_join_snapshot: Option<ResMut<HotJoinSnapshot>>,
     ) {
         replicated_clients.add(&mut client_buffers, **trigger.event());
+        if let Some(mut hot_join_snapshot) = hot_join_snapshot {
+            hot_join_snapshot.add_pending_client(**trigger.event());
+        }
+    }
+
+    /// Invalidates [`HotJoinSnapshot`] since paused and sleeping entities are not included into it.
+    fn invalidate_hot_join_snapshot(
+        _trigger: Trigger<OnAdd, (ReplicationPaused, ReplicationSleeping)>,
+        mut hot_join_snapshot: ResMut<HotJoinSnapshot>,
+    ) {
+        hot_join_snapshot.invalidate();
+    }
+
+    /// Forgets about the state acknowledged by clients, so that the full state
+    /// will be re-sent once [`ReplicationPaused`] is removed.
+    fn pause_replication(
+        trigger: Trigger<OnAdd, ReplicationPaused>,
+        mut replicated_clients: ResMut<ReplicatedClients>,
+    ) {
+        for client in replicated_clients.iter_mut() {
+            client.clear_mutation_tick(trigger.entity());
+        }
+    }
+
+    /// Sends [`KickReason`] to kicked clients and requests their disconnection from the backend.
+    fn send_kicks(
+        mut server: ResMut<RepliconServer>,
+        mut kick_events: EventWriter<ToClients<KickReason>>,
+    ) {
+        for (client_id, reason) in server.drain_kicks() {
+            kick_events.send(ToClients {
+                mode: SendMode::Direct(client_id),
+                event: KickReason(reason),
+            });
+        }
+    }
+
+    fn handle_snapshot_requests(
+        cooldown: Duration,
+    ) -> impl FnMut(
+        Local<HashMap<ClientId, Duration>>,
+        EventReader<FromClient<ClientSnapshotRequest>>,
+        ResMut<ReplicatedClients>,
+        Res<Time>,
+    ) {
+        move |mut last_requests, mut snapshot_requests, mut replicated_clients, time| {
+            last_requests
+                .retain(|&client_id, _| replicated_clients.get_client(client_id).is_some());
+
+            for request in snapshot_requests.read() {
+                let Some(client) = replicated_clients.get_client_mut(request.client_id) else {
+                    continue;
+                };
+
+                if let Some(&last_request) = last_requests.get(&request.client_id) {
+                    if time.elapsed().saturating_sub(last_request) < cooldown {
+                        debug!("ignoring snapshot request from {:?}", request.client_id);
+                        continue;
+                    }
+                }
+
+                debug!("accepting snapshot request from {:?}", request.client_id);
+                client.clear_mutation_ticks();
+                last_requests.insert(request.client_id, time.elapsed());
+            }
+        }
     }
 
     fn cleanup_acks(
@@ -243,11 +530,53 @@ impl ServerPlugin {
         }
     }
 
+    fn record_stats_history(
+        mut history: ResMut<NetworkStatsHistory>,
+        connected_clients: Res<ConnectedClients>,
+        replication_stats: Res<ServerReplicationStats>,
+    ) {
+        history.record(&connected_clients, &replication_stats);
+    }
+
+    /// Mirrors visibility of replicated parents to their descendants for [`VisibilityPolicy::Hierarchical`].
+    ///
+    /// Propagates only from entities whose visibility changed during this tick
+    /// and from entities with changed [`Parent`].
+    fn propagate_hierarchical_visibility(
+        mut changed_entities: Local<Vec<Entity>>,
+        mut replicated_clients: ResMut<ReplicatedClients>,
+        changed_parents: Query<(Entity, &Parent), (Changed<Parent>, With<Replicated>)>,
+        children: Query<&Children>,
+        replicated: Query<(), With<Replicated>>,
+    ) {
+        for client in replicated_clients.iter_mut() {
+            let visibility = client.visibility_mut();
+            changed_entities.extend(visibility.iter_changed());
+
+            for (entity, parent) in &changed_parents {
+                let visible = visibility.is_visible(**parent);
+                visibility.set_visibility(entity, visible);
+                changed_entities.push(entity);
+            }
+
+            for entity in changed_entities.drain(..) {
+                let visible = visibility.is_visible(entity);
+                for child in children
+                    .iter_descendants(entity)
+                    .filter(|&child| replicated.contains(child))
+                {
+                    visibility.set_visibility(child, visible);
+                }
+            }
+        }
+    }
+
     /// Collects [`ReplicationMessages`] and sends them.
-    pub(super) fn send_replication(
+    pub(crate) fn send_replication(
         mut serialized: Local<SerializedData>,
         mut messages: Local<ReplicationMessages>,
         mut replicated_archetypes: Local<ReplicatedArchetypes>,
+        mut ordered_entities: Local<OrderedEntities>,
         change_tick: SystemChangeTick,
         mut set: ParamSet<(
             &World,
@@ -257,8 +586,18 @@ impl ServerPlugin {
             ResMut<ClientEntityMap>,
             ResMut<DespawnBuffer>,
             ResMut<RepliconServer>,
+            (
+                ResMut<DeltaCache>,
+                ResMut<LastSentValues>,
+                Option<ResMut<ServerReplicationStats>>,
+                Option<ResMut<HotJoinSnapshot>>,
+                ResMut<PendingUpdateMetadata>,
+                ResMut<ResourceBuffer>,
+            ),
         )>,
         track_mutate_messages: Res<TrackMutateMessages>,
+        settings: Res<SendSettings>,
+        channels: Res<RepliconChannels>,
         registry: Res<ReplicationRegistry>,
         rules: Res<ReplicationRules>,
         server_tick: Res<ServerTick>,
@@ -270,8 +609,32 @@ impl ServerPlugin {
         let mut replicated_clients = mem::take(&mut *set.p1());
         let mut removal_buffer = mem::take(&mut *set.p2());
         let mut client_buffers = mem::take(&mut *set.p3());
+        let (
+            mut delta_cache,
+            mut last_sent,
+            stats,
+            hot_join_snapshot,
+            mut metadata,
+            mut resource_buffer,
+        ) = set.p7();
+        let metadata_range = serialized.write_metadata(metadata.iter())?;
+        metadata.clear();
+        let mut resource_buffer = mem::take(&mut *resource_buffer);
+        let mut delta_cache = mem::take(&mut *delta_cache);
+        let mut last_sent = mem::take(&mut *last_sent);
+        let mut stats = stats.map(|mut stats| mem::take(&mut *stats));
+        let mut hot_join_snapshot = hot_join_snapshot.map(|mut hot_join_snapshot| {
+            mem::replace(&mut *hot_join_snapshot, HotJoinSnapshot::new(0))
+        });
 
         messages.reset(replicated_clients.len());
+        removal_buffer.flush_pending(set.p0(), **server_tick);
+
+        if let Some(hot_join_snapshot) = &mut hot_join_snapshot {
+            if rules.is_changed() || !set.p5().is_empty() || !removal_buffer.is_empty() {
+                hot_join_snapshot.invalidate();
+            }
+        }
 
         collect_mappings(
             &mut messages,
@@ -291,36 +654,92 @@ impl ServerPlugin {
             &replicated_clients,
             &removal_buffer,
         )?;
-        collect_changes(
-            &mut messages,
-            &mut serialized,
-            &mut replicated_clients,
-            &replicated_archetypes,
-            &registry,
-            &removal_buffer,
-            set.p0(),
-            &change_tick,
-            **server_tick,
-        )?;
+        if let Some(hot_join_snapshot) = &mut hot_join_snapshot {
+            if hot_join_snapshot.should_rebuild(**server_tick) {
+                hot_join_snapshot.rebuild(
+                    &replicated_archetypes,
+                    &registry,
+                    set.p0(),
+                    change_tick.this_run(),
+                    **server_tick,
+                )?;
+            }
+            hot_join_snapshot.collect(
+                &mut messages,
+                &mut serialized,
+                &mut replicated_clients,
+                set.p0(),
+            );
+        }
+        let ctx = CollectCtx {
+            world: set.p0(),
+            replicated_archetypes: &replicated_archetypes,
+            registry: &registry,
+            removal_buffer: &removal_buffer,
+            change_tick: &change_tick,
+            server_tick: **server_tick,
+            read_priority: settings.bandwidth_budget.is_some(),
+            entity_limit: settings.entity_limit,
+        };
+        let mut params = CollectParams {
+            messages: &mut messages,
+            serialized: &mut serialized,
+            replicated_clients: &mut replicated_clients,
+            delta_cache: &mut delta_cache,
+            last_sent: &mut last_sent,
+            inserted: false,
+        };
+        collect_changes(ctx, &mut params, &mut ordered_entities)?;
+        let inserted = params.inserted;
         removal_buffer.clear();
+        delta_cache.retain_existing(set.p0());
+        last_sent.retain_existing(set.p0());
+        if inserted {
+            if let Some(hot_join_snapshot) = &mut hot_join_snapshot {
+                // Clients that join later would receive inserted components as mutations.
+                hot_join_snapshot.invalidate();
+            }
+        }
 
         send_messages(
             &mut messages,
             &mut replicated_clients,
             &mut set.p6(),
             **server_tick,
+            metadata_range,
             **track_mutate_messages,
+            settings.bandwidth_budget,
+            channels.mutation_channel_ids(),
             &mut serialized,
             &mut client_buffers,
             change_tick,
             &time,
+            stats.as_mut(),
         )?;
+        resource_buffer.send(&mut set.p6(), &mut replicated_clients)?;
         serialized.clear();
 
         // Return borrowed data back.
         *set.p1() = replicated_clients;
         *set.p2() = removal_buffer;
         *set.p3() = client_buffers;
+        let (
+            mut delta_cache_res,
+            mut last_sent_res,
+            stats_res,
+            hot_join_snapshot_res,
+            _,
+            mut resource_buffer_res,
+        ) = set.p7();
+        *delta_cache_res = delta_cache;
+        *resource_buffer_res = resource_buffer;
+        *last_sent_res = last_sent;
+        if let Some(stats) = stats {
+            *stats_res.unwrap() = stats;
+        }
+        if let Some(hot_join_snapshot) = hot_join_snapshot {
+            *hot_join_snapshot_res.unwrap() = hot_join_snapshot;
+        }
 
         Ok(())
     }
@@ -331,11 +750,37 @@ impl ServerPlugin {
         mut replicated_clients: ResMut<ReplicatedClients>,
         mut client_buffers: ResMut<ClientBuffers>,
         mut buffered_events: ResMut<BufferedServerEvents>,
+        mut despawn_buffer: ResMut<DespawnBuffer>,
+        mut removal_buffer: ResMut<RemovalBuffer>,
+        mut delta_cache: ResMut<DeltaCache>,
+        mut last_sent: ResMut<LastSentValues>,
+        mut rate_limits: ResMut<ClientEventRateLimits>,
+        mut metadata: ResMut<PendingUpdateMetadata>,
+        mut resource_buffer: ResMut<ResourceBuffer>,
+        stats: Option<ResMut<ServerReplicationStats>>,
+        stats_history: Option<ResMut<NetworkStatsHistory>>,
+        hot_join_snapshot: Option<ResMut<HotJoinSnapshot>>,
     ) {
         *server_tick = Default::default();
-        entity_map.0.clear();
+        entity_map.clear();
         replicated_clients.clear(&mut client_buffers);
         buffered_events.clear();
+        despawn_buffer.reset();
+        removal_buffer.reset();
+        delta_cache.clear();
+        last_sent.clear();
+        rate_limits.clear();
+        metadata.clear();
+        resource_buffer.clear();
+        if let Some(mut stats) = stats {
+            *stats = Default::default();
+        }
+        if let Some(mut stats_history) = stats_history {
+            stats_history.clear();
+        }
+        if let Some(mut hot_join_snapshot) = hot_join_snapshot {
+            hot_join_snapshot.clear();
+        }
     }
 }
 
@@ -344,22 +789,39 @@ fn send_messages(
     replicated_clients: &mut ReplicatedClients,
     server: &mut RepliconServer,
     server_tick: RepliconTick,
+    metadata: Range<usize>,
     track_mutate_messages: bool,
+    bandwidth_budget: Option<usize>,
+    mutation_channels: &[u8],
     serialized: &mut SerializedData,
     client_buffers: &mut ClientBuffers,
     change_tick: SystemChangeTick,
     time: &Time,
+    mut stats: Option<&mut ServerReplicationStats>,
 ) -> Result<(), Box<bincode::ErrorKind>> {
+    if let Some(stats) = stats.as_deref_mut() {
+        stats.client_bytes.clear();
+    }
+
     let mut server_tick_range = None;
     for ((update_message, mutate_message), client) in
         messages.iter_mut().zip(replicated_clients.iter_mut())
     {
+        update_message.set_metadata(metadata.clone());
         if !update_message.is_empty() {
             client.set_update_tick(server_tick);
             let server_tick = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
 
             trace!("sending update message to {:?}", client.id());
-            update_message.send(server, client, serialized, server_tick)?;
+            let bytes = update_message.send(server, client, serialized, server_tick)?;
+            if let Some(stats) = stats.as_deref_mut() {
+                if bytes != 0 {
+                    stats.update_messages += 1;
+                    stats.update_bytes += bytes;
+                    stats.entities += update_message.entities_count();
+                    *stats.client_bytes.entry(client.id()).or_default() += bytes;
+                }
+            }
         } else {
             trace!("no updates to send for {:?}", client.id());
         }
@@ -367,22 +829,34 @@ fn send_messages(
         if !mutate_message.is_empty() || track_mutate_messages {
             let server_tick = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
 
-            let messages_count = mutate_message.send(
+            let mutate_stats = mutate_message.send(
                 server,
                 client,
                 client_buffers,
                 serialized,
                 track_mutate_messages,
+                bandwidth_budget,
+                mutation_channels,
                 server_tick,
                 change_tick.this_run(),
                 time.elapsed(),
             )?;
             trace!(
-                "sending {messages_count} mutate message(s) to {:?}",
+                "sending {} mutate message(s) with {} bytes to {:?}",
+                mutate_stats.messages_count,
+                mutate_stats.total_bytes,
                 client.id()
             );
+            if let Some(stats) = stats.as_deref_mut() {
+                stats.mutate_messages += mutate_stats.messages_count;
+                stats.mutate_bytes += mutate_stats.total_bytes;
+                stats.entities += mutate_stats.entities_count;
+                *stats.client_bytes.entry(client.id()).or_default() += mutate_stats.total_bytes;
+            }
+            client.set_mutate_stats(mutate_stats);
         } else {
             trace!("no mutations to send for {:?}", client.id());
+            client.set_mutate_stats(Default::default());
         }
 
         client.visibility_mut().update();
@@ -399,9 +873,8 @@ fn collect_mappings(
     entity_map: &mut ClientEntityMap,
 ) -> bincode::Result<()> {
     for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter()) {
-        if let Some(mappings) = entity_map.0.get_mut(&client.id()) {
-            let len = mappings.len();
-            let mappings = serialized.write_mappings(mappings.drain(..))?;
+        if let Some((len, mappings)) = entity_map.drain_client(client.id()) {
+            let mappings = serialized.write_mappings(mappings)?;
             message.set_mappings(mappings, len);
         }
     }
@@ -416,7 +889,7 @@ fn collect_despawns(
     replicated_clients: &mut ReplicatedClients,
     despawn_buffer: &mut DespawnBuffer,
 ) -> bincode::Result<()> {
-    for entity in despawn_buffer.drain(..) {
+    for entity in despawn_buffer.drain() {
         let entity_range = serialized.write_entity(entity)?;
         for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter_mut()) {
             if client.visibility().is_visible(entity) {
@@ -458,28 +931,36 @@ fn collect_removals(
 }
 
 /// Collects component changes from this tick into update and mutate messages since the last entity tick.
+///
+/// Sets [`CollectParams::inserted`] if a replicated component was inserted into an entity that was already replicated.
 fn collect_changes(
-    messages: &mut ReplicationMessages,
-    serialized: &mut SerializedData,
-    replicated_clients: &mut ReplicatedClients,
-    replicated_archetypes: &ReplicatedArchetypes,
-    registry: &ReplicationRegistry,
-    removal_buffer: &RemovalBuffer,
-    world: &World,
-    change_tick: &SystemChangeTick,
-    server_tick: RepliconTick,
+    ctx: CollectCtx,
+    params: &mut CollectParams,
+    ordered_entities: &mut OrderedEntities,
 ) -> bincode::Result<()> {
-    for replicated_archetype in replicated_archetypes.iter() {
-        // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
-        let archetype = unsafe {
-            world
-                .archetypes()
-                .get(replicated_archetype.id)
-                .unwrap_unchecked()
-        };
+    ordered_entities.update(ctx.replicated_archetypes, ctx.world);
+    let queued_set: EntityHashSet = params
+        .replicated_clients
+        .iter_mut()
+        .flat_map(|client| client.replication_queue_mut().drain())
+        .collect();
+
+    // Ordered entities are gathered in the same pass over archetypes to avoid looking them up again.
+    let mut ordered = EntityHashMap::default();
+    // Queued and changed entities are collected first in sorted order if the limit is set.
+    let mut limited_entities = Vec::new();
+    // Remaining entities that may still need to be sent when the limit is set,
+    // for example after gaining visibility or to resend mutations.
+    let mut remaining_entities = Vec::new();
+
+    for (replicated_archetype, archetype) in ctx
+        .replicated_archetypes
+        .iter_nonempty(ctx.world)
+        .filter(|(replicated_archetype, _)| !replicated_archetype.paused)
+    {
         // SAFETY: table obtained from this archetype.
         let table = unsafe {
-            world
+            ctx.world
                 .storages()
                 .tables
                 .get(archetype.table_id())
@@ -487,136 +968,449 @@ fn collect_changes(
         };
 
         for entity in archetype.entities() {
-            let mut entity_range = None;
-            for ((update_message, mutate_message), client) in
-                messages.iter_mut().zip(replicated_clients.iter())
-            {
-                let visibility = client.visibility().state(entity.id());
-                update_message.start_entity_changes(visibility);
-                mutate_message.start_entity_mutations();
+            let replicated_entity = ReplicatedEntity {
+                archetype: replicated_archetype,
+                table,
+                entity,
+            };
+
+            if ordered_entities.contains(entity.id()) {
+                // Will be collected below in the sorted order.
+                ordered.insert(entity.id(), replicated_entity);
+                continue;
             }
 
-            // SAFETY: all replicated archetypes have marker component with table storage.
-            let (_, marker_ticks) = unsafe {
-                get_component_unchecked(
-                    table,
-                    &world.storages().sparse_sets,
-                    entity,
-                    StorageType::Table,
-                    replicated_archetypes.marker_id(),
+            if ctx.entity_limit.is_none() {
+                collect_entity_changes(ctx, params, replicated_entity, None)?;
+                continue;
+            }
+
+            let queued = queued_set.contains(&entity.id());
+            let changed = (!queued).then(|| entity_changed(ctx, replicated_entity));
+            if queued || changed == Some(true) {
+                let priority = ctx
+                    .world
+                    .get::<ReplicationPriority>(entity.id())
+                    .copied()
+                    .unwrap_or_default();
+                limited_entities.push((queued, priority, replicated_entity, changed));
+            } else {
+                remaining_entities.push(replicated_entity);
+            }
+        }
+    }
+
+    // Entities deferred from previous ticks go first, then changed entities with higher priority.
+    limited_entities.sort_by_key(|&(queued, priority, ..)| (Reverse(queued), Reverse(priority)));
+    for (_, _, replicated_entity, changed) in limited_entities {
+        collect_entity_changes(ctx, params, replicated_entity, changed)?;
+    }
+
+    for replicated_entity in remaining_entities {
+        collect_entity_changes(ctx, params, replicated_entity, Some(false))?;
+    }
+
+    for entity in ordered_entities.iter() {
+        let replicated_entity = ordered
+            .remove(&entity)
+            .expect("ordered entities should be replicated");
+
+        collect_entity_changes(ctx, params, replicated_entity, None)?;
+    }
+
+    Ok(())
+}
+
+/// Collects changes for a single entity from a replicated archetype.
+///
+/// `changed` contains the result of [`entity_changed`] if it was already computed.
+fn collect_entity_changes(
+    ctx: CollectCtx,
+    params: &mut CollectParams,
+    replicated_entity: ReplicatedEntity,
+    mut changed: Option<bool>,
+) -> bincode::Result<()> {
+    let ReplicatedEntity {
+        archetype: replicated_archetype,
+        table,
+        entity,
+    } = replicated_entity;
+
+    // SAFETY: all replicated archetypes have marker component with table storage.
+    let (_, marker_ticks) = unsafe {
+        get_component_unchecked(
+            table,
+            &ctx.world.storages().sparse_sets,
+            entity,
+            StorageType::Table,
+            ctx.replicated_archetypes.marker_id(),
+        )
+    };
+    // If the marker was added in this tick, the entity just started replicating.
+    // It could be a newly spawned entity or an old entity with just-enabled replication,
+    // so we need to include even old components that were registered for replication.
+    let marker_added =
+        marker_ticks.is_added(ctx.change_tick.last_run(), ctx.change_tick.this_run());
+
+    let policy = params.replicated_clients.visibility_policy();
+    let tags = match policy {
+        VisibilityPolicy::TagBased => ctx.world.get::<ReplicationTags>(entity.id()),
+        _ => None,
+    };
+
+    let mut entity_range = None;
+    let mut priority = None;
+    let mut has_receivers = false;
+    for ((update_message, mutate_message), client) in params
+        .messages
+        .iter_mut()
+        .zip(params.replicated_clients.iter_mut())
+    {
+        let mut visibility = entity_visibility(policy, client, entity.id(), tags);
+        if let VisibilityPolicy::TagBased = policy {
+            if visibility == Visibility::Hidden && client.visibility().is_visible(entity.id()) {
+                // Tags no longer intersect with the client's filter.
+                let entity_range =
+                    write_entity_cached(&mut entity_range, params.serialized, entity.id())?;
+                update_message.add_despawn(entity_range);
+                client.remove_despawned(entity.id());
+            }
+        }
+        if replicated_archetype.sleeping
+            && !marker_added
+            && visibility == Visibility::Visible
+            && client.mutation_tick(entity.id()).is_some()
+        {
+            // The client already received this entity, sleeping entities are not checked for changes.
+            visibility = Visibility::Hidden;
+        }
+        if let Some(limit) = ctx.entity_limit {
+            if visibility != Visibility::Hidden && client.replication_queue().is_full(limit) {
+                // Skip serialization for clients that already reached the limit and defer the entity
+                // if it has anything to send.
+                let new_entity = marker_added
+                    || visibility == Visibility::Gained
+                    || client.mutation_tick(entity.id()).is_none();
+                if new_entity
+                    || *changed.get_or_insert_with(|| entity_changed(ctx, replicated_entity))
+                {
+                    client.replication_queue_mut().push(entity.id());
+                }
+                visibility = Visibility::Hidden;
+            }
+        }
+        has_receivers |= visibility != Visibility::Hidden;
+        update_message.start_entity_changes(visibility);
+        mutate_message.start_entity_mutations();
+    }
+
+    if replicated_archetype.sleeping && !has_receivers {
+        return Ok(());
+    }
+
+    for replicated_component in &replicated_archetype.components {
+        let (component_id, component_fns, rule_fns) = ctx.registry.get(replicated_component.fns_id);
+
+        // SAFETY: component and storage were obtained from this archetype.
+        let (component, ticks) = unsafe {
+            get_component_unchecked(
+                table,
+                &ctx.world.storages().sparse_sets,
+                entity,
+                replicated_component.storage_type,
+                component_id,
+            )
+        };
+
+        let serialize_ctx = SerializeCtx {
+            server_tick: ctx.server_tick,
+            component_id,
+        };
+        let changed_tick = match component_fns.change_filter() {
+            // SAFETY: `component` and `component_fns` were created for the same type.
+            Some(filter) => unsafe {
+                params.last_sent.changed_tick(
+                    filter,
+                    entity.id(),
+                    component_id,
+                    component,
+                    &ticks,
+                    ctx.change_tick.last_run(),
+                    ctx.change_tick.this_run(),
                 )
+            },
+            None => ticks.changed,
+        };
+        if !marker_added && ticks.is_added(ctx.change_tick.last_run(), ctx.change_tick.this_run()) {
+            params.inserted = true;
+        }
+        let mut component_ranges = SmallVec::new();
+        let mut should_replicate = None;
+        for ((update_message, mutate_message), client) in params
+            .messages
+            .iter_mut()
+            .zip(params.replicated_clients.iter())
+        {
+            if update_message.entity_visibility() == Visibility::Hidden {
+                continue;
+            }
+
+            let lod = if component_fns.has_lods() {
+                component_fns.select_lod(camera_distance_squared(ctx.world, client, entity.id()))
+            } else {
+                0
             };
-            // If the marker was added in this tick, the entity just started replicating.
-            // It could be a newly spawned entity or an old entity with just-enabled replication,
-            // so we need to include even old components that were registered for replication.
-            let marker_added =
-                marker_ticks.is_added(change_tick.last_run(), change_tick.this_run());
-
-            for replicated_component in &replicated_archetype.components {
-                let (component_id, component_fns, rule_fns) =
-                    registry.get(replicated_component.fns_id);
-
-                // SAFETY: component and storage were obtained from this archetype.
-                let (component, ticks) = unsafe {
-                    get_component_unchecked(
-                        table,
-                        &world.storages().sparse_sets,
-                        entity,
-                        replicated_component.storage_type,
-                        component_id,
-                    )
-                };
 
-                let ctx = SerializeCtx {
-                    server_tick,
-                    component_id,
-                };
-                let mut component_range = None;
-                for ((update_message, mutate_message), client) in
-                    messages.iter_mut().zip(replicated_clients.iter())
+            if let Some(tick) = client
+                .mutation_tick(entity.id())
+                .filter(|_| !marker_added)
+                .filter(|_| update_message.entity_visibility() != Visibility::Gained)
+                .filter(|_| !ticks.is_added(ctx.change_tick.last_run(), ctx.change_tick.this_run()))
+            {
+                if changed_tick.is_newer_than(tick, ctx.change_tick.this_run())
+                    && *should_replicate.get_or_insert_with(|| {
+                        // SAFETY: `component` and `rule_fns` were created for the same type.
+                        unsafe { component_fns.should_replicate(rule_fns, component) }
+                    })
                 {
-                    if update_message.entity_visibility() == Visibility::Hidden {
-                        continue;
-                    }
-
-                    if let Some(tick) = client
-                        .mutation_tick(entity.id())
-                        .filter(|_| !marker_added)
-                        .filter(|_| update_message.entity_visibility() != Visibility::Gained)
-                        .filter(|_| !ticks.is_added(change_tick.last_run(), change_tick.this_run()))
-                    {
-                        if ticks.is_changed(tick, change_tick.this_run()) {
-                            if !mutate_message.mutations_written() {
-                                let entity_range = write_entity_cached(
-                                    &mut entity_range,
-                                    serialized,
-                                    entity.id(),
-                                )?;
-                                mutate_message.add_mutated_entity(entity.id(), entity_range);
+                    if !mutate_message.mutations_written() {
+                        let entity_range =
+                            write_entity_cached(&mut entity_range, params.serialized, entity.id())?;
+                        let priority = *priority.get_or_insert_with(|| {
+                            if ctx.read_priority {
+                                ctx.world
+                                    .get::<ReplicationPriority>(entity.id())
+                                    .copied()
+                                    .unwrap_or_default()
+                            } else {
+                                Default::default()
                             }
-                            let component_range = write_component_cached(
-                                &mut component_range,
-                                serialized,
-                                rule_fns,
-                                component_fns,
-                                &ctx,
-                                replicated_component,
-                                component,
-                            )?;
-                            mutate_message.add_mutated_component(component_range);
-                        }
+                        });
+                        mutate_message.add_mutated_entity(entity.id(), entity_range, priority);
+                    }
+                    let component_range = if component_fns.is_delta() {
+                        write_delta_component(
+                            params.serialized,
+                            params.delta_cache,
+                            client.id(),
+                            entity.id(),
+                            tick,
+                            ctx.change_tick.this_run(),
+                            rule_fns,
+                            component_fns,
+                            &serialize_ctx,
+                            replicated_component,
+                            component,
+                        )?
                     } else {
-                        if !update_message.entity_written() {
-                            let entity_range =
-                                write_entity_cached(&mut entity_range, serialized, entity.id())?;
-                            update_message.add_changed_entity(entity_range);
-                        }
-                        let component_range = write_component_cached(
-                            &mut component_range,
-                            serialized,
+                        write_component_cached(
+                            &mut component_ranges,
+                            params.serialized,
                             rule_fns,
                             component_fns,
-                            &ctx,
+                            &serialize_ctx,
                             replicated_component,
                             component,
-                        )?;
-                        update_message.add_inserted_component(component_range);
-                    }
+                            lod,
+                        )?
+                    };
+                    mutate_message.add_mutated_component(component_range);
                 }
-            }
-
-            for ((update_message, mutate_message), client) in
-                messages.iter_mut().zip(replicated_clients.iter_mut())
-            {
-                let visibility = update_message.entity_visibility();
-                if visibility == Visibility::Hidden {
-                    continue;
-                }
-
-                let new_entity = marker_added || visibility == Visibility::Gained;
-                if new_entity
-                    || update_message.entity_written()
-                    || removal_buffer.contains_key(&entity.id())
-                {
-                    // If there is any insertion, removal, or it's a new entity for a client, include all mutations
-                    // into update message and bump the last acknowledged tick to keep entity updates atomic.
-                    update_message.take_mutations(mutate_message);
-                    client.set_mutation_tick(entity.id(), change_tick.this_run());
-                }
-
-                if new_entity && !update_message.entity_written() {
-                    // Force-write new entity even if it doesn't have any components.
+            } else {
+                if !update_message.entity_written() {
                     let entity_range =
-                        write_entity_cached(&mut entity_range, serialized, entity.id())?;
+                        write_entity_cached(&mut entity_range, params.serialized, entity.id())?;
                     update_message.add_changed_entity(entity_range);
                 }
+                let component_range = write_component_cached(
+                    &mut component_ranges,
+                    params.serialized,
+                    rule_fns,
+                    component_fns,
+                    &serialize_ctx,
+                    replicated_component,
+                    component,
+                    lod,
+                )?;
+                update_message.add_inserted_component(component_range);
+
+                if component_fns.is_delta() {
+                    // SAFETY: `component` and `component_fns` were created for the same type.
+                    let value = unsafe { component_fns.clone_delta(component) };
+                    params
+                        .delta_cache
+                        .bases_mut(client.id(), entity.id(), component_id)
+                        .push(ctx.change_tick.this_run(), ctx.server_tick, value);
+                }
+            }
+        }
+    }
+
+    for ((update_message, mutate_message), client) in params
+        .messages
+        .iter_mut()
+        .zip(params.replicated_clients.iter_mut())
+    {
+        let visibility = update_message.entity_visibility();
+        if visibility == Visibility::Hidden {
+            continue;
+        }
+
+        let mut new_entity = marker_added || visibility == Visibility::Gained;
+        if ctx.entity_limit.is_some() {
+            // Entities without a mutation tick were never received by the client,
+            // which happens for entities deferred by the limit.
+            new_entity |= client.mutation_tick(entity.id()).is_none();
+            if new_entity || update_message.entity_written() || mutate_message.mutations_written() {
+                client.replication_queue_mut().add_collected();
             }
         }
+
+        if new_entity
+            || update_message.entity_written()
+            || ctx.removal_buffer.contains_key(&entity.id())
+        {
+            // If there is any insertion, removal, or it's a new entity for a client, include all mutations
+            // into update message and bump the last acknowledged tick to keep entity updates atomic.
+            update_message.take_mutations(mutate_message);
+            client.set_mutation_tick(entity.id(), ctx.change_tick.this_run());
+        }
+
+        if new_entity && !update_message.entity_written() {
+            // Force-write new entity even if it doesn't have any components.
+            let entity_range =
+                write_entity_cached(&mut entity_range, params.serialized, entity.id())?;
+            update_message.add_changed_entity(entity_range);
+        }
     }
 
     Ok(())
 }
 
+/// Returns visibility of the entity for the client.
+///
+/// For [`VisibilityPolicy::TagBased`] it's computed from the intersection of the entity's `tags`
+/// with the client's filter. The entity is considered gained if the client hasn't received it yet.
+fn entity_visibility(
+    policy: VisibilityPolicy,
+    client: &ReplicatedClient,
+    entity: Entity,
+    tags: Option<&ReplicationTags>,
+) -> Visibility {
+    let VisibilityPolicy::TagBased = policy else {
+        return client.visibility().state(entity);
+    };
+
+    if !tags.is_some_and(|tags| client.tag_filter().intersects(tags)) {
+        Visibility::Hidden
+    } else if client.visibility().is_visible(entity) {
+        Visibility::Visible
+    } else {
+        Visibility::Gained
+    }
+}
+
+/// Returns `true` if the entity started replicating, has removals or any of its replicated components
+/// changed since the last run.
+///
+/// Used to sort only changed entities when [`ServerPlugin::entity_replication_limit`] is set.
+fn entity_changed(ctx: CollectCtx, replicated_entity: ReplicatedEntity) -> bool {
+    let ReplicatedEntity {
+        archetype: replicated_archetype,
+        table,
+        entity,
+    } = replicated_entity;
+
+    if ctx.removal_buffer.contains_key(&entity.id()) {
+        return true;
+    }
+
+    // SAFETY: all replicated archetypes have marker component with table storage.
+    let (_, marker_ticks) = unsafe {
+        get_component_unchecked(
+            table,
+            &ctx.world.storages().sparse_sets,
+            entity,
+            StorageType::Table,
+            ctx.replicated_archetypes.marker_id(),
+        )
+    };
+    if marker_ticks.is_added(ctx.change_tick.last_run(), ctx.change_tick.this_run()) {
+        return true;
+    }
+
+    replicated_archetype
+        .components
+        .iter()
+        .any(|replicated_component| {
+            // SAFETY: component and storage were obtained from this archetype.
+            let (_, ticks) = unsafe {
+                get_component_unchecked(
+                    table,
+                    &ctx.world.storages().sparse_sets,
+                    entity,
+                    replicated_component.storage_type,
+                    replicated_component.component_id,
+                )
+            };
+            ticks.is_changed(ctx.change_tick.last_run(), ctx.change_tick.this_run())
+        })
+}
+
+/// Returns replicated archetype, table and archetype entity for a non-paused replicated entity.
+fn find_replicated_entity<'a>(
+    replicated_archetypes: &'a ReplicatedArchetypes,
+    world: &'a World,
+    entity: Entity,
+) -> Option<(&'a ReplicatedArchetype, &'a Table, &'a ArchetypeEntity)> {
+    let location = world.entities().get(entity)?;
+    let replicated_archetype = replicated_archetypes
+        .get(location.archetype_id)
+        .filter(|replicated_archetype| !replicated_archetype.paused)?;
+    let archetype = world.archetypes().get(location.archetype_id)?;
+    let table = world.storages().tables.get(location.table_id)?;
+    let entity = archetype.entities().get(location.archetype_row.index())?;
+
+    Some((replicated_archetype, table, entity))
+}
+
+/// Read-only state shared by all functions that collect entity changes.
+#[derive(Clone, Copy)]
+struct CollectCtx<'a> {
+    world: &'a World,
+    replicated_archetypes: &'a ReplicatedArchetypes,
+    registry: &'a ReplicationRegistry,
+    removal_buffer: &'a RemovalBuffer,
+    change_tick: &'a SystemChangeTick,
+    server_tick: RepliconTick,
+
+    /// Read [`ReplicationPriority`] for mutated entities.
+    read_priority: bool,
+    entity_limit: Option<usize>,
+}
+
+/// Borrowed data that is written while collecting entity changes.
+///
+/// To avoid passing a lot of arguments into all collect functions.
+struct CollectParams<'a> {
+    messages: &'a mut ReplicationMessages,
+    serialized: &'a mut SerializedData,
+    replicated_clients: &'a mut ReplicatedClients,
+    delta_cache: &'a mut DeltaCache,
+    last_sent: &'a mut LastSentValues,
+
+    /// Set if a replicated component was inserted into an entity that was already replicated.
+    inserted: bool,
+}
+
+/// Non-paused replicated entity with its archetype and table.
+#[derive(Clone, Copy)]
+struct ReplicatedEntity<'a> {
+    archetype: &'a ReplicatedArchetype,
+    table: &'a Table,
+    entity: &'a ArchetypeEntity,
+}
+
 /// Extracts component in form of [`Ptr`] and its ticks from table or sparse set based on its storage type.
 ///
 /// # Safety
@@ -667,18 +1461,22 @@ fn write_entity_cached(
     Ok(range)
 }
 
-/// Writes a component or re-uses previously written range if exists.
+/// Writes a component or re-uses previously written range for the same level of detail if exists.
 fn write_component_cached(
-    component_range: &mut Option<Range<usize>>,
+    component_ranges: &mut SmallVec<[(usize, Range<usize>); 1]>,
     serialized: &mut SerializedData,
     rule_fns: &UntypedRuleFns,
     component_fns: &ComponentFns,
     ctx: &SerializeCtx,
     replicated_component: &ReplicatedComponent,
     component: Ptr<'_>,
+    lod: usize,
 ) -> bincode::Result<Range<usize>> {
-    if let Some(component_range) = component_range.clone() {
-        return Ok(component_range);
+    if let Some((_, component_range)) = component_ranges
+        .iter()
+        .find(|&&(cached_lod, _)| cached_lod == lod)
+    {
+        return Ok(component_range.clone());
     }
 
     let range = serialized.write_component(
@@ -687,8 +1485,59 @@ fn write_component_cached(
         ctx,
         replicated_component.fns_id,
         component,
+        lod,
     )?;
-    *component_range = Some(range.clone());
+    component_ranges.push((lod, range.clone()));
+
+    Ok(range)
+}
+
+/// Returns the squared distance between the entity and the camera of the client.
+///
+/// Returns [`None`] if the client has no camera or one of the entities has no [`Transform`].
+fn camera_distance_squared(
+    world: &World,
+    client: &ReplicatedClient,
+    entity: Entity,
+) -> Option<f32> {
+    let camera_transform = world.get::<Transform>(client.camera_entity()?)?;
+    let transform = world.get::<Transform>(entity)?;
+    Some(
+        transform
+            .translation
+            .distance_squared(camera_transform.translation),
+    )
+}
+
+/// Writes a delta-encoded component for a client and stores its value as a future base.
+///
+/// Uses the latest value acknowledged by the client on `acked_tick` as the base.
+fn write_delta_component(
+    serialized: &mut SerializedData,
+    delta_cache: &mut DeltaCache,
+    client_id: ClientId,
+    entity: Entity,
+    acked_tick: Tick,
+    this_run: Tick,
+    rule_fns: &UntypedRuleFns,
+    component_fns: &ComponentFns,
+    ctx: &SerializeCtx,
+    replicated_component: &ReplicatedComponent,
+    component: Ptr<'_>,
+) -> bincode::Result<Range<usize>> {
+    let bases = delta_cache.bases_mut(client_id, entity, ctx.component_id);
+    let range = serialized.write_delta_component(
+        rule_fns,
+        component_fns,
+        ctx,
+        replicated_component.fns_id,
+        component,
+        bases.acked(acked_tick, this_run),
+    )?;
+
+    // SAFETY: `component` and `component_fns` were created for the same type.
+    let value = unsafe { component_fns.clone_delta(component) };
+    bases.push(this_run, ctx.server_tick, value);
 
     Ok(range)
 }
@@ -709,6 +1558,23 @@ fn write_tick_cached(
     Ok(range)
 }
 
+/// User-defined metadata attached to update messages for the next replication tick.
+///
+/// Each entry is a key and arbitrary data. Should be populated before [`ServerSet::Send`].
+/// If not empty, an update message will be sent to all replicated clients even if there are no
+/// other changes. Cleared after sending.
+///
+/// Received on clients as [`ServerUpdateMetadata`](crate::client::ServerUpdateMetadata).
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct PendingUpdateMetadata(pub Vec<(u32, Vec<u8>)>);
+
+/// Send-related settings from [`ServerPlugin`].
+#[derive(Resource, Clone, Copy)]
+pub(super) struct SendSettings {
+    bandwidth_budget: Option<usize>,
+    entity_limit: Option<usize>,
+}
+
 /// Set with replication and event systems related to server.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum ServerSet {
@@ -736,12 +1602,32 @@ pub enum ServerSet {
     ///
     /// Runs in [`PostUpdate`].
     StoreHierarchy,
+    /// Systems that run right before replication is collected.
+    ///
+    /// Intended for user systems that prepare per-tick replication state,
+    /// such as updating interest management or resetting replication budgets.
+    ///
+    /// Runs in [`PostUpdate`].
+    BeforeSend,
     /// Systems that send data to [`RepliconServer`].
     ///
     /// Used by `bevy_replicon`.
     ///
     /// Runs in [`PostUpdate`] on server tick, see [`TickPolicy`].
     Send,
+    /// Systems that run right after replication is collected.
+    ///
+    /// Intended for user systems that clear frame-local state or collect telemetry
+    /// before packets are sent to the messaging backend.
+    ///
+    /// Runs in [`PostUpdate`].
+    AfterSend,
+    /// Systems that populate Bevy's [`Diagnostics`](bevy::diagnostic::Diagnostics).
+    ///
+    /// Used by `bevy_replicon`.
+    ///
+    /// Runs in [`PostUpdate`].
+    Diagnostics,
     /// Systems that send packets to the messaging backend.
     ///
     /// Used by the messaging backend.
@@ -789,3 +1675,26 @@ pub enum ServerEvent {
 /// See also [`Trigger`].
 #[derive(Debug, Clone, Copy, Event, Deref)]
 pub struct StartReplication(pub ClientId);
+
+/// Replication stats for sent messages.
+///
+/// Statistic will be collected only if the resource is present.
+/// The resource is not added by default, see [`ServerPlugin::track_stats`].
+///
+/// See also [`ServerDiagnosticsPlugin`](diagnostics::ServerDiagnosticsPlugin)
+/// for automatic integration with Bevy diagnostics.
+#[derive(Clone, Default, Resource, Debug)]
+pub struct ServerReplicationStats {
+    /// Update messages sent.
+    pub update_messages: usize,
+    /// Bytes sent in update messages.
+    pub update_bytes: usize,
+    /// Mutate messages sent.
+    pub mutate_messages: usize,
+    /// Bytes sent in mutate messages.
+    pub mutate_bytes: usize,
+    /// Incremented per entity that was serialized into an update or mutate message for a client.
+    pub entities: usize,
+    /// Bytes sent to each client during the last replication tick.
+    pub client_bytes: HashMap<ClientId, usize>,
+}
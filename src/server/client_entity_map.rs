@@ -1,4 +1,4 @@
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::HashMap};
 
 use crate::core::ClientId;
 
@@ -70,7 +70,16 @@ If client's original entity is not found, a new entity will be spawned on the cl
 just the same as when no client entity is provided.
 **/
 #[derive(Resource, Debug, Default, Deref)]
-pub struct ClientEntityMap(pub(super) HashMap<ClientId, Vec<ClientMapping>>);
+pub struct ClientEntityMap {
+    /// Mappings that haven't been sent yet for each client.
+    #[deref]
+    mappings: HashMap<ClientId, Vec<ClientMapping>>,
+
+    /// Server entities mapped to client entities from [`Self::mappings`].
+    ///
+    /// Used for reverse lookups.
+    client_entities: EntityHashMap<Entity>,
+}
 
 impl ClientEntityMap {
     /// Registers `mapping` for a client entity pre-spawned by the specified client.
@@ -78,7 +87,77 @@ impl ClientEntityMap {
     /// This will be sent as part of replication data and added to the client's
     /// [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap).
     pub fn insert(&mut self, client_id: ClientId, mapping: ClientMapping) {
-        self.0.entry(client_id).or_default().push(mapping);
+        self.client_entities
+            .insert(mapping.server_entity, mapping.client_entity);
+        self.mappings.entry(client_id).or_default().push(mapping);
+    }
+
+    /// Returns an iterator over all pending mappings as `(server_entity, client_entity)` pairs.
+    pub fn iter_mappings(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.mappings
+            .values()
+            .flatten()
+            .map(|mapping| (mapping.server_entity, mapping.client_entity))
+    }
+
+    /// Returns the number of pending mappings for all clients.
+    pub fn len(&self) -> usize {
+        self.mappings.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if there are no pending mappings.
+    pub fn is_empty(&self) -> bool {
+        self.mappings.values().all(Vec::is_empty)
+    }
+
+    /// Returns `true` if there is a pending mapping for the server entity.
+    pub fn contains_server(&self, server_entity: Entity) -> bool {
+        self.client_entities.contains_key(&server_entity)
+    }
+
+    /// Returns the client entity from a pending mapping for the server entity.
+    ///
+    /// If the server entity is mapped for multiple clients, the last inserted mapping is used.
+    pub fn client_entity_for(&self, server_entity: Entity) -> Option<Entity> {
+        self.client_entities.get(&server_entity).copied()
+    }
+
+    /// Removes all mappings for a client, returning them as an iterator with their count.
+    ///
+    /// Keeps the allocated memory for reuse.
+    pub(super) fn drain_client(
+        &mut self,
+        client_id: ClientId,
+    ) -> Option<(usize, impl Iterator<Item = ClientMapping> + '_)> {
+        let mappings = self.mappings.get_mut(&client_id)?;
+        let client_entities = &mut self.client_entities;
+        let iter = mappings.drain(..).inspect(|mapping| {
+            remove_client_entity(client_entities, mapping);
+        });
+
+        Some((iter.len(), iter))
+    }
+
+    /// Removes all mappings for a disconnected client.
+    pub(super) fn remove_client(&mut self, client_id: ClientId) {
+        if let Some(mappings) = self.mappings.remove(&client_id) {
+            for mapping in &mappings {
+                remove_client_entity(&mut self.client_entities, mapping);
+            }
+        }
+    }
+
+    /// Removes all mappings.
+    pub(super) fn clear(&mut self) {
+        self.mappings.clear();
+        self.client_entities.clear();
+    }
+}
+
+/// Removes the reverse lookup for `mapping` unless the server entity was re-mapped to another client entity.
+fn remove_client_entity(client_entities: &mut EntityHashMap<Entity>, mapping: &ClientMapping) {
+    if client_entities.get(&mapping.server_entity) == Some(&mapping.client_entity) {
+        client_entities.remove(&mapping.server_entity);
     }
 }
 
@@ -88,3 +167,62 @@ pub struct ClientMapping {
     pub server_entity: Entity,
     pub client_entity: Entity,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mappings() {
+        const CLIENT1: ClientId = ClientId::new(1);
+        const CLIENT2: ClientId = ClientId::new(2);
+        let server_entity1 = Entity::from_raw(0);
+        let server_entity2 = Entity::from_raw(1);
+        let client_entity1 = Entity::from_raw(2);
+        let client_entity2 = Entity::from_raw(3);
+
+        let mut entity_map = ClientEntityMap::default();
+        assert!(entity_map.is_empty());
+
+        entity_map.insert(
+            CLIENT1,
+            ClientMapping {
+                server_entity: server_entity1,
+                client_entity: client_entity1,
+            },
+        );
+        entity_map.insert(
+            CLIENT2,
+            ClientMapping {
+                server_entity: server_entity2,
+                client_entity: client_entity2,
+            },
+        );
+        assert_eq!(entity_map.len(), 2);
+        assert!(entity_map.contains_server(server_entity1));
+        assert_eq!(
+            entity_map.client_entity_for(server_entity2),
+            Some(client_entity2)
+        );
+
+        let mut mappings: Vec<_> = entity_map.iter_mappings().collect();
+        mappings.sort();
+        assert_eq!(
+            mappings,
+            [
+                (server_entity1, client_entity1),
+                (server_entity2, client_entity2)
+            ]
+        );
+
+        let (len, mappings) = entity_map.drain_client(CLIENT1).unwrap();
+        assert_eq!(len, 1);
+        assert_eq!(mappings.count(), 1);
+        assert!(!entity_map.contains_server(server_entity1));
+        assert_eq!(entity_map.len(), 1);
+
+        entity_map.remove_client(CLIENT2);
+        assert!(entity_map.client_entity_for(server_entity2).is_none());
+        assert!(entity_map.is_empty());
+    }
+}
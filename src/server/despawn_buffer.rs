@@ -1,7 +1,9 @@
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
 
-use super::{ServerPlugin, ServerSet};
-use crate::core::{common_conditions::server_running, replication::Replicated};
+use super::{server_tick::ServerTick, ServerPlugin, ServerSet};
+use crate::core::{
+    common_conditions::server_running, replication::Replicated, replicon_tick::RepliconTick,
+};
 
 /// Treats removals of [`Replicated`] component as despawns and stores them into [`DespawnBuffer`] resource.
 ///
@@ -12,7 +14,9 @@ impl Plugin for DespawnBufferPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<DespawnBuffer>().add_systems(
             PostUpdate,
-            Self::buffer_despawns
+            (Self::despawn_scheduled, Self::buffer_despawns)
+                .chain()
+                .after(ServerPlugin::increment_tick)
                 .before(ServerPlugin::send_replication)
                 .in_set(ServerSet::Send)
                 .run_if(server_running),
@@ -21,6 +25,24 @@ impl Plugin for DespawnBufferPlugin {
 }
 
 impl DespawnBufferPlugin {
+    fn despawn_scheduled(
+        mut commands: Commands,
+        mut despawn_buffer: ResMut<DespawnBuffer>,
+        server_tick: Res<ServerTick>,
+    ) {
+        despawn_buffer.scheduled.retain(|&(tick, entity)| {
+            if tick > **server_tick {
+                return true;
+            }
+
+            if let Some(entity) = commands.get_entity(entity) {
+                debug!("despawning scheduled `{}`", entity.id());
+                entity.despawn_recursive();
+            }
+            false
+        });
+    }
+
     fn buffer_despawns(
         mut removed_replications: RemovedComponents<Replicated>,
         mut despawn_buffer: ResMut<DespawnBuffer>,
@@ -33,9 +55,57 @@ impl DespawnBufferPlugin {
 
 /// Buffer with all despawned entities.
 ///
-/// Should be cleaned up manually.
-#[derive(Default, Resource, Deref, DerefMut)]
-pub(crate) struct DespawnBuffer(Vec<Entity>);
+/// Also stores entities scheduled for despawn with [`Self::schedule_despawn_at`].
+#[derive(Default, Resource, Deref)]
+pub struct DespawnBuffer {
+    /// Despawned entities in the order they were buffered.
+    #[deref]
+    entities: Vec<Entity>,
+
+    /// Entities from [`Self::entities`] for fast duplicate lookups.
+    buffered: EntityHashSet,
+
+    /// Entities that will be despawned on the specified tick.
+    ///
+    /// Scanned every tick since wrapping ticks can't be totally ordered.
+    scheduled: Vec<(RepliconTick, Entity)>,
+}
+
+impl DespawnBuffer {
+    /// Schedules `entity` to be despawned on the server at `tick`.
+    ///
+    /// The entity continues to be replicated until [`ServerTick`] reaches `tick`.
+    /// Then it will be despawned recursively before sending replication,
+    /// so the despawn will be sent to clients with this tick.
+    /// If the tick has already passed, the entity will be despawned on the next server tick.
+    ///
+    /// If the entity was despawned earlier, the scheduled despawn will be ignored.
+    pub fn schedule_despawn_at(&mut self, entity: Entity, tick: RepliconTick) {
+        self.scheduled.push((tick, entity));
+    }
+
+    /// Adds a despawned entity if it's not already buffered.
+    pub(super) fn push(&mut self, entity: Entity) {
+        if self.buffered.insert(entity) {
+            self.entities.push(entity);
+        } else {
+            trace!("ignoring duplicate despawn for {entity:?}");
+        }
+    }
+
+    /// Removes all buffered entities, returning them in insertion order.
+    pub(super) fn drain(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        self.buffered.clear();
+        self.entities.drain(..)
+    }
+
+    /// Clears all buffered despawns, including scheduled ones.
+    pub(super) fn reset(&mut self) {
+        self.entities.clear();
+        self.buffered.clear();
+        self.scheduled.clear();
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -46,7 +116,8 @@ mod tests {
     fn despawns() {
         let mut app = App::new();
         app.add_plugins(DespawnBufferPlugin)
-            .init_resource::<RepliconServer>();
+            .init_resource::<RepliconServer>()
+            .init_resource::<ServerTick>();
 
         app.world_mut()
             .resource_mut::<RepliconServer>()
@@ -61,4 +132,88 @@ mod tests {
         let despawn_buffer = app.world().resource::<DespawnBuffer>();
         assert_eq!(despawn_buffer.len(), 1);
     }
+
+    #[test]
+    fn scheduled() {
+        let mut app = App::new();
+        app.add_plugins(DespawnBufferPlugin)
+            .init_resource::<RepliconServer>()
+            .init_resource::<ServerTick>();
+
+        app.world_mut()
+            .resource_mut::<RepliconServer>()
+            .set_running(true);
+
+        let entity_a = app.world_mut().spawn(Replicated).id();
+        let entity_b = app.world_mut().spawn(Replicated).id();
+
+        let mut despawn_buffer = app.world_mut().resource_mut::<DespawnBuffer>();
+        despawn_buffer.schedule_despawn_at(entity_b, RepliconTick::new(2));
+        despawn_buffer.schedule_despawn_at(entity_a, RepliconTick::new(1));
+
+        app.update();
+
+        assert!(app.world().get_entity(entity_a).is_ok());
+        assert!(app.world().get_entity(entity_b).is_ok());
+
+        app.world_mut().resource_mut::<ServerTick>().increment();
+        app.update();
+
+        assert!(app.world().get_entity(entity_a).is_err());
+        assert!(app.world().get_entity(entity_b).is_ok());
+        let despawn_buffer = app.world().resource::<DespawnBuffer>();
+        assert_eq!(**despawn_buffer, [entity_a]);
+
+        app.world_mut().resource_mut::<ServerTick>().increment();
+        app.update();
+
+        assert!(app.world().get_entity(entity_b).is_err());
+        let despawn_buffer = app.world().resource::<DespawnBuffer>();
+        assert_eq!(**despawn_buffer, [entity_a, entity_b]);
+    }
+
+    #[test]
+    fn scheduled_reset() {
+        let mut app = App::new();
+        app.add_plugins(DespawnBufferPlugin)
+            .init_resource::<RepliconServer>()
+            .init_resource::<ServerTick>();
+
+        app.world_mut()
+            .resource_mut::<RepliconServer>()
+            .set_running(true);
+
+        let entity = app.world_mut().spawn(Replicated).id();
+
+        let mut despawn_buffer = app.world_mut().resource_mut::<DespawnBuffer>();
+        despawn_buffer.schedule_despawn_at(entity, RepliconTick::new(1));
+        despawn_buffer.reset();
+
+        app.world_mut().resource_mut::<ServerTick>().increment();
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_ok());
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut despawn_buffer = DespawnBuffer::default();
+        let entity_a = Entity::from_raw(0);
+        let entity_b = Entity::from_raw(1);
+
+        despawn_buffer.push(entity_b);
+        despawn_buffer.push(entity_a);
+        despawn_buffer.push(entity_b);
+        assert_eq!(despawn_buffer.len(), 2);
+
+        let entities: Vec<_> = despawn_buffer.drain().collect();
+        assert_eq!(entities, [entity_b, entity_a]);
+
+        despawn_buffer.push(entity_b);
+        assert_eq!(
+            despawn_buffer.len(),
+            1,
+            "buffer should be reusable after drain"
+        );
+    }
 }
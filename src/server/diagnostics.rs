@@ -0,0 +1,98 @@
+use bevy::diagnostic::DiagnosticPath;
+use bevy::{
+    diagnostic::{Diagnostic, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+
+use super::{ServerReplicationStats, ServerSet};
+use crate::core::common_conditions::server_running;
+
+/// Plugin to write [`Diagnostics`] based on [`ServerReplicationStats`] every frame.
+///
+/// Adds [`ServerReplicationStats`] resource.
+pub struct ServerDiagnosticsPlugin;
+
+impl Plugin for ServerDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerReplicationStats>()
+            .add_systems(
+                PostUpdate,
+                Self::add_measurements
+                    .in_set(ServerSet::Diagnostics)
+                    .run_if(server_running),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::UPDATE_MESSAGES)
+                    .with_suffix(" update messages")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::UPDATE_BYTES)
+                    .with_suffix(" update bytes")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::MUTATE_MESSAGES)
+                    .with_suffix(" mutate messages")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::MUTATE_BYTES)
+                    .with_suffix(" mutate bytes")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::ENTITIES)
+                    .with_suffix(" entities")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            );
+    }
+}
+
+impl ServerDiagnosticsPlugin {
+    /// How many update messages sent.
+    pub const UPDATE_MESSAGES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/update_messages");
+    /// How many bytes sent in update messages.
+    pub const UPDATE_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/update_bytes");
+    /// How many mutate messages sent.
+    pub const MUTATE_MESSAGES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/mutate_messages");
+    /// How many bytes sent in mutate messages.
+    pub const MUTATE_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/mutate_bytes");
+    /// How many entities serialized for all clients.
+    pub const ENTITIES: DiagnosticPath = DiagnosticPath::const_new("server/replication/entities");
+
+    /// Max diagnostic history length.
+    pub const DIAGNOSTIC_HISTORY_LEN: usize = 60;
+
+    fn add_measurements(
+        mut diagnostics: Diagnostics,
+        stats: Res<ServerReplicationStats>,
+        mut last_stats: Local<ServerReplicationStats>,
+    ) {
+        // Stats are reset when the server stops, so subtraction could overflow.
+        diagnostics.add_measurement(&Self::UPDATE_MESSAGES, || {
+            stats
+                .update_messages
+                .saturating_sub(last_stats.update_messages) as f64
+        });
+        diagnostics.add_measurement(&Self::UPDATE_BYTES, || {
+            stats.update_bytes.saturating_sub(last_stats.update_bytes) as f64
+        });
+        diagnostics.add_measurement(&Self::MUTATE_MESSAGES, || {
+            stats
+                .mutate_messages
+                .saturating_sub(last_stats.mutate_messages) as f64
+        });
+        diagnostics.add_measurement(&Self::MUTATE_BYTES, || {
+            stats.mutate_bytes.saturating_sub(last_stats.mutate_bytes) as f64
+        });
+        diagnostics.add_measurement(&Self::ENTITIES, || {
+            stats.entities.saturating_sub(last_stats.entities) as f64
+        });
+        last_stats.clone_from(&stats);
+    }
+}
@@ -8,9 +8,10 @@ use crate::core::{
     common_conditions::*,
     connected_clients::ConnectedClients,
     event::{
+        client_event::ClientEventRateLimits,
         ctx::{ServerReceiveCtx, ServerSendCtx},
         event_registry::EventRegistry,
-        server_event::BufferedServerEvents,
+        server_event::{BufferedServerEvents, ClientGroupRegistry},
     },
     replication::replicated_clients::ReplicatedClients,
     replicon_server::RepliconServer,
@@ -44,6 +45,7 @@ impl Plugin for ServerEventPlugin {
             ParamBuilder,
             ParamBuilder,
             ParamBuilder,
+            ParamBuilder,
         )
             .build_state(app.world_mut())
             .build_system(Self::send_or_buffer);
@@ -57,6 +59,7 @@ impl Plugin for ServerEventPlugin {
             ParamBuilder,
             ParamBuilder,
             ParamBuilder,
+            ParamBuilder,
         )
             .build_state(app.world_mut())
             .build_system(Self::receive);
@@ -73,6 +76,7 @@ impl Plugin for ServerEventPlugin {
                 }
             }),
             ParamBuilder,
+            ParamBuilder,
         )
             .build_state(app.world_mut())
             .build_system(Self::resend_locally);
@@ -80,7 +84,10 @@ impl Plugin for ServerEventPlugin {
         app.insert_resource(event_registry)
             .add_systems(
                 PreUpdate,
-                receive.in_set(ServerSet::Receive).run_if(server_running),
+                (Self::refill_rate_limits, receive)
+                    .chain()
+                    .in_set(ServerSet::Receive)
+                    .run_if(server_running),
             )
             .add_systems(
                 PostUpdate,
@@ -105,6 +112,7 @@ impl ServerEventPlugin {
         mut buffered_events: ResMut<BufferedServerEvents>,
         registry: Res<AppTypeRegistry>,
         connected_clients: Res<ConnectedClients>,
+        client_groups: Res<ClientGroupRegistry>,
         event_registry: Res<EventRegistry>,
     ) {
         buffered_events.start_tick();
@@ -124,6 +132,7 @@ impl ServerEventPlugin {
                     &server_events,
                     &mut server,
                     &connected_clients,
+                    &client_groups,
                     &mut buffered_events,
                 );
             }
@@ -134,15 +143,26 @@ impl ServerEventPlugin {
         mut server: ResMut<RepliconServer>,
         mut buffered_events: ResMut<BufferedServerEvents>,
         replicated_clients: Res<ReplicatedClients>,
+        client_groups: Res<ClientGroupRegistry>,
     ) {
+        trace!(
+            "sending {} buffered event(s) with ~{} bytes",
+            buffered_events.event_count(),
+            buffered_events.byte_estimate()
+        );
         buffered_events
-            .send_all(&mut server, &replicated_clients)
+            .send_all(&mut server, &replicated_clients, &client_groups)
             .expect("buffered server events should send");
     }
 
+    fn refill_rate_limits(mut rate_limits: ResMut<ClientEventRateLimits>, time: Res<Time>) {
+        rate_limits.refill(time.delta_secs());
+    }
+
     fn receive(
         mut client_events: FilteredResourcesMut,
         mut server: ResMut<RepliconServer>,
+        mut rate_limits: ResMut<ClientEventRateLimits>,
         registry: Res<AppTypeRegistry>,
         event_registry: Res<EventRegistry>,
     ) {
@@ -156,13 +176,21 @@ impl ServerEventPlugin {
                 .expect("client events shouldn't be removed");
 
             // SAFETY: passed pointer was obtained using this event data.
-            unsafe { event_data.receive(&mut ctx, client_events.into_inner(), &mut server) };
+            unsafe {
+                event_data.receive(
+                    &mut ctx,
+                    client_events.into_inner(),
+                    &mut server,
+                    &mut rate_limits,
+                )
+            };
         }
     }
 
     fn resend_locally(
         mut server_events: FilteredResourcesMut,
         mut events: FilteredResourcesMut,
+        client_groups: Res<ClientGroupRegistry>,
         event_registry: Res<EventRegistry>,
     ) {
         for event_data in event_registry.iter_server_events() {
@@ -174,7 +202,13 @@ impl ServerEventPlugin {
                 .expect("events shouldn't be removed");
 
             // SAFETY: passed pointers were obtained using this event data.
-            unsafe { event_data.resend_locally(server_events.into_inner(), events.into_inner()) };
+            unsafe {
+                event_data.resend_locally(
+                    server_events.into_inner(),
+                    events.into_inner(),
+                    &client_groups,
+                )
+            };
         }
     }
 }
@@ -0,0 +1,261 @@
+use std::ops::Range;
+
+use bevy::{ecs::component::Tick, prelude::*};
+
+use super::{
+    get_component_unchecked,
+    replicated_archetypes::ReplicatedArchetypes,
+    replication_messages::{serialized_data::SerializedData, ReplicationMessages},
+};
+use crate::core::{
+    replication::{
+        replicated_clients::{client_visibility::Visibility, ReplicatedClients, VisibilityPolicy},
+        replication_registry::{ctx::SerializeCtx, ReplicationRegistry},
+        ReplicationTags,
+    },
+    replicon_tick::RepliconTick,
+    ClientId,
+};
+
+/// Pre-serialized state of all replicated entities used to speed up initial replication for new clients.
+///
+/// Used if [`ServerPlugin::hot_join_cache`](super::ServerPlugin::hot_join_cache) is set.
+/// Clients that start replication receive the snapshot as is, and only changes since the snapshot
+/// are collected for them.
+///
+/// Rebuilt only when a client starts replication and the snapshot is older than the interval or invalid.
+/// Sleeping and paused entities are not included into the snapshot.
+///
+/// Invalidated until the next rebuild on any despawn, component insertion or removal, insertion of
+/// [`ReplicationSleeping`](crate::core::replication::ReplicationSleeping) or
+/// [`ReplicationPaused`](crate::core::replication::ReplicationPaused), or change of
+/// [`ReplicationRules`](crate::core::replication::replication_rules::ReplicationRules).
+#[derive(Resource)]
+pub struct HotJoinSnapshot {
+    /// Number of ticks for which the snapshot can be reused.
+    interval: u32,
+
+    /// Serialized entities and their components.
+    serialized: SerializedData,
+
+    /// Entities from the snapshot with ranges pointing into [`Self::serialized`].
+    entities: Vec<SnapshotEntity>,
+
+    /// Server and system ticks at which the snapshot was last built.
+    built_ticks: Option<(RepliconTick, Tick)>,
+
+    /// Indicates that nothing invalidated the snapshot since the last rebuild.
+    valid: bool,
+
+    /// Clients that started replication since the last send.
+    pending_clients: Vec<ClientId>,
+}
+
+impl HotJoinSnapshot {
+    pub(super) fn new(interval: u32) -> Self {
+        Self {
+            interval,
+            serialized: Default::default(),
+            entities: Default::default(),
+            built_ticks: None,
+            valid: false,
+            pending_clients: Default::default(),
+        }
+    }
+
+    /// Returns the server tick at which the snapshot was built.
+    ///
+    /// Returns [`None`] if the snapshot is invalid.
+    pub fn tick(&self) -> Option<RepliconTick> {
+        self.built_ticks
+            .filter(|_| self.valid)
+            .map(|(tick, _)| tick)
+    }
+
+    /// Returns the number of entities in the snapshot.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if the snapshot contains no entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Marks the snapshot as invalid until the next rebuild.
+    pub fn invalidate(&mut self) {
+        if self.valid {
+            debug!("invalidating hot join snapshot");
+            self.valid = false;
+        }
+    }
+
+    /// Returns `true` if the snapshot can be sent at `server_tick`.
+    fn is_fresh(&self, server_tick: RepliconTick) -> bool {
+        self.tick()
+            .is_some_and(|tick| server_tick - tick <= self.interval)
+    }
+
+    /// Returns `true` if there are clients waiting for the snapshot, but it can't be sent at `server_tick`.
+    pub(super) fn should_rebuild(&self, server_tick: RepliconTick) -> bool {
+        !self.pending_clients.is_empty() && !self.is_fresh(server_tick)
+    }
+
+    pub(super) fn add_pending_client(&mut self, client_id: ClientId) {
+        self.pending_clients.push(client_id);
+    }
+
+    /// Writes the snapshot into update messages for clients that just started replication.
+    ///
+    /// Updates mutation ticks for all written entities,
+    /// so only changes since the snapshot will be collected for them.
+    pub(super) fn collect(
+        &mut self,
+        messages: &mut ReplicationMessages,
+        serialized: &mut SerializedData,
+        replicated_clients: &mut ReplicatedClients,
+        world: &World,
+    ) {
+        if self.pending_clients.is_empty() {
+            return;
+        }
+
+        let (_, system_tick) = self
+            .built_ticks
+            .expect("snapshot should be rebuilt for pending clients");
+
+        let policy = replicated_clients.visibility_policy();
+        let mut offset = None;
+        for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter_mut()) {
+            if !self.pending_clients.contains(&client.id()) {
+                continue;
+            }
+
+            debug!("sending hot join snapshot to {:?}", client.id());
+            let offset = *offset.get_or_insert_with(|| {
+                let offset = serialized.len();
+                serialized.extend_from_slice(&self.serialized);
+                offset
+            });
+            for snapshot_entity in &self.entities {
+                let visible = match policy {
+                    VisibilityPolicy::TagBased => world
+                        .get::<ReplicationTags>(snapshot_entity.entity)
+                        .is_some_and(|tags| client.tag_filter().intersects(tags)),
+                    _ => client.visibility().state(snapshot_entity.entity) == Visibility::Visible,
+                };
+                if !visible {
+                    continue;
+                }
+
+                message.add_changed_entity(shift(&snapshot_entity.entity_range, offset));
+                for component in &snapshot_entity.components {
+                    message.add_inserted_component(shift(component, offset));
+                }
+                client.set_mutation_tick(snapshot_entity.entity, system_tick);
+            }
+        }
+
+        self.pending_clients.clear();
+    }
+
+    /// Serializes all non-sleeping and non-paused replicated entities.
+    pub(super) fn rebuild(
+        &mut self,
+        replicated_archetypes: &ReplicatedArchetypes,
+        registry: &ReplicationRegistry,
+        world: &World,
+        system_tick: Tick,
+        server_tick: RepliconTick,
+    ) -> bincode::Result<()> {
+        self.serialized.clear();
+        self.entities.clear();
+
+        for (replicated_archetype, archetype) in
+            replicated_archetypes
+                .iter_nonempty(world)
+                .filter(|(replicated_archetype, _)| {
+                    !replicated_archetype.paused && !replicated_archetype.sleeping
+                })
+        {
+            // SAFETY: table obtained from this archetype.
+            let table = unsafe {
+                world
+                    .storages()
+                    .tables
+                    .get(archetype.table_id())
+                    .unwrap_unchecked()
+            };
+
+            for entity in archetype.entities() {
+                let entity_range = self.serialized.write_entity(entity.id())?;
+                let mut components = Vec::with_capacity(replicated_archetype.components.len());
+                for replicated_component in &replicated_archetype.components {
+                    let (component_id, component_fns, rule_fns) =
+                        registry.get(replicated_component.fns_id);
+
+                    // SAFETY: component and storage were obtained from this archetype.
+                    let (component, _) = unsafe {
+                        get_component_unchecked(
+                            table,
+                            &world.storages().sparse_sets,
+                            entity,
+                            replicated_component.storage_type,
+                            component_id,
+                        )
+                    };
+
+                    let ctx = SerializeCtx {
+                        server_tick,
+                        component_id,
+                    };
+                    // The snapshot is shared between clients, so the lowest level of detail is used.
+                    let range = self.serialized.write_component(
+                        rule_fns,
+                        component_fns,
+                        &ctx,
+                        replicated_component.fns_id,
+                        component,
+                        component_fns.select_lod(None),
+                    )?;
+                    components.push(range);
+                }
+
+                self.entities.push(SnapshotEntity {
+                    entity: entity.id(),
+                    entity_range,
+                    components,
+                });
+            }
+        }
+
+        debug!(
+            "rebuilt hot join snapshot with {} entities and {} bytes",
+            self.entities.len(),
+            self.serialized.len()
+        );
+        self.built_ticks = Some((server_tick, system_tick));
+        self.valid = true;
+
+        Ok(())
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.serialized.clear();
+        self.entities.clear();
+        self.built_ticks = None;
+        self.valid = false;
+        self.pending_clients.clear();
+    }
+}
+
+/// Serialized entity inside [`HotJoinSnapshot`].
+struct SnapshotEntity {
+    entity: Entity,
+    entity_range: Range<usize>,
+    components: Vec<Range<usize>>,
+}
+
+fn shift(range: &Range<usize>, offset: usize) -> Range<usize> {
+    range.start + offset..range.end + offset
+}
@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::ServerReplicationStats;
+use crate::core::{
+    connected_clients::{ConnectedClient, ConnectedClients},
+    ClientId,
+};
+
+/// Rolling network stats for each connected client over the last replication ticks.
+///
+/// Updated in [`ServerSet::AfterSend`](super::ServerSet::AfterSend) on each replication tick.
+/// Inserted as resource by [`ServerPlugin`](super::ServerPlugin) if
+/// [`ServerPlugin::track_stats`](super::ServerPlugin::track_stats) is enabled.
+/// See also [`ServerPlugin::stats_history_window`](super::ServerPlugin::stats_history_window).
+#[derive(Resource, Debug)]
+pub struct NetworkStatsHistory {
+    window: usize,
+    clients: HashMap<ClientId, ClientStatsHistory>,
+}
+
+impl NetworkStatsHistory {
+    /// Maximum number of ticks that can be stored for each client.
+    pub const MAX_WINDOW: usize = 1024;
+
+    /// Creates a new instance that stores stats for the last `window` ticks.
+    ///
+    /// The window is capped to [`Self::MAX_WINDOW`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        assert_ne!(window, 0, "stats history window should be positive");
+        Self {
+            window: window.min(Self::MAX_WINDOW),
+            clients: Default::default(),
+        }
+    }
+
+    /// Returns the number of ticks stored for each client.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the history for a client.
+    ///
+    /// Returns [`None`] if the client is not connected or no ticks were recorded yet.
+    pub fn client(&self, client_id: ClientId) -> Option<&ClientStatsHistory> {
+        self.clients.get(&client_id)
+    }
+
+    /// Records stats for all connected clients and forgets disconnected ones.
+    pub(super) fn record(
+        &mut self,
+        connected_clients: &ConnectedClients,
+        replication_stats: &ServerReplicationStats,
+    ) {
+        self.clients.retain(|client_id, _| {
+            connected_clients
+                .iter()
+                .any(|client| client.id() == *client_id)
+        });
+
+        for &client in connected_clients.iter() {
+            let bytes_sent = replication_stats
+                .client_bytes
+                .get(&client.id())
+                .copied()
+                .unwrap_or_default();
+            let history = self.clients.entry(client.id()).or_default();
+            if history.snapshots.len() == self.window {
+                history.snapshots.pop_front();
+            }
+            history
+                .snapshots
+                .push_back(StatsSnapshot { client, bytes_sent });
+        }
+    }
+
+    /// Clears the history for all clients.
+    pub(super) fn clear(&mut self) {
+        self.clients.clear();
+    }
+}
+
+/// Stats of a single client over the last replication ticks.
+///
+/// See also [`NetworkStatsHistory`].
+#[derive(Default, Debug)]
+pub struct ClientStatsHistory {
+    snapshots: VecDeque<StatsSnapshot>,
+}
+
+impl ClientStatsHistory {
+    /// Returns the number of recorded ticks.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns `true` if no ticks were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Returns the average round-trip time in milliseconds.
+    ///
+    /// Returns zero if the history is empty.
+    pub fn average_latency_ms(&self) -> f64 {
+        self.average(|snapshot| snapshot.client.rtt() * 1000.0)
+    }
+
+    /// Returns the average packet loss %.
+    ///
+    /// Returns zero if the history is empty.
+    pub fn average_packet_loss(&self) -> f64 {
+        self.average(|snapshot| snapshot.client.packet_loss())
+    }
+
+    /// Returns the maximum number of replication bytes sent to the client in a single tick.
+    ///
+    /// See also [`ServerReplicationStats::client_bytes`].
+    pub fn peak_bytes_sent(&self) -> usize {
+        self.snapshots
+            .iter()
+            .map(|snapshot| snapshot.bytes_sent)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn average(&self, value: impl Fn(&StatsSnapshot) -> f64) -> f64 {
+        if self.snapshots.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f64 = self.snapshots.iter().map(value).sum();
+        sum / self.snapshots.len() as f64
+    }
+}
+
+/// Stats of a client recorded in a single replication tick.
+#[derive(Debug, Clone, Copy)]
+struct StatsSnapshot {
+    client: ConnectedClient,
+    bytes_sent: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_window() {
+        const CLIENT_ID: ClientId = ClientId::new(0);
+
+        let mut connected_clients = ConnectedClients::default();
+        connected_clients.add(CLIENT_ID);
+        let mut replication_stats = ServerReplicationStats::default();
+        let mut history = NetworkStatsHistory::new(2);
+
+        for (rtt, bytes_sent) in [(0.1, 30), (0.2, 20), (0.4, 10)] {
+            let client = connected_clients.iter_mut().next().unwrap();
+            client.set_rtt(rtt);
+            client.set_packet_loss(rtt * 10.0);
+            replication_stats.client_bytes.insert(CLIENT_ID, bytes_sent);
+            history.record(&connected_clients, &replication_stats);
+        }
+
+        let client_history = history.client(CLIENT_ID).unwrap();
+        assert_eq!(client_history.len(), 2);
+        assert!((client_history.average_latency_ms() - 300.0).abs() < 1e-9);
+        assert!((client_history.average_packet_loss() - 3.0).abs() < 1e-9);
+        assert_eq!(client_history.peak_bytes_sent(), 20);
+
+        connected_clients.remove(CLIENT_ID);
+        history.record(&connected_clients, &replication_stats);
+        assert!(history.client(CLIENT_ID).is_none());
+    }
+
+    #[test]
+    fn capped_window() {
+        let history = NetworkStatsHistory::new(usize::MAX);
+        assert_eq!(history.window(), NetworkStatsHistory::MAX_WINDOW);
+    }
+}
@@ -0,0 +1,234 @@
+use std::{collections::VecDeque, mem};
+
+use bevy::{
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+
+use super::{find_replicated_entity, replicated_archetypes::ReplicatedArchetypes};
+use crate::core::replication::EntityReplicationOrder;
+
+/// Replicated entities with [`EntityReplicationOrder`] and their targets sorted by constraints.
+///
+/// Keeps the allocated memory between ticks.
+#[derive(Default)]
+pub(crate) struct OrderedEntities {
+    /// Entities in the sorted order.
+    sorted: Vec<Entity>,
+
+    /// Entities from [`Self::sorted`] for fast lookups.
+    set: EntityHashSet,
+
+    /// Entities in the collection order.
+    entities: Vec<Entity>,
+
+    /// Indices of entities inside [`Self::entities`].
+    indices: EntityHashMap<usize>,
+
+    /// Constraints between entities as indices, the first should be collected before the second.
+    edges: Vec<(usize, usize)>,
+
+    /// Number of unresolved constraints for each entity.
+    in_degrees: Vec<usize>,
+
+    /// Entities that depend on each entity.
+    dependents: Vec<Vec<usize>>,
+
+    /// Entities without unresolved constraints.
+    queue: VecDeque<usize>,
+
+    /// Indicates that the cycle was already reported.
+    ///
+    /// Reset once constraints no longer contain a cycle to warn only once per cycle.
+    cycle_reported: bool,
+}
+
+impl OrderedEntities {
+    /// Returns `true` if the entity is collected with the ordered entities.
+    pub(super) fn contains(&self, entity: Entity) -> bool {
+        self.set.contains(&entity)
+    }
+
+    /// Returns entities in the sorted order.
+    pub(super) fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.sorted.iter().copied()
+    }
+
+    /// Collects and sorts replicated entities with [`EntityReplicationOrder`] and their targets.
+    ///
+    /// Independent entities preserve the collection order.
+    /// If constraints contain a cycle, they are ignored.
+    pub(super) fn update(&mut self, replicated_archetypes: &ReplicatedArchetypes, world: &World) {
+        self.clear();
+
+        for (_, archetype) in replicated_archetypes
+            .iter_nonempty(world)
+            .filter(|(replicated_archetype, _)| !replicated_archetype.paused)
+            .filter(|(_, archetype)| archetype.contains(replicated_archetypes.order_id()))
+        {
+            for entity in archetype.entities() {
+                let order = world
+                    .get::<EntityReplicationOrder>(entity.id())
+                    .expect("archetype should contain the order component");
+
+                let index = self.add(entity.id());
+                for (first, second) in [
+                    order.after.map(|after| (after, entity.id())),
+                    order.before.map(|before| (entity.id(), before)),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    if first == second {
+                        continue;
+                    }
+
+                    let target = if first == entity.id() { second } else { first };
+                    if find_replicated_entity(replicated_archetypes, world, target).is_some() {
+                        let target_index = self.add(target);
+                        if first == entity.id() {
+                            self.edges.push((index, target_index));
+                        } else {
+                            self.edges.push((target_index, index));
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.edges.is_empty() {
+            self.cycle_reported = false;
+            mem::swap(&mut self.sorted, &mut self.entities);
+        } else if !self.sort() {
+            mem::swap(&mut self.sorted, &mut self.entities);
+        }
+        self.set.extend(self.sorted.iter().copied());
+    }
+
+    /// Adds an entity if it's not already present and returns its index.
+    fn add(&mut self, entity: Entity) -> usize {
+        *self.indices.entry(entity).or_insert_with(|| {
+            self.entities.push(entity);
+            self.entities.len() - 1
+        })
+    }
+
+    /// Sorts entities into [`Self::sorted`] using Kahn's algorithm.
+    ///
+    /// Returns `false` if constraints contain a cycle.
+    fn sort(&mut self) -> bool {
+        self.in_degrees.resize(self.entities.len(), 0);
+        self.dependents
+            .resize_with(self.entities.len(), Default::default);
+        for &(first, second) in &self.edges {
+            self.dependents[first].push(second);
+            self.in_degrees[second] += 1;
+        }
+
+        self.queue.extend(
+            self.in_degrees
+                .iter()
+                .enumerate()
+                .filter(|(_, &in_degree)| in_degree == 0)
+                .map(|(index, _)| index),
+        );
+        while let Some(index) = self.queue.pop_front() {
+            self.sorted.push(self.entities[index]);
+            for &dependent in &self.dependents[index] {
+                self.in_degrees[dependent] -= 1;
+                if self.in_degrees[dependent] == 0 {
+                    self.queue.push_back(dependent);
+                }
+            }
+        }
+
+        if self.sorted.len() != self.entities.len() {
+            if !self.cycle_reported {
+                warn!("`EntityReplicationOrder` constraints contain a cycle, ignoring them");
+                self.cycle_reported = true;
+            }
+            self.sorted.clear();
+            return false;
+        }
+
+        self.cycle_reported = false;
+        true
+    }
+
+    fn clear(&mut self) {
+        self.sorted.clear();
+        self.set.clear();
+        self.entities.clear();
+        self.indices.clear();
+        self.edges.clear();
+        self.in_degrees.clear();
+        for dependents in &mut self.dependents {
+            dependents.clear();
+        }
+        self.queue.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::replication::{replication_rules::ReplicationRules, Replicated};
+
+    #[test]
+    fn sorting() {
+        let mut world = World::new();
+        let first = world.spawn(Replicated).id();
+        let second = world
+            .spawn((
+                Replicated,
+                EntityReplicationOrder {
+                    before: Some(first),
+                    after: None,
+                },
+            ))
+            .id();
+
+        let mut replicated_archetypes = ReplicatedArchetypes::from_world(&mut world);
+        replicated_archetypes.update(&world, &ReplicationRules::default());
+
+        let mut ordered_entities = OrderedEntities::default();
+        ordered_entities.update(&replicated_archetypes, &world);
+        assert_eq!(ordered_entities.iter().collect::<Vec<_>>(), [second, first]);
+        assert!(ordered_entities.contains(first));
+        assert!(!ordered_entities.cycle_reported);
+    }
+
+    #[test]
+    fn cycle() {
+        let mut world = World::new();
+        let first = world.spawn(Replicated).id();
+        let second = world
+            .spawn((
+                Replicated,
+                EntityReplicationOrder {
+                    before: Some(first),
+                    after: Some(first),
+                },
+            ))
+            .id();
+
+        let mut replicated_archetypes = ReplicatedArchetypes::from_world(&mut world);
+        replicated_archetypes.update(&world, &ReplicationRules::default());
+
+        let mut ordered_entities = OrderedEntities::default();
+        for _ in 0..2 {
+            ordered_entities.update(&replicated_archetypes, &world);
+            assert_eq!(ordered_entities.iter().collect::<Vec<_>>(), [second, first]);
+            assert!(ordered_entities.cycle_reported);
+        }
+
+        world
+            .entity_mut(second)
+            .get_mut::<EntityReplicationOrder>()
+            .unwrap()
+            .after = None;
+
+        ordered_entities.update(&replicated_archetypes, &world);
+        assert!(!ordered_entities.cycle_reported);
+    }
+}
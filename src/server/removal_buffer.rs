@@ -15,6 +15,7 @@ use super::{ServerPlugin, ServerSet};
 use crate::core::{
     common_conditions::server_running,
     replication::{replication_registry::FnsId, replication_rules::ReplicationRules, Replicated},
+    replicon_tick::RepliconTick,
 };
 
 /// Buffers all replicated component removals in [`RemovalBuffer`] resource.
@@ -150,9 +151,26 @@ pub(crate) struct RemovalBuffer {
     /// All data is cleared before the insertion.
     /// Stored to reuse allocated capacity.
     ids_buffer: Vec<Vec<(ComponentId, FnsId)>>,
+
+    /// Number of ticks to accumulate removals before sending.
+    ///
+    /// See [`ServerPlugin::removal_coalescing_window`].
+    coalescing_window: u32,
+
+    /// Removals that wait for the coalescing window to close with ticks on which they first happened.
+    ///
+    /// Buffered by [`Self::update`] and unused if [`Self::coalescing_window`] is 0.
+    pending: EntityHashMap<Vec<(ComponentId, FnsId, Option<RepliconTick>)>>,
 }
 
 impl RemovalBuffer {
+    pub(super) fn new(coalescing_window: u32) -> Self {
+        Self {
+            coalescing_window,
+            ..Default::default()
+        }
+    }
+
     /// Registers component removals that match replication rules for an entity.
     fn update(
         &mut self,
@@ -179,15 +197,73 @@ impl RemovalBuffer {
             }
         }
 
-        if removed_ids.is_empty() {
+        if self.coalescing_window != 0 {
+            let pending = self.pending.entry(entity).or_default();
+            for (component_id, fns_id) in removed_ids.drain(..) {
+                if pending
+                    .iter()
+                    .all(|&(pending_id, ..)| pending_id != component_id)
+                {
+                    // The tick will be assigned on the next send.
+                    pending.push((component_id, fns_id, None));
+                }
+            }
+            if pending.is_empty() {
+                self.pending.remove(&entity);
+            }
+            self.ids_buffer.push(removed_ids);
+        } else if removed_ids.is_empty() {
             self.ids_buffer.push(removed_ids);
         } else {
             self.removals.insert(entity, removed_ids);
         }
     }
 
+    /// Moves pending removals into the buffer if the coalescing window closed.
+    ///
+    /// Removals of components that were re-added and of entities that were despawned
+    /// or stopped replicating are discarded.
+    pub(super) fn flush_pending(&mut self, world: &World, server_tick: RepliconTick) {
+        let mut window_closed = false;
+        self.pending.retain(|&entity, pending| {
+            let Some(entity) = world
+                .get_entity(entity)
+                .ok()
+                .filter(|entity| entity.contains::<Replicated>())
+            else {
+                return false;
+            };
+
+            pending.retain_mut(|(component_id, _, tick)| {
+                if entity.contains_id(*component_id) {
+                    return false;
+                }
+                let tick = *tick.get_or_insert(server_tick);
+                window_closed |= server_tick - tick >= self.coalescing_window;
+                true
+            });
+
+            !pending.is_empty()
+        });
+
+        if !window_closed {
+            return;
+        }
+
+        for (entity, pending) in self.pending.drain() {
+            let mut removed_ids = self.ids_buffer.pop().unwrap_or_default();
+            removed_ids.extend(
+                pending
+                    .into_iter()
+                    .map(|(component_id, fns_id, _)| (component_id, fns_id)),
+            );
+            self.removals.insert(entity, removed_ids);
+        }
+    }
+
     /// Clears all removals.
     ///
+    /// Removals that wait for the coalescing window are kept.
     /// Keeps the allocated memory for reuse.
     pub(super) fn clear(&mut self) {
         self.ids_buffer
@@ -196,6 +272,12 @@ impl RemovalBuffer {
                 components
             }));
     }
+
+    /// Clears all removals, including pending ones.
+    pub(super) fn reset(&mut self) {
+        self.clear();
+        self.pending.clear();
+    }
 }
 
 #[cfg(test)]
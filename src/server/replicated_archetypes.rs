@@ -2,16 +2,17 @@ use std::mem;
 
 use bevy::{
     ecs::{
-        archetype::{ArchetypeGeneration, ArchetypeId},
+        archetype::{Archetype, ArchetypeGeneration, ArchetypeId},
         component::{ComponentId, StorageType},
     },
     log::Level,
     prelude::*,
-    utils::tracing::enabled,
+    utils::{tracing::enabled, HashMap},
 };
 
 use crate::core::replication::{
-    replication_registry::FnsId, replication_rules::ReplicationRules, Replicated,
+    replication_registry::FnsId, replication_rules::ReplicationRules, EntityReplicationOrder,
+    Replicated, ReplicationPaused, ReplicationSleeping,
 };
 
 /// Cached information about all replicated archetypes.
@@ -20,12 +21,24 @@ pub(crate) struct ReplicatedArchetypes {
     /// ID of [`Replicated`] component.
     marker_id: ComponentId,
 
+    /// ID of [`ReplicationSleeping`] component.
+    sleeping_id: ComponentId,
+
+    /// ID of [`ReplicationPaused`] component.
+    paused_id: ComponentId,
+
+    /// ID of [`EntityReplicationOrder`] component.
+    order_id: ComponentId,
+
     /// Highest processed archetype ID.
     generation: ArchetypeGeneration,
 
     /// Archetypes marked as replicated.
     #[deref]
     archetypes: Vec<ReplicatedArchetype>,
+
+    /// Indices of archetypes inside [`Self::archetypes`].
+    indices: HashMap<ArchetypeId, usize>,
 }
 
 impl ReplicatedArchetypes {
@@ -34,6 +47,39 @@ impl ReplicatedArchetypes {
         self.marker_id
     }
 
+    /// ID of the [`EntityReplicationOrder`] component.
+    pub(super) fn order_id(&self) -> ComponentId {
+        self.order_id
+    }
+
+    /// Returns cached information about a replicated archetype.
+    pub(super) fn get(&self, archetype_id: ArchetypeId) -> Option<&ReplicatedArchetype> {
+        let &index = self.indices.get(&archetype_id)?;
+        Some(&self.archetypes[index])
+    }
+
+    /// Iterates over replicated archetypes that contain at least one entity.
+    ///
+    /// Returns cached information along with the associated archetype from the world.
+    pub(super) fn iter_nonempty<'a>(
+        &'a self,
+        world: &'a World,
+    ) -> impl Iterator<Item = (&'a ReplicatedArchetype, &'a Archetype)> {
+        self.archetypes
+            .iter()
+            .map(|replicated_archetype| {
+                // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
+                let archetype = unsafe {
+                    world
+                        .archetypes()
+                        .get(replicated_archetype.id)
+                        .unwrap_unchecked()
+                };
+                (replicated_archetype, archetype)
+            })
+            .filter(|(_, archetype)| !archetype.is_empty())
+    }
+
     /// Updates the internal view of the [`World`]'s replicated archetypes.
     ///
     /// If this is not called before querying data, the results may not accurately reflect what is in the world.
@@ -46,6 +92,8 @@ impl ReplicatedArchetypes {
             .filter(|archetype| archetype.contains(self.marker_id))
         {
             let mut replicated_archetype = ReplicatedArchetype::new(archetype.id());
+            replicated_archetype.sleeping = archetype.contains(self.sleeping_id);
+            replicated_archetype.paused = archetype.contains(self.paused_id);
             for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
                 for &(component_id, fns_id) in &rule.components {
                     // Since rules are sorted by priority,
@@ -86,6 +134,8 @@ impl ReplicatedArchetypes {
                     });
                 }
             }
+            self.indices
+                .insert(replicated_archetype.id, self.archetypes.len());
             self.archetypes.push(replicated_archetype);
         }
     }
@@ -95,8 +145,12 @@ impl FromWorld for ReplicatedArchetypes {
     fn from_world(world: &mut World) -> Self {
         Self {
             marker_id: world.register_component::<Replicated>(),
+            sleeping_id: world.register_component::<ReplicationSleeping>(),
+            paused_id: world.register_component::<ReplicationPaused>(),
+            order_id: world.register_component::<EntityReplicationOrder>(),
             generation: ArchetypeGeneration::initial(),
             archetypes: Default::default(),
+            indices: Default::default(),
         }
     }
 }
@@ -108,6 +162,12 @@ pub(crate) struct ReplicatedArchetype {
 
     /// Components marked as replicated.
     pub(super) components: Vec<ReplicatedComponent>,
+
+    /// Indicates that the archetype contains [`ReplicationSleeping`].
+    pub(super) sleeping: bool,
+
+    /// Indicates that the archetype contains [`ReplicationPaused`].
+    pub(super) paused: bool,
 }
 
 impl ReplicatedArchetype {
@@ -115,13 +175,15 @@ impl ReplicatedArchetype {
         Self {
             id,
             components: Default::default(),
+            sleeping: false,
+            paused: false,
         }
     }
 }
 
 /// Stores information about a replicated component.
 pub(super) struct ReplicatedComponent {
-    component_id: ComponentId,
+    pub(super) component_id: ComponentId,
     pub(super) storage_type: StorageType,
     pub(super) fns_id: FnsId,
 }
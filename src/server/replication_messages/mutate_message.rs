@@ -1,12 +1,14 @@
-use std::{io::Cursor, mem, ops::Range, time::Duration};
+use std::{cmp::Reverse, io::Cursor, mem, ops::Range, time::Duration};
 
 use bevy::{ecs::component::Tick, prelude::*};
 use integer_encoding::{VarInt, VarIntWriter};
 
 use super::{component_changes::ComponentChanges, serialized_data::SerializedData};
 use crate::core::{
-    channels::ReplicationChannel,
-    replication::replicated_clients::{ClientBuffers, ReplicatedClient},
+    replication::{
+        replicated_clients::{ClientBuffers, MutateSendStats, ReplicatedClient},
+        ReplicationPriority,
+    },
     replicon_server::RepliconServer,
     replicon_tick::RepliconTick,
 };
@@ -25,8 +27,10 @@ use crate::core::{
 /// The data is serialized manually and stored in the form of ranges
 /// from [`SerializedData`].
 ///
-/// Sent over the [`ReplicationChannel::Mutations`] channel. If the message gets lost, we try to resend it manually,
+/// Sent over the [`ReplicationChannel::Mutations`](crate::core::channels::ReplicationChannel::Mutations) channel. If the message gets lost, we try to resend it manually,
 /// using the last up-to-date mutations to avoid re-sending old values.
+/// If multiple mutation channels are registered, entities are distributed between them by index
+/// and each message contains entities only from a single channel.
 ///
 /// Stored inside [`ReplicationMessages`](super::ReplicationMessages).
 #[derive(Default)]
@@ -37,6 +41,11 @@ pub(crate) struct MutateMessage {
     /// needs to acknowledge to consider entity mutations as received.
     entities: Vec<Entity>,
 
+    /// Priorities for [`Self::entities`].
+    ///
+    /// Used only if the bandwidth budget is set.
+    priorities: Vec<ReplicationPriority>,
+
     /// Component mutations that happened in this tick.
     ///
     /// Serialized as a list of pairs of entity chunk and multiple chunks with mutated components.
@@ -56,10 +65,15 @@ pub(crate) struct MutateMessage {
     /// Intermediate buffer to reuse allocated memory from [`Self::mutations`].
     buffer: Vec<Vec<Range<usize>>>,
 
-    /// Intermediate buffer with mutate index, message size and a range for [`Self::mutations`].
+    /// Intermediate buffer with indices of [`Self::mutations`] in the new order.
+    ///
+    /// See [`Self::reorder`].
+    order: Vec<usize>,
+
+    /// Intermediate buffer with channel ID, mutate index, message size and a range for [`Self::mutations`].
     ///
     /// We split messages first in order to know their count in advance.
-    messages: Vec<(u16, usize, Range<usize>)>,
+    messages: Vec<(u8, u16, usize, Range<usize>)>,
 }
 
 impl MutateMessage {
@@ -78,7 +92,12 @@ impl MutateMessage {
     }
 
     /// Adds an entity chunk.
-    pub(crate) fn add_mutated_entity(&mut self, entity: Entity, entity_range: Range<usize>) {
+    pub(crate) fn add_mutated_entity(
+        &mut self,
+        entity: Entity,
+        entity_range: Range<usize>,
+        priority: ReplicationPriority,
+    ) {
         let components = self.buffer.pop().unwrap_or_default();
         self.mutations.push(ComponentChanges {
             entity: entity_range,
@@ -86,6 +105,7 @@ impl MutateMessage {
             components,
         });
         self.entities.push(entity);
+        self.priorities.push(priority);
         self.mutations_written = true;
     }
 
@@ -107,6 +127,7 @@ impl MutateMessage {
     /// Removes last added entity from [`Self::add_mutated_entity`] with associated components.
     pub(super) fn pop_mutations(&mut self) {
         self.entities.pop();
+        self.priorities.pop();
         if let Some(mut mutations) = self.mutations.pop() {
             mutations.components.clear();
             self.buffer.push(mutations.components);
@@ -124,12 +145,23 @@ impl MutateMessage {
         client_buffers: &mut ClientBuffers,
         serialized: &SerializedData,
         track_mutate_messages: bool,
+        bandwidth_budget: Option<usize>,
+        mutation_channels: &[u8],
         server_tick: Range<usize>,
         tick: Tick,
         timestamp: Duration,
-    ) -> bincode::Result<usize> {
+    ) -> bincode::Result<MutateSendStats> {
         debug_assert_eq!(self.entities.len(), self.mutations.len());
 
+        let deferred_entities = if let Some(budget) = bandwidth_budget {
+            self.apply_budget(budget)
+        } else {
+            0
+        };
+        if mutation_channels.len() > 1 {
+            self.sort_by_channel(mutation_channels.len());
+        }
+
         const MAX_COUNT_SIZE: usize = mem::size_of::<usize>() + 1;
         let mut update_tick = Cursor::new([0; mem::size_of::<RepliconTick>()]);
         bincode::serialize_into(&mut update_tick, &client.update_tick())?;
@@ -143,17 +175,23 @@ impl MutateMessage {
         let mut header_size = metadata_size + mutate_index.required_space();
         let mut body_size = 0;
         let mut mutations_range = Range::<usize>::default();
+        let mut channel_id = mutation_channels[0];
         for (entity, mutations) in self.entities.iter().zip(&self.mutations) {
             let components_size = mutations.components_size();
             let mutations_size =
                 mutations.entity.len() + components_size.required_space() + components_size;
+            let entity_channel_id =
+                mutation_channels[channel_index(*entity, mutation_channels.len())];
 
             // Try to pack back first, then try to pack forward.
+            // Entities from different channels always go into separate messages.
             if body_size != 0
-                && !can_pack(header_size + body_size, mutations_size)
-                && !can_pack(header_size + mutations_size, body_size)
+                && (entity_channel_id != channel_id
+                    || (!can_pack(header_size + body_size, mutations_size)
+                        && !can_pack(header_size + mutations_size, body_size)))
             {
                 self.messages.push((
+                    channel_id,
                     mutate_index,
                     body_size + header_size,
                     mutations_range.clone(),
@@ -166,6 +204,7 @@ impl MutateMessage {
                 body_size = 0;
             }
 
+            channel_id = entity_channel_id;
             entities.push(*entity);
             mutations_range.end += 1;
             body_size += mutations_size;
@@ -174,6 +213,7 @@ impl MutateMessage {
             // When the loop ends, pack all leftovers into a message.
             // Or create an empty message if tracking mutate messages is enabled.
             self.messages.push((
+                channel_id,
                 mutate_index,
                 body_size + header_size,
                 mutations_range.clone(),
@@ -181,7 +221,14 @@ impl MutateMessage {
         }
 
         let messages_count = self.messages.len();
-        for (mutate_index, mut message_size, mutations_range) in self.messages.drain(..) {
+        let mut stats = MutateSendStats {
+            messages_count,
+            total_bytes: 0,
+            entities_count: self.entities.len(),
+            deferred_entities,
+        };
+        for (channel_id, mutate_index, mut message_size, mutations_range) in self.messages.drain(..)
+        {
             if track_mutate_messages {
                 // Update message counter size based on actual value.
                 message_size -= MAX_COUNT_SIZE - messages_count.required_space();
@@ -204,10 +251,75 @@ impl MutateMessage {
 
             debug_assert_eq!(message.len(), message_size);
 
-            server.send(client.id(), ReplicationChannel::Mutations, message);
+            stats.total_bytes += message.len();
+            server.send(client.id(), channel_id, message);
         }
 
-        Ok(messages_count)
+        Ok(stats)
+    }
+
+    /// Sorts entities by priority and removes the ones that don't fit into the budget.
+    ///
+    /// The first entity is always kept. Returns the number of removed entities.
+    fn apply_budget(&mut self, budget: usize) -> usize {
+        self.order.extend(0..self.mutations.len());
+        // Stable sort to preserve the iteration order for entities with the same priority.
+        self.order
+            .sort_by_key(|&index| Reverse(self.priorities[index]));
+        self.reorder();
+
+        let mut total_size = 0;
+        let kept_entities = self
+            .mutations
+            .iter()
+            .position(|mutations| {
+                let components_size = mutations.components_size();
+                total_size +=
+                    mutations.entity.len() + components_size.required_space() + components_size;
+                total_size > budget
+            })
+            .map_or(self.mutations.len(), |index| index.max(1));
+
+        let deferred_entities = self.mutations.len() - kept_entities;
+        self.priorities.truncate(kept_entities);
+        self.entities.truncate(kept_entities);
+        self.buffer
+            .extend(self.mutations.drain(kept_entities..).map(|mut mutations| {
+                mutations.components.clear();
+                mutations.components
+            }));
+
+        deferred_entities
+    }
+
+    /// Moves entities with their data into the order from [`Self::order`] in place.
+    ///
+    /// Clears [`Self::order`] after the call.
+    fn reorder(&mut self) {
+        // Follow each permutation cycle, marking visited positions.
+        for start in 0..self.order.len() {
+            let mut current = start;
+            while self.order[current] != start {
+                let next = self.order[current];
+                self.priorities.swap(current, next);
+                self.entities.swap(current, next);
+                self.mutations.swap(current, next);
+                self.order[current] = current;
+                current = next;
+            }
+            self.order[current] = current;
+        }
+        self.order.clear();
+    }
+
+    /// Groups entities by their mutation channel.
+    ///
+    /// Preserves the order of entities within the same channel.
+    fn sort_by_channel(&mut self, channels_count: usize) {
+        self.order.extend(0..self.entities.len());
+        self.order
+            .sort_by_key(|&index| channel_index(self.entities[index], channels_count));
+        self.reorder();
     }
 
     /// Clears all chunks.
@@ -215,6 +327,7 @@ impl MutateMessage {
     /// Keeps allocated memory for reuse.
     pub(super) fn clear(&mut self) {
         self.entities.clear();
+        self.priorities.clear();
         self.buffer
             .extend(self.mutations.drain(..).map(|mut mutations| {
                 mutations.components.clear();
@@ -223,6 +336,11 @@ impl MutateMessage {
     }
 }
 
+/// Returns index of the mutation channel for an entity.
+fn channel_index(entity: Entity, channels_count: usize) -> usize {
+    entity.index() as usize % channels_count
+}
+
 fn can_pack(message_size: usize, add: usize) -> bool {
     const MAX_PACKET_SIZE: usize = 1200; // TODO: make it configurable by the messaging backend.
 
@@ -246,4 +364,37 @@ mod tests {
         assert!(!can_pack(1200, 1));
         assert!(!can_pack(1200, 3000));
     }
+
+    #[test]
+    fn budget() {
+        let mut message = MutateMessage::default();
+        for (index, priority) in [0, 2, 1, 2, 1].into_iter().enumerate() {
+            message.start_entity_mutations();
+            message.add_mutated_entity(
+                Entity::from_raw(index as u32),
+                0..1,
+                ReplicationPriority(priority),
+            );
+            message.add_mutated_component(0..2);
+        }
+
+        // Each entity takes 4 bytes: entity, size and component.
+        assert_eq!(message.apply_budget(12), 2);
+        assert_eq!(
+            message.entities,
+            [1, 3, 2].map(Entity::from_raw),
+            "entities should be sorted by priority"
+        );
+        assert_eq!(message.priorities.len(), 3);
+        assert_eq!(message.mutations.len(), 3);
+        assert_eq!(message.buffer.len(), 2);
+        assert!(message.order.is_empty());
+
+        assert_eq!(
+            message.apply_budget(0),
+            2,
+            "first entity should be kept even if it doesn't fit"
+        );
+        assert_eq!(message.entities, [Entity::from_raw(1)]);
+    }
 }
@@ -2,12 +2,14 @@ use std::ops::Range;
 
 use bevy::{prelude::*, ptr::Ptr};
 use bincode::{DefaultOptions, Options};
+use integer_encoding::VarIntWriter;
 
 use crate::{
     core::{
         entity_serde,
         replication::replication_registry::{
-            component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns, FnsId,
+            component_fns::ComponentFns, ctx::SerializeCtx, delta_fns::DeltaValue,
+            rule_fns::UntypedRuleFns, FnsId,
         },
         replicon_tick::RepliconTick,
     },
@@ -60,12 +62,35 @@ impl SerializedData {
         ctx: &SerializeCtx,
         fns_id: FnsId,
         ptr: Ptr,
+        lod: usize,
     ) -> bincode::Result<Range<usize>> {
         let start = self.len();
 
         DefaultOptions::new().serialize_into(&mut self.0, &fns_id)?;
         // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
-        unsafe { component_fns.serialize(ctx, rule_fns, ptr, &mut self.0)? };
+        unsafe { component_fns.serialize(ctx, rule_fns, ptr, lod, &mut self.0)? };
+
+        let end = self.len();
+
+        Ok(start..end)
+    }
+
+    /// Like [`Self::write_component`], but writes a delta-encoded component as a diff from `base`
+    /// if it's smaller than the full value.
+    pub(crate) fn write_delta_component(
+        &mut self,
+        rule_fns: &UntypedRuleFns,
+        component_fns: &ComponentFns,
+        ctx: &SerializeCtx,
+        fns_id: FnsId,
+        ptr: Ptr,
+        base: Option<(RepliconTick, &DeltaValue)>,
+    ) -> bincode::Result<Range<usize>> {
+        let start = self.len();
+
+        DefaultOptions::new().serialize_into(&mut self.0, &fns_id)?;
+        // SAFETY: `component_fns`, `ptr` and `rule_fns` were created for the same component type.
+        unsafe { component_fns.write_delta(ctx, rule_fns, ptr, base, &mut self.0)? };
 
         let end = self.len();
 
@@ -88,6 +113,24 @@ impl SerializedData {
         Ok(start..end)
     }
 
+    /// Writes user-defined metadata as a list of key, size and data.
+    pub(crate) fn write_metadata<'a>(
+        &mut self,
+        metadata: impl Iterator<Item = &'a (u32, Vec<u8>)>,
+    ) -> bincode::Result<Range<usize>> {
+        let start = self.len();
+
+        for (key, data) in metadata {
+            self.0.write_varint(*key)?;
+            self.0.write_varint(data.len())?;
+            self.0.extend_from_slice(data);
+        }
+
+        let end = self.len();
+
+        Ok(start..end)
+    }
+
     pub(crate) fn write_tick(&mut self, tick: RepliconTick) -> bincode::Result<Range<usize>> {
         let start = self.len();
 
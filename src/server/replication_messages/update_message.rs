@@ -19,7 +19,7 @@ use crate::core::{
 
 /// A message with replicated data.
 ///
-/// Contains tick, mappings, insertions, removals, and despawns that
+/// Contains tick, user-defined metadata, mappings, insertions, removals, and despawns that
 /// happened in this tick.
 ///
 /// The data is serialized manually and stored in the form of ranges
@@ -41,6 +41,14 @@ use crate::core::{
 /// Stored inside [`ReplicationMessages`](super::ReplicationMessages).
 #[derive(Default)]
 pub(crate) struct UpdateMessage {
+    /// User-defined metadata for this tick.
+    ///
+    /// Serialized as a single continuous chunk of key, size and data triples.
+    /// Always prefixed with its size in bytes, so clients could skip it.
+    ///
+    /// See also [`PendingUpdateMetadata`](crate::server::PendingUpdateMetadata).
+    metadata: Range<usize>,
+
     /// Mappings for client's pre-spawned entities.
     ///
     /// Serialized as single continuous chunk of entity pairs.
@@ -95,6 +103,10 @@ pub(crate) struct UpdateMessage {
 }
 
 impl UpdateMessage {
+    pub(crate) fn set_metadata(&mut self, metadata: Range<usize>) {
+        self.metadata = metadata;
+    }
+
     pub(crate) fn set_mappings(&mut self, mappings: Range<usize>, len: usize) {
         self.mappings = mappings;
         self.mappings_len = len;
@@ -194,18 +206,27 @@ impl UpdateMessage {
 
     pub(crate) fn is_empty(&self) -> bool {
         self.changes.is_empty()
+            && self.metadata.is_empty()
             && self.despawns.is_empty()
             && self.removals.is_empty()
             && self.mappings.is_empty()
     }
 
+    /// Returns the number of entities with changes.
+    pub(crate) fn entities_count(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Sends the message and returns its size in bytes.
+    ///
+    /// Returns 0 if the message was skipped.
     pub(crate) fn send(
         &self,
         server: &mut RepliconServer,
         client: &ReplicatedClient,
         serialized: &SerializedData,
         server_tick: Range<usize>,
-    ) -> bincode::Result<()> {
+    ) -> bincode::Result<usize> {
         let flags = self.flags();
         let last_flag = flags.last();
 
@@ -213,6 +234,9 @@ impl UpdateMessage {
         let mut message_size = size_of::<UpdateMessageFlags>() + server_tick.len();
         for (_, flag) in flags.iter_names() {
             match flag {
+                UpdateMessageFlags::METADATA => {
+                    message_size += self.metadata.len().required_space() + self.metadata.len();
+                }
                 UpdateMessageFlags::MAPPINGS => {
                     if flag != last_flag {
                         message_size += self.mappings_len.required_space();
@@ -236,7 +260,9 @@ impl UpdateMessage {
                         .sum::<usize>();
                 }
                 UpdateMessageFlags::CHANGES => {
-                    debug_assert_eq!(flag, last_flag);
+                    if flag != last_flag {
+                        message_size += self.changes.len().required_space();
+                    }
                     message_size += self
                         .changes
                         .iter()
@@ -256,15 +282,20 @@ impl UpdateMessage {
         message.extend_from_slice(&serialized[server_tick]);
         for (_, flag) in flags.iter_names() {
             match flag {
+                UpdateMessageFlags::METADATA => {
+                    // Always write size to let clients skip it.
+                    message.write_varint(self.metadata.len())?;
+                    message.extend_from_slice(&serialized[self.metadata.clone()]);
+                }
                 UpdateMessageFlags::MAPPINGS => {
                     // Always write size since the message can't have only mappings.
                     // Otherwise this would mean that the client already received the mapped
                     // entity and it's already mapped or server sends an invisible entity which
                     // is an error.
-                    if flag == last_flag {
+                    if flag == flags.difference(UpdateMessageFlags::METADATA).last() {
                         error!("skipping the sending of a message with mappings but without any entity data,
                                 which could be caused by mapping invisible or non-replicatable entities for `{:?}", client.id());
-                        return Ok(());
+                        return Ok(0);
                     }
 
                     message.write_varint(self.mappings_len)?;
@@ -289,7 +320,10 @@ impl UpdateMessage {
                     }
                 }
                 UpdateMessageFlags::CHANGES => {
-                    // Changes are always last, don't write len for it.
+                    // Changes can be followed only by metadata.
+                    if flag != last_flag {
+                        message.write_varint(self.changes.len())?;
+                    }
                     for changes in &self.changes {
                         message.extend_from_slice(&serialized[changes.entity.clone()]);
                         message.write_varint(changes.components_len)?;
@@ -306,12 +340,15 @@ impl UpdateMessage {
 
         server.send(client.id(), ReplicationChannel::Updates, message);
 
-        Ok(())
+        Ok(message_size)
     }
 
     fn flags(&self) -> UpdateMessageFlags {
         let mut flags = UpdateMessageFlags::default();
 
+        if !self.metadata.is_empty() {
+            flags |= UpdateMessageFlags::METADATA;
+        }
         if !self.mappings.is_empty() {
             flags |= UpdateMessageFlags::MAPPINGS;
         }
@@ -332,6 +369,7 @@ impl UpdateMessage {
     ///
     /// Keeps allocated memory for reuse.
     pub(super) fn clear(&mut self) {
+        self.metadata = Default::default();
         self.mappings = Default::default();
         self.mappings_len = 0;
         self.despawns.clear();
@@ -1,7 +1,11 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
+use bytes::Bytes;
 
 use crate::{
     core::{
+        channels::{ChannelKind, RepliconChannel, RepliconChannels},
         replication::replicated_clients::ReplicatedClients,
         replicon_client::{RepliconClient, RepliconClientStatus},
         replicon_server::RepliconServer,
@@ -80,10 +84,17 @@ pub trait ServerTestAppExt {
     ///
     /// Internally updates [`self`] before sending and updates the client app after receiving.
     ///
+    /// If [`NetworkConditions`] resource is present in [`self`], messages will be delayed and dropped accordingly.
+    ///
     /// # Panics
     ///
     /// Panics if a client app hasn't been connected before.
     fn exchange_with_client(&mut self, client_app: &mut App);
+
+    /// Simulates network conditions for [`Self::exchange_with_client`] by inserting [`NetworkConditions`] into [`self`].
+    ///
+    /// See the resource documentation for details.
+    fn with_network_conditions(&mut self, latency_ms: u64, loss_rate: f32) -> &mut Self;
 }
 
 impl ServerTestAppExt for App {
@@ -103,6 +114,7 @@ impl ServerTestAppExt for App {
             .max()
             .unwrap_or(ClientId::SERVER);
         let client_id = ClientId::new(max_id.get() + 1);
+        client.set_status(RepliconClientStatus::Connecting);
         client.set_status(RepliconClientStatus::Connected {
             client_id: Some(client_id),
         });
@@ -135,6 +147,14 @@ impl ServerTestAppExt for App {
     }
 
     fn exchange_with_client(&mut self, client_app: &mut App) {
+        if self.world().contains_resource::<NetworkConditions>() {
+            self.world_mut()
+                .resource_scope(|world, mut conditions: Mut<NetworkConditions>| {
+                    conditions.exchange(world, client_app)
+                });
+            return;
+        }
+
         let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
         let client_id = client
             .id()
@@ -154,4 +174,157 @@ impl ServerTestAppExt for App {
             }
         })
     }
+
+    fn with_network_conditions(&mut self, latency_ms: u64, loss_rate: f32) -> &mut Self {
+        self.insert_resource(NetworkConditions::new(
+            Duration::from_millis(latency_ms),
+            loss_rate,
+        ))
+    }
+}
+
+/// Simulated network conditions for [`ServerTestAppExt::exchange_with_client`].
+///
+/// Insert into the server app via [`ServerTestAppExt::with_network_conditions`]
+/// and modify at any time to change the conditions for subsequent exchanges.
+///
+/// Messages are held until the latency passes and delivered only during an exchange.
+/// Time is measured with [`Time<Real>`] of the server app, so it advances only on its updates.
+/// To control it from tests, insert [`TimeUpdateStrategy::ManualDuration`](bevy::time::TimeUpdateStrategy::ManualDuration).
+/// Loss affects only messages on [`ChannelKind::Unreliable`] channels,
+/// since reliable channels are retransmitted by messaging backends.
+/// Drops are pseudo-random, but deterministic between runs.
+#[derive(Resource)]
+pub struct NetworkConditions {
+    /// Delay before a message is delivered.
+    pub latency: Duration,
+
+    /// Probability of dropping a message from `0.0` to `1.0`.
+    pub loss_rate: f32,
+
+    /// Messages sent from clients to the server.
+    to_server: Vec<DelayedMessage>,
+
+    /// Messages sent from the server to clients.
+    to_clients: Vec<DelayedMessage>,
+
+    /// State for the pseudo-random number generator.
+    rng_state: u64,
+}
+
+impl NetworkConditions {
+    /// Creates conditions without buffered messages.
+    pub fn new(latency: Duration, loss_rate: f32) -> Self {
+        Self {
+            latency,
+            loss_rate,
+            to_server: Default::default(),
+            to_clients: Default::default(),
+            rng_state: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    fn exchange(&mut self, world: &mut World, client_app: &mut App) {
+        let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+        let client_id = client
+            .id()
+            .expect("client should have an assigned ID for exchanging messages");
+
+        let channels = world.resource::<RepliconChannels>();
+        let client_unreliable = unreliable_channels(channels.client_channels());
+        let server_unreliable = unreliable_channels(channels.server_channels());
+
+        let now = world
+            .get_resource::<Time<Real>>()
+            .map(Time::elapsed)
+            .unwrap_or_default();
+        for (channel_id, message) in client.drain_sent() {
+            self.push(
+                now,
+                client_unreliable[channel_id as usize],
+                (client_id, channel_id, message),
+                false,
+            );
+        }
+
+        let mut server = world.resource_mut::<RepliconServer>();
+        server.retain_sent(|(sender_id, channel_id, message)| {
+            if *sender_id == client_id {
+                self.push(
+                    now,
+                    server_unreliable[*channel_id as usize],
+                    (client_id, *channel_id, message.clone()),
+                    true,
+                );
+                false
+            } else {
+                true
+            }
+        });
+
+        self.to_server.retain(|delayed| {
+            if delayed.client_id == client_id && delayed.delivery_time <= now {
+                server.insert_received(client_id, delayed.channel_id, delayed.message.clone());
+                false
+            } else {
+                true
+            }
+        });
+
+        self.to_clients.retain(|delayed| {
+            if delayed.client_id == client_id && delayed.delivery_time <= now {
+                client.insert_received(delayed.channel_id, delayed.message.clone());
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn push(
+        &mut self,
+        now: Duration,
+        unreliable: bool,
+        (client_id, channel_id, message): (ClientId, u8, Bytes),
+        to_client: bool,
+    ) {
+        if unreliable && self.roll() < self.loss_rate {
+            return;
+        }
+
+        let delayed = DelayedMessage {
+            delivery_time: now + self.latency,
+            client_id,
+            channel_id,
+            message,
+        };
+        if to_client {
+            self.to_clients.push(delayed);
+        } else {
+            self.to_server.push(delayed);
+        }
+    }
+
+    /// Returns a pseudo-random number in range `[0.0, 1.0)` using xorshift.
+    fn roll(&mut self) -> f32 {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        (self.rng_state >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn unreliable_channels(channels: &[RepliconChannel]) -> Vec<bool> {
+    channels
+        .iter()
+        .map(|channel| channel.kind == ChannelKind::Unreliable)
+        .collect()
+}
+
+struct DelayedMessage {
+    /// Value of [`Time<Real>`] elapsed time after which the message will be delivered.
+    delivery_time: Duration,
+    client_id: ClientId,
+    channel_id: u8,
+    message: Bytes,
 }
@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use bevy::{
     ecs::{entity::MapEntities, event::Events},
     prelude::*,
-    time::TimePlugin,
+    time::{TimePlugin, TimeUpdateStrategy},
 };
 use bevy_replicon::{
     core::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
@@ -125,6 +127,96 @@ fn local_resending() {
     assert_eq!(client_events.len(), 1);
 }
 
+#[test]
+fn rate_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event_with_rate_limit::<DummyEvent>(ChannelKind::Ordered, 2.0)
+            .finish();
+    }
+    server_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .send_event_batch([DummyEvent, DummyEvent, DummyEvent]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(
+        client_events.len(),
+        2,
+        "events above the limit should be dropped"
+    );
+
+    server_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        250,
+    )));
+    server_app.update();
+    server_app
+        .world_mut()
+        .resource_mut::<Events<FromClient<DummyEvent>>>()
+        .clear();
+
+    client_app
+        .world_mut()
+        .send_event_batch([DummyEvent, DummyEvent]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(
+        client_events.len(),
+        1,
+        "only tokens restored over 2 updates should be available"
+    );
+}
+
+#[test]
+fn default_rate_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                default_event_rate_limit: Some(1.0),
+                ..Default::default()
+            }),
+        ))
+        .add_client_event::<DummyEvent>(ChannelKind::Ordered)
+        .finish();
+    }
+    server_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .send_event_batch([DummyEvent, DummyEvent]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 1);
+}
+
 #[derive(Deserialize, Event, Serialize)]
 struct DummyEvent;
 
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{
+        replication::replication_registry::{
+            rule_fns::{RuleFns, MAX_DECOMPRESSED_SIZE},
+            test_fns::TestFnsEntityExt,
+            ReplicationRegistry,
+        },
+        replicon_tick::RepliconTick,
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use integer_encoding::VarIntWriter;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn zstd() {
+    let uncompressed = replicate_with(RuleFns::default);
+    let compressed = replicate_with(|| RuleFns::default().with_compression(3));
+    assert!(
+        compressed < uncompressed,
+        "compressed size {compressed} should be less than {uncompressed}"
+    );
+}
+
+#[test]
+fn lz4() {
+    let uncompressed = replicate_with(RuleFns::default);
+    let compressed = replicate_with(|| RuleFns::default().with_lz4());
+    assert!(
+        compressed < uncompressed,
+        "compressed size {compressed} should be less than {uncompressed}"
+    );
+}
+
+#[test]
+#[should_panic(expected = "exceeds")]
+fn oversized() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<VecComponent>::default().with_lz4())
+            });
+
+    let mut data = Vec::new();
+    data.write_varint(MAX_DECOMPRESSED_SIZE + 1).unwrap();
+    data.write_varint(0usize).unwrap();
+
+    app.world_mut()
+        .spawn_empty()
+        .apply_write(&data, fns_id, RepliconTick::default());
+}
+
+/// Replicates insertion and mutation of [`VecComponent`] with functions returned by `rule_fns`.
+///
+/// Returns the total size of sent replication messages.
+fn replicate_with(rule_fns: impl Fn() -> RuleFns<VecComponent>) -> usize {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with(rule_fns());
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, VecComponent(vec![1; 1000])))
+        .id();
+
+    let mut sent_bytes = 0;
+    server_app.update();
+    sent_bytes += exchange_counting(&mut server_app, &mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&VecComponent>();
+    let component = components.single(client_app.world());
+    assert_eq!(component.0, [1; 1000]);
+
+    server_app
+        .world_mut()
+        .get_mut::<VecComponent>(server_entity)
+        .unwrap()
+        .0[500..]
+        .fill(2);
+
+    server_app.update();
+    sent_bytes += exchange_counting(&mut server_app, &mut client_app);
+    client_app.update();
+
+    let component = components.single(client_app.world());
+    assert_eq!(component.0[..500], [1; 500]);
+    assert_eq!(component.0[500..], [2; 500]);
+
+    sent_bytes
+}
+
+/// Like [`ServerTestAppExt::exchange_with_client`], but returns the size of messages sent by the server.
+fn exchange_counting(server_app: &mut App, client_app: &mut App) -> usize {
+    let mut sent_bytes = 0;
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in server.drain_sent() {
+        sent_bytes += message.len();
+        client.insert_received(channel_id, message);
+    }
+
+    sent_bytes
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct VecComponent(Vec<u8>);
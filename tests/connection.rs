@@ -1,8 +1,15 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
 use bevy_replicon::{
-    core::channels::ReplicationChannel, prelude::*, server::server_tick::ServerTick,
+    client::{ServerUpdateMetadata, ServerUpdateTick},
+    core::{
+        channels::ReplicationChannel, replicon_client::DrainStats, replicon_tick::RepliconTick,
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    server::{server_tick::ServerTick, PendingUpdateMetadata},
     test_app::ServerTestAppExt,
 };
+use integer_encoding::VarIntWriter;
 
 #[test]
 fn client_to_server() {
@@ -17,6 +24,7 @@ fn client_to_server() {
     const CLIENT_ID: ClientId = ClientId::new(0);
 
     let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connecting);
     client.set_status(RepliconClientStatus::Connected {
         client_id: Some(CLIENT_ID),
     });
@@ -38,6 +46,78 @@ fn client_to_server() {
     assert_eq!(messages, MESSAGES);
 }
 
+#[test]
+fn client_drain_stats() {
+    let mut client_app = App::new();
+    client_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    assert_eq!(client.last_drain_stats(), None);
+
+    client.set_status(RepliconClientStatus::Connecting);
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+    client.send(ReplicationChannel::Updates, [0; 3].as_slice());
+    client.send(ReplicationChannel::Mutations, [0; 2].as_slice());
+    assert_eq!(client.drain_sent().count(), 2);
+    assert_eq!(
+        client.last_drain_stats(),
+        Some(DrainStats {
+            total_bytes: 5,
+            message_count: 2,
+        })
+    );
+
+    assert_eq!(client.drain_sent().count(), 0);
+    assert_eq!(client.last_drain_stats(), Some(DrainStats::default()));
+
+    client.set_status(RepliconClientStatus::Disconnected);
+    assert_eq!(client.last_drain_stats(), None);
+}
+
+#[test]
+fn client_without_core() {
+    let mut client_app = App::new();
+    client_app.add_plugins((MinimalPlugins, ClientPlugin::default()));
+    client_app.finish();
+}
+
+#[test]
+fn client_connecting_twice() {
+    let mut client_app = App::new();
+    client_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connecting);
+    client.set_status(RepliconClientStatus::Connecting);
+    assert!(client.is_connecting());
+}
+
+#[test]
+#[should_panic(expected = "can't be changed")]
+fn client_connected_without_connecting() {
+    let mut client_app = App::new();
+    client_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+}
+
+#[test]
+#[should_panic(expected = "can't be changed")]
+fn client_connected_twice() {
+    let mut client_app = App::new();
+    client_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connecting);
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+}
+
 #[test]
 fn server_to_client() {
     let mut server_app = App::new();
@@ -57,6 +137,7 @@ fn server_to_client() {
     }
 
     let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connecting);
     client.set_status(RepliconClientStatus::Connected {
         client_id: Some(CLIENT_ID),
     });
@@ -94,6 +175,157 @@ fn connect_disconnect() {
     assert!(replicated_clients.is_empty());
 }
 
+#[test]
+fn kick_with_reason() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .kick_with_reason(client_id, "cheating");
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let disconnect_requests: Vec<_> = server.drain_disconnect_requests().collect();
+    assert_eq!(disconnect_requests, [client_id]);
+
+    let reasons: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Events<KickReason>>()
+        .drain()
+        .map(|reason| reason.0)
+        .collect();
+    assert_eq!(reasons, ["cheating"]);
+}
+
+#[test]
+fn update_metadata() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .resource_mut::<PendingUpdateMetadata>()
+        .extend([(0, vec![1, 2]), (5, Vec::new())]);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app
+        .world()
+        .resource::<PendingUpdateMetadata>()
+        .is_empty());
+
+    let metadata = client_app.world().resource::<ServerUpdateMetadata>();
+    assert_eq!(metadata.len(), 2);
+    assert_eq!(&metadata[&0][..], [1, 2]);
+    assert!(metadata[&5].is_empty());
+
+    let server_tick = *server_app.world().resource::<ServerTick>();
+    let update_tick = *client_app.world().resource::<ServerUpdateTick>();
+    assert_eq!(*server_tick, *update_tick);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let metadata = client_app.world().resource::<ServerUpdateMetadata>();
+    assert!(metadata.is_empty(), "metadata should be cleared each tick");
+}
+
+#[test]
+fn update_metadata_with_changes() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+    server_app
+        .world_mut()
+        .resource_mut::<PendingUpdateMetadata>()
+        .push((1, vec![3]));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let metadata = client_app.world().resource::<ServerUpdateMetadata>();
+    assert_eq!(&metadata[&1][..], [3]);
+
+    let mut replicated = client_app
+        .world_mut()
+        .query_filtered::<(), With<Replicated>>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "UnexpectedEof")]
+fn update_metadata_overflow() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Metadata flag, tick and the maximum size.
+    let mut message = vec![0b00010000];
+    bincode::serialize_into(&mut message, &RepliconTick::new(1)).unwrap();
+    message.write_varint(usize::MAX).unwrap();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, message);
+
+    client_app.update();
+}
+
 #[test]
 fn client_cleanup_on_disconnect() {
     let mut app = App::new();
@@ -108,6 +340,7 @@ fn client_cleanup_on_disconnect() {
     app.update();
 
     let mut client = app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connecting);
     client.set_status(RepliconClientStatus::Connected { client_id: None });
 
     client.send(ReplicationChannel::Updates, Vec::new());
@@ -121,6 +354,56 @@ fn client_cleanup_on_disconnect() {
     app.update();
 }
 
+#[test]
+fn preserve_state_on_disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    preserve_state_on_disconnect: true,
+                    ..Default::default()
+                }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app.disconnect_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().len(),
+        1,
+        "state should be preserved after disconnect"
+    );
+    let update_tick = **client_app.world().resource::<ServerUpdateTick>();
+    assert_ne!(update_tick, RepliconTick::default());
+
+    client_app
+        .world_mut()
+        .run_system_once(ClientPlugin::reset_manually())
+        .unwrap();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+    let update_tick = **client_app.world().resource::<ServerUpdateTick>();
+    assert_eq!(update_tick, RepliconTick::default());
+}
+
 #[test]
 fn server_cleanup_on_stop() {
     let mut app = App::new();
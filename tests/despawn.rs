@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 use bevy_replicon::{
-    core::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+    core::server_entity_map::ServerEntityMap,
+    prelude::*,
+    server::{despawn_buffer::DespawnBuffer, server_tick::ServerTick},
+    test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
 
@@ -120,6 +123,52 @@ fn after_spawn() {
     assert!(replicated.iter(client_app.world()).next().is_none());
 }
 
+#[test]
+fn after_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Mutate and despawn in the same tick.
+    server_app
+        .world_mut()
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert!(replicated.iter(client_app.world()).next().is_none());
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+}
+
 #[test]
 fn hidden() {
     let mut server_app = App::new();
@@ -158,5 +207,58 @@ fn hidden() {
     );
 }
 
+#[test]
+fn scheduled() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = *entity_map
+        .to_client()
+        .get(&server_entity)
+        .expect("entity should be replicated");
+
+    let despawn_tick = **server_app.world().resource::<ServerTick>() + 2;
+    server_app
+        .world_mut()
+        .resource_mut::<DespawnBuffer>()
+        .schedule_despawn_at(server_entity, despawn_tick);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app.world().get_entity(server_entity).is_ok());
+    assert!(client_app.world().get_entity(client_entity).is_ok());
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(server_app.world().get_entity(server_entity).is_err());
+    assert!(
+        client_app.world().get_entity(client_entity).is_err(),
+        "entity should be despawned on the scheduled tick"
+    );
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
@@ -4,20 +4,23 @@ use bevy::prelude::*;
 use bevy_replicon::{
     core::{
         replication::{
-            command_markers::MarkerConfig,
+            command_markers::{MarkerConfig, TiebreakerPolicy},
             deferred_entity::DeferredEntity,
             replication_registry::{
                 command_fns,
-                ctx::{DespawnCtx, WriteCtx},
-                rule_fns::RuleFns,
+                ctx::{DespawnCtx, SerializeCtx, WriteCtx},
+                quantize::{self, Vec3Quantizer},
+                rule_fns::{self, RuleFns},
                 test_fns::TestFnsEntityExt,
                 ReplicationRegistry,
             },
         },
         replicon_tick::RepliconTick,
+        server_entity_map::ServerEntityMap,
     },
     prelude::*,
 };
+use bincode::{DefaultOptions, Options};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -205,8 +208,8 @@ fn remove_with_marker() {
 fn write_with_multiple_markers() {
     let mut app = App::new();
     app.add_plugins((MinimalPlugins, RepliconPlugins))
-        .register_marker::<DummyMarker>()
         .register_marker::<ReplaceMarker>()
+        .register_marker::<DummyMarker>()
         .set_marker_fns::<ReplaceMarker, _>(
             replace,
             command_fns::default_remove::<ReplacedComponent>,
@@ -230,7 +233,7 @@ fn write_with_multiple_markers() {
     entity.apply_write(&data, fns_id, tick);
     assert!(
         entity.contains::<ReplacedComponent>(),
-        "last marker should take priority"
+        "first marker should take priority"
     );
 }
 
@@ -238,8 +241,8 @@ fn write_with_multiple_markers() {
 fn remove_with_mutltiple_markers() {
     let mut app = App::new();
     app.add_plugins((MinimalPlugins, RepliconPlugins))
-        .register_marker::<DummyMarker>()
         .register_marker::<ReplaceMarker>()
+        .register_marker::<DummyMarker>()
         .set_marker_fns::<ReplaceMarker, _>(
             replace,
             command_fns::default_remove::<ReplacedComponent>,
@@ -262,7 +265,7 @@ fn remove_with_mutltiple_markers() {
     entity.apply_remove(fns_id, tick);
     assert!(
         !entity.contains::<ReplacedComponent>(),
-        "last marker should take priority"
+        "first marker should take priority"
     );
 }
 
@@ -331,6 +334,320 @@ fn remove_with_priority_marker() {
     assert!(!entity.contains::<ReplacedComponent>());
 }
 
+#[test]
+fn write_with_last_tiebreaker_marker() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_marker::<DummyMarker>()
+        .register_marker_with::<ReplaceMarker>(MarkerConfig {
+            tiebreaker: TiebreakerPolicy::Last,
+            ..Default::default()
+        })
+        .set_marker_fns::<ReplaceMarker, _>(
+            replace,
+            command_fns::default_remove::<ReplacedComponent>,
+        )
+        .set_marker_fns::<DummyMarker, _>(
+            command_fns::default_write::<OriginalComponent>,
+            command_fns::default_remove::<OriginalComponent>,
+        );
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<OriginalComponent>::default())
+            });
+
+    let mut entity = app
+        .world_mut()
+        .spawn((OriginalComponent, ReplaceMarker, DummyMarker));
+    let data = entity.serialize(fns_id, tick);
+    entity.apply_write(&data, fns_id, tick);
+    assert!(
+        entity.contains::<ReplacedComponent>(),
+        "last marker should take priority"
+    );
+}
+
+#[test]
+fn remove_with_last_tiebreaker_marker() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_marker::<DummyMarker>()
+        .register_marker_with::<ReplaceMarker>(MarkerConfig {
+            tiebreaker: TiebreakerPolicy::Last,
+            ..Default::default()
+        })
+        .set_marker_fns::<ReplaceMarker, _>(
+            replace,
+            command_fns::default_remove::<ReplacedComponent>,
+        )
+        .set_marker_fns::<DummyMarker, _>(
+            command_fns::default_write::<OriginalComponent>,
+            command_fns::default_remove::<OriginalComponent>,
+        );
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<OriginalComponent>::default())
+            });
+
+    let mut entity = app
+        .world_mut()
+        .spawn((ReplacedComponent, ReplaceMarker, DummyMarker));
+    entity.apply_remove(fns_id, tick);
+    assert!(
+        !entity.contains::<ReplacedComponent>(),
+        "last marker should take priority"
+    );
+}
+
+#[test]
+fn write_with_schema_version() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate_with_schema_version(
+            2,
+            serialize_versioned,
+            deserialize_legacy,
+            deserialize_versioned,
+        );
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(
+                    world,
+                    RuleFns::new(serialize_versioned, deserialize_versioned),
+                )
+            });
+
+    let registry = app.world().resource::<ReplicationRegistry>();
+    assert_eq!(registry.component_schema_version(fns_id), 2);
+
+    let mut entity = app.world_mut().spawn(VersionedComponent(5));
+    let data = entity.serialize(fns_id, tick);
+    entity.remove::<VersionedComponent>();
+    entity.apply_write(&data, fns_id, tick);
+    let component = entity.get::<VersionedComponent>().unwrap();
+    assert_eq!(component.0, 5);
+}
+
+#[test]
+fn write_with_legacy_schema_version() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate_with_schema_version(
+            2,
+            serialize_versioned,
+            deserialize_legacy,
+            deserialize_versioned,
+        );
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(
+                    world,
+                    RuleFns::new(serialize_versioned, deserialize_versioned),
+                )
+            });
+
+    // Version 1 followed by the value in the legacy format.
+    let data = [1, 50];
+    let mut entity = app.world_mut().spawn_empty();
+    entity.apply_write(&data, fns_id, tick);
+    let component = entity.get::<VersionedComponent>().unwrap();
+    assert_eq!(component.0, 5);
+}
+
+#[test]
+fn unversioned_schema() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<OriginalComponent>::default())
+            });
+
+    let registry = app.world().resource::<ReplicationRegistry>();
+    assert_eq!(registry.component_schema_version(fns_id), 0);
+}
+
+#[test]
+fn write_quantized() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(
+                    world,
+                    RuleFns::new(
+                        quantize::serialize_quantized::<Transform, Vec3Quantizer<-100, 100, 16>>,
+                        quantize::deserialize_quantized::<Transform, Vec3Quantizer<-100, 100, 16>>,
+                    ),
+                )
+            });
+
+    let transform = Transform::from_xyz(1.5, -20.25, 200.0).with_scale(Vec3::splat(2.0));
+    let mut entity = app.world_mut().spawn(transform);
+    let data = entity.serialize(fns_id, tick);
+    entity.remove::<Transform>();
+    entity.apply_write(&data, fns_id, tick);
+    let restored = *entity.get::<Transform>().unwrap();
+    assert!(restored
+        .translation
+        .abs_diff_eq(Vec3::new(1.5, -20.25, 100.0), 0.002));
+    assert_eq!(restored.rotation, transform.rotation);
+    assert_eq!(restored.scale, transform.scale);
+}
+
+#[test]
+fn write_with_validation() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(
+                    world,
+                    RuleFns::<Health>::default().with_validate(validate_health),
+                )
+            });
+
+    let mut entity = app.world_mut().spawn(Health(-1));
+    let invalid_data = entity.serialize(fns_id, tick);
+    entity.remove::<Health>();
+    entity.apply_write(&invalid_data, fns_id, tick);
+    assert!(!entity.contains::<Health>());
+
+    entity.insert(Health(5));
+    let valid_data = entity.serialize(fns_id, tick);
+    entity.remove::<Health>();
+    entity.apply_write(&valid_data, fns_id, tick);
+    assert_eq!(entity.get::<Health>().unwrap().0, 5);
+
+    entity.apply_write(&invalid_data, fns_id, tick);
+    assert_eq!(
+        entity.get::<Health>().unwrap().0,
+        5,
+        "invalid value shouldn't overwrite the existing one"
+    );
+}
+
+#[test]
+fn write_with_size_limit() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .replicate_with_size_limit(
+            4,
+            RuleFns::<Samples>::default(),
+            RuleFns::new(serialize_first_sample, deserialize_first_sample),
+        );
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<Samples>::default())
+            });
+
+    let mut entity = app.world_mut().spawn(Samples(vec![1, 2]));
+    let data = entity.serialize(fns_id, tick);
+    assert_eq!(
+        data[0], 0,
+        "data within the limit should use primary functions"
+    );
+    entity.remove::<Samples>();
+    entity.apply_write(&data, fns_id, tick);
+    assert_eq!(entity.get::<Samples>().unwrap().0, [1, 2]);
+
+    entity.insert(Samples(vec![1, 2, 3, 4, 5]));
+    let data = entity.serialize(fns_id, tick);
+    assert_eq!(
+        data[0], 1,
+        "data over the limit should use fallback functions"
+    );
+    entity.remove::<Samples>();
+    entity.apply_write(&data, fns_id, tick);
+    assert_eq!(entity.get::<Samples>().unwrap().0, [1]);
+}
+
+#[test]
+fn write_with_spawned_entity() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(
+                    world,
+                    RuleFns::new(serialize_weapon_slot, deserialize_weapon_slot),
+                )
+            });
+
+    let server_ammo = app.world_mut().spawn_empty().id();
+    let mut entity = app.world_mut().spawn(WeaponSlot(server_ammo));
+    let data = entity.serialize(fns_id, tick);
+    entity.remove::<WeaponSlot>();
+    entity.apply_write(&data, fns_id, tick);
+    let client_ammo = entity.get::<WeaponSlot>().unwrap().0;
+    assert_ne!(client_ammo, server_ammo);
+
+    let world = app.world();
+    assert!(world.get::<Replicated>(client_ammo).is_some());
+
+    let entity_map = world.resource::<ServerEntityMap>();
+    assert_eq!(entity_map.to_client().get(&server_ammo), Some(&client_ammo));
+}
+
+#[test]
+fn write_with_debug() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let tick = RepliconTick::default();
+    let (_, fns_id) =
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(
+                    world,
+                    RuleFns::<Health>::new_debug(
+                        rule_fns::default_serialize,
+                        rule_fns::default_deserialize,
+                    ),
+                )
+            });
+
+    let mut entity = app.world_mut().spawn(Health(10));
+    let data = entity.serialize(fns_id, tick);
+    entity.remove::<Health>();
+    entity.apply_write(&data, fns_id, tick);
+    assert_eq!(entity.get::<Health>().unwrap().0, 10);
+
+    entity.insert(Health(5));
+    entity.apply_write(&data, fns_id, tick);
+    assert_eq!(
+        entity.get::<Health>().unwrap().0,
+        10,
+        "should also write in place"
+    );
+}
+
 #[test]
 fn despawn() {
     let mut app = App::new();
@@ -355,6 +672,19 @@ struct ReplacedComponent;
 #[derive(Component)]
 struct Despawned;
 
+#[derive(Component)]
+struct VersionedComponent(u8);
+
+#[derive(Component, Debug, Deserialize, Serialize)]
+struct Health(i32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct Samples(Vec<u8>);
+
+/// Stores an entity with ammo data.
+#[derive(Component)]
+struct WeaponSlot(Entity);
+
 #[derive(Component, Deserialize, Serialize)]
 struct ReplaceMarker;
 
@@ -374,7 +704,74 @@ fn replace(
     Ok(())
 }
 
+fn serialize_versioned(
+    _ctx: &SerializeCtx,
+    component: &VersionedComponent,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(message, &component.0)
+}
+
+fn deserialize_versioned(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<VersionedComponent> {
+    let value = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(VersionedComponent(value))
+}
+
+/// Deserializes [`VersionedComponent`] from the previous schema, which stored the value multiplied by 10.
+fn deserialize_legacy(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<VersionedComponent> {
+    let value: u8 = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(VersionedComponent(value / 10))
+}
+
+/// Serializes only the first value from [`Samples`].
+fn serialize_first_sample(
+    _ctx: &SerializeCtx,
+    samples: &Samples,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(message, &samples.0.first())
+}
+
+fn deserialize_first_sample(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<Samples> {
+    let sample: Option<u8> = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(Samples(sample.into_iter().collect()))
+}
+
+fn serialize_weapon_slot(
+    _ctx: &SerializeCtx,
+    slot: &WeaponSlot,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(message, &slot.0)
+}
+
+/// Spawns a client entity for the ammo entity from [`WeaponSlot`].
+fn deserialize_weapon_slot(
+    ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<WeaponSlot> {
+    let server_ammo = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(WeaponSlot(ctx.spawn_mapped_entity(server_ammo)))
+}
+
 /// Adds special [`Despawned`] marker instead of despawning an entity.
 fn mark_despawned(_ctx: &DespawnCtx, mut entity: EntityWorldMut) {
     entity.insert(Despawned);
 }
+
+/// Rejects negative [`Health`].
+fn validate_health(health: &Health) -> Result<(), String> {
+    if health.0 < 0 {
+        return Err(format!("health can't be negative, got {}", health.0));
+    }
+    Ok(())
+}
@@ -2,11 +2,16 @@ use std::io::Cursor;
 
 use bevy::{ecs::entity::MapEntities, prelude::*};
 use bevy_replicon::{
+    bundles::TransformReplicationBundle,
     client::confirm_history::{ConfirmHistory, EntityReplicated},
     core::{
         replication::{
             deferred_entity::DeferredEntity,
-            replication_registry::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+            replication_registry::{
+                command_fns,
+                ctx::{SerializeCtx, WriteCtx},
+                rule_fns::RuleFns,
+            },
         },
         server_entity_map::ServerEntityMap,
     },
@@ -14,6 +19,7 @@ use bevy_replicon::{
     server::server_tick::ServerTick,
     test_app::ServerTestAppExt,
 };
+use bincode::{DefaultOptions, Options};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -94,6 +100,43 @@ fn sparse_set_storage() {
         .single(client_app.world());
 }
 
+#[test]
+fn transform_bundle() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_group::<TransformReplicationBundle>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let transforms = [
+        Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(Quat::from_rotation_y(1.0)),
+        Transform::from_scale(Vec3::splat(2.0)),
+    ];
+    for transform in transforms {
+        server_app
+            .world_mut()
+            .spawn(TransformReplicationBundle::from(transform));
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Transform>();
+    let mut client_transforms: Vec<_> = replicated.iter(client_app.world()).copied().collect();
+    client_transforms.sort_by(|a, b| a.scale.x.total_cmp(&b.scale.x));
+    assert_eq!(client_transforms, transforms);
+}
+
 #[test]
 fn mapped_existing_entity() {
     let mut server_app = App::new();
@@ -326,6 +369,47 @@ fn group() {
         .single(client_app.world());
 }
 
+#[test]
+fn batched() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<GroupComponentA>()
+        .replicate::<GroupComponentB>();
+    }
+
+    client_app.add_observer(
+        |trigger: Trigger<OnAdd, GroupComponentA>, components: Query<&GroupComponentB>| {
+            assert!(
+                components.get(trigger.entity()).is_ok(),
+                "all components should be inserted at once"
+            );
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, GroupComponentA, GroupComponentB));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app
+        .world_mut()
+        .query::<(&GroupComponentA, &GroupComponentB)>()
+        .single(client_app.world());
+}
+
 #[test]
 fn not_replicated() {
     let mut server_app = App::new();
@@ -548,6 +632,7 @@ fn confirm_history() {
         .single(client_app.world());
     assert!(confirm_history.contains(tick));
 
+    let component_id = client_app.world().component_id::<DummyComponent>().unwrap();
     let mut replicated_events = client_app
         .world_mut()
         .resource_mut::<Events<EntityReplicated>>();
@@ -558,6 +643,79 @@ fn confirm_history() {
         .unwrap();
     assert_eq!(event.entity, client_entity);
     assert_eq!(event.tick, tick);
+    assert_eq!(*event.changed_components, [component_id]);
+}
+
+#[test]
+fn lod() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with_lod(vec![
+            (0.0, RuleFns::<PreciseComponent>::default()),
+            (10.0, RuleFns::new(serialize_rounded, deserialize_rounded)),
+        ]);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let camera = server_app.world_mut().spawn(Transform::default()).id();
+    server_app
+        .world_mut()
+        .resource_mut::<ReplicatedClients>()
+        .client_mut(client_id)
+        .set_camera_entity(Some(camera));
+
+    let near_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            PreciseComponent(0.4),
+            Transform::from_xyz(1.0, 0.0, 0.0),
+        ))
+        .id();
+    let far_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            PreciseComponent(0.4),
+            Transform::from_xyz(100.0, 0.0, 0.0),
+        ))
+        .id();
+    let without_transform = server_app
+        .world_mut()
+        .spawn((Replicated, PreciseComponent(0.4)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    for (server_entity, expected) in [
+        (near_entity, 0.4),
+        (far_entity, 0.0),
+        (without_transform, 0.0),
+    ] {
+        let client_entity = *entity_map.to_client().get(&server_entity).unwrap();
+        let component = client_app
+            .world()
+            .get::<PreciseComponent>(client_entity)
+            .unwrap();
+        assert_eq!(component.0, expected);
+    }
 }
 
 #[derive(Component, Deserialize, Serialize)]
@@ -572,6 +730,9 @@ impl MapEntities for MappedComponent {
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
 
+#[derive(Component, Deserialize, Serialize)]
+struct PreciseComponent(f32);
+
 #[derive(Component, Deserialize, Serialize)]
 #[component(storage = "SparseSet")]
 struct SparseSetComponent;
@@ -599,7 +760,24 @@ fn replace(
     cursor: &mut Cursor<&[u8]>,
 ) -> bincode::Result<()> {
     rule_fns.deserialize(ctx, cursor)?;
-    ctx.commands.entity(entity.id()).insert(ReplacedComponent);
+    entity.insert(ReplacedComponent);
 
     Ok(())
 }
+
+/// Serializes [`PreciseComponent`] rounded to an integer.
+fn serialize_rounded(
+    _ctx: &SerializeCtx,
+    component: &PreciseComponent,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(message, &(component.0.round() as i32))
+}
+
+fn deserialize_rounded(
+    _ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<PreciseComponent> {
+    let value: i32 = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(PreciseComponent(value as f32))
+}
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use bevy_replicon::{
     client::server_mutate_ticks::{MutateTickReceived, ServerMutateTicks},
-    core::replication::track_mutate_messages::TrackAppExt,
+    core::{
+        channels::ReplicationChannel, replication::track_mutate_messages::TrackAppExt,
+        server_entity_map::ServerEntityMap,
+    },
     prelude::*,
     server::server_tick::ServerTick,
     test_app::ServerTestAppExt,
@@ -176,5 +179,145 @@ fn multiple_messages() {
     assert!(mutate_ticks.contains(tick));
 }
 
+#[test]
+fn confirmed_entities() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    track_confirmed_entities: true,
+                    ..Default::default()
+                }),
+        ))
+        .track_mutate_messages()
+        .replicate::<BoolComponent>();
+    }
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value only for one entity.
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    // Clear previous events.
+    client_app
+        .world_mut()
+        .resource_mut::<Events<MutateTickReceived>>()
+        .clear();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = *entity_map.to_client().get(&server_entity).unwrap();
+
+    let mut tick_events = client_app
+        .world_mut()
+        .resource_mut::<Events<MutateTickReceived>>();
+    let [event] = tick_events.drain().collect::<Vec<_>>().try_into().unwrap();
+    assert_eq!(event.entities_confirmed.len(), 1);
+    assert!(event.entities_confirmed.contains(&client_entity));
+}
+
+#[test]
+fn confirmed_entities_without_outdated() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    track_confirmed_entities: true,
+                    ..Default::default()
+                }),
+        ))
+        .track_mutate_messages()
+        .replicate::<BoolComponent>();
+    }
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Produce two mutate messages for the entity, one per tick.
+    let mut messages = Vec::new();
+    for value in [true, false] {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<BoolComponent>(server_entity)
+            .unwrap();
+        component.0 = value;
+
+        server_app.update();
+
+        let tick = **server_app.world().resource::<ServerTick>();
+        let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+        let (_, channel_id, message) = server
+            .drain_sent()
+            .find(|&(_, channel_id, _)| channel_id == ReplicationChannel::Mutations.into())
+            .unwrap();
+        messages.push((tick, channel_id, message));
+    }
+
+    // Deliver them in reverse order to make the first one outdated.
+    for (_, channel_id, message) in messages.iter().rev().cloned() {
+        let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+        client.insert_received(channel_id, message);
+        client_app.update();
+    }
+
+    let (old_tick, ..) = messages[0];
+    let mut tick_events = client_app
+        .world_mut()
+        .resource_mut::<Events<MutateTickReceived>>();
+    let event = tick_events
+        .drain()
+        .find(|event| event.tick == old_tick)
+        .expect("outdated tick should still be confirmed");
+    assert!(
+        event.entities_confirmed.is_empty(),
+        "entity with discarded mutations shouldn't be confirmed"
+    );
+}
+
 #[derive(Clone, Component, Copy, Deserialize, Serialize)]
 struct BoolComponent(bool);
@@ -1,22 +1,28 @@
-use std::io::Cursor;
+use std::{io::Cursor, thread};
 
-use bevy::{ecs::entity::MapEntities, prelude::*, utils::Duration};
+use bevy::{ecs::entity::MapEntities, prelude::*, time::TimeUpdateStrategy, utils::Duration};
 use bevy_replicon::{
     client::{
-        confirm_history::{ConfirmHistory, EntityReplicated},
-        ServerUpdateTick,
+        confirm_history::{ConfirmHistory, EntityReplicated, ReplicatedSource},
+        BufferedMutations, BufferedMutationsOverflow, MutationEvicted, ServerUpdateTick,
     },
     core::{
+        channels::ReplicationChannel,
         replication::{
             command_markers::MarkerConfig,
             deferred_entity::DeferredEntity,
-            replication_registry::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+            replication_registry::{
+                command_fns,
+                ctx::WriteCtx,
+                delta_fns::Delta,
+                rule_fns::{DeserializeFn, RuleFns},
+            },
         },
         server_entity_map::ServerEntityMap,
     },
     prelude::*,
     server::server_tick::ServerTick,
-    test_app::ServerTestAppExt,
+    test_app::{NetworkConditions, ServerTestAppExt},
 };
 use serde::{Deserialize, Serialize};
 
@@ -458,6 +464,95 @@ fn marker_with_history_consume() {
     );
 }
 
+#[test]
+fn marker_with_history_consume_validation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .init_resource::<ConsumedValues>()
+        .register_marker_with::<HistoryMarker>(MarkerConfig {
+            need_history: true,
+            ..Default::default()
+        })
+        .set_marker_fns::<HistoryMarker, BoolComponent>(
+            write_history,
+            command_fns::default_remove::<BoolComponent>,
+        )
+        .replicate::<BoolComponent>()
+        .replicate_with(
+            RuleFns::<ValidatedComponent>::default()
+                .with_consume(consume_into_resource)
+                .with_validate(validate_false),
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), ValidatedComponent(false)))
+        .id();
+
+    let client_entity = client_app.world_mut().spawn(HistoryMarker).id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value to invalid, but don't process it on client.
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<ValidatedComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value again to trigger another message.
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<ValidatedComponent>(server_entity)
+        .unwrap();
+    component.0 = false;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let consumed = client_app.world().resource::<ConsumedValues>();
+    assert!(
+        consumed.is_empty(),
+        "invalid older mutation shouldn't be consumed"
+    );
+
+    let component = client_app
+        .world()
+        .get::<ValidatedComponent>(client_entity)
+        .unwrap();
+    assert!(!component.0);
+}
+
 #[test]
 fn marker_with_history_old_update() {
     let mut server_app = App::new();
@@ -539,6 +634,93 @@ fn marker_with_history_old_update() {
     );
 }
 
+#[test]
+fn marker_with_history_old_update_resync() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    mutation_drop_policy: DropPolicy::RequestResync,
+                    ..Default::default()
+                }),
+        ))
+        .register_marker_with::<HistoryMarker>(MarkerConfig {
+            need_history: true,
+            ..Default::default()
+        })
+        .set_marker_fns::<HistoryMarker, BoolComponent>(
+            write_history,
+            command_fns::default_remove::<BoolComponent>,
+        )
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    let client_entity = client_app.world_mut().spawn(HistoryMarker).id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let snapshot_requests = client_app
+        .world()
+        .resource::<Events<ClientSnapshotRequest>>();
+    assert!(snapshot_requests.is_empty());
+
+    let mut tick = **server_app.world().resource::<ServerTick>();
+    tick += u64::BITS + 1;
+    let mut history = client_app
+        .world_mut()
+        .get_mut::<ConfirmHistory>(client_entity)
+        .unwrap();
+    history.confirm(tick);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let snapshot_requests = client_app
+        .world()
+        .resource::<Events<ClientSnapshotRequest>>();
+    assert_eq!(
+        snapshot_requests.len(),
+        1,
+        "discarded mutation should request resync"
+    );
+}
+
 #[test]
 fn many_entities() {
     let mut server_app = App::new();
@@ -788,7 +970,7 @@ fn buffering() {
 }
 
 #[test]
-fn old_ignored() {
+fn with_condition() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -799,15 +981,14 @@ fn old_ignored() {
                 ..Default::default()
             }),
         ))
-        .replicate_mapped::<MappedComponent>();
+        .replicate_if::<BoolComponent>(|component| component.0);
     }
 
     server_app.connect_client(&mut client_app);
 
-    let server_map_entity = server_app.world_mut().spawn_empty().id();
     let server_entity = server_app
         .world_mut()
-        .spawn((Replicated, MappedComponent(server_map_entity)))
+        .spawn((Replicated, BoolComponent(false)))
         .id();
 
     server_app.update();
@@ -815,45 +996,54 @@ fn old_ignored() {
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    // Change the value, but don't process it on client.
-    let dummy_entity1 = server_app.world_mut().spawn_empty().id();
-    let mut component = server_app
+    client_app
         .world_mut()
-        .get_mut::<MappedComponent>(server_entity)
-        .unwrap();
-    component.0 = dummy_entity1;
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+
+    // Trigger change detection without affecting the condition.
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .set_changed();
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
 
-    // Change the value again to trigger another message.
-    let dummy_entity2 = server_app.world_mut().spawn_empty().id();
-    let mut component = server_app
+    let mutate_stats = server_app
+        .world()
+        .resource::<ReplicatedClients>()
+        .iter()
+        .next()
+        .unwrap()
+        .mutate_stats();
+    assert_eq!(
+        mutate_stats.messages_count, 0,
+        "mutation shouldn't be sent when the condition is false"
+    );
+
+    server_app
         .world_mut()
-        .get_mut::<MappedComponent>(server_entity)
-        .unwrap();
-    component.0 = dummy_entity2;
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
 
-    let entity_map = client_app.world().resource::<ServerEntityMap>();
-    assert!(
-        !entity_map.to_client().contains_key(&dummy_entity1),
-        "client should ignore older mutation"
-    );
-
-    let mut replicated = client_app.world_mut().query::<&Replicated>();
-    assert_eq!(
-        replicated.iter(client_app.world()).len(),
-        3,
-        "client should have 2 initial entities and 1 from mutation"
-    );
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(component.0);
 }
 
 #[test]
-fn acknowledgment() {
+fn sleeping() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -861,7 +1051,6 @@ fn acknowledgment() {
             MinimalPlugins,
             RepliconPlugins.set(ServerPlugin {
                 tick_policy: TickPolicy::EveryFrame,
-                mutations_timeout: Duration::ZERO, // Will cause dropping updates after each frame.
                 ..Default::default()
             }),
         ))
@@ -880,25 +1069,9 @@ fn acknowledgment() {
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    let mut component = server_app
-        .world_mut()
-        .get_mut::<BoolComponent>(server_entity)
-        .unwrap();
-    component.0 = true;
-
-    server_app.update();
-    server_app.exchange_with_client(&mut client_app);
-    client_app.update();
-
-    let component = client_app
-        .world_mut()
-        .query::<Ref<BoolComponent>>()
-        .single(client_app.world());
-    let tick1 = component.last_changed();
-
-    // Take and drop ack message.
-    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
-    assert_eq!(client.drain_sent().count(), 1);
+    let mut entity = server_app.world_mut().entity_mut(server_entity);
+    entity.insert(ReplicationSleeping);
+    entity.get_mut::<BoolComponent>().unwrap().0 = true;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
@@ -907,14 +1080,14 @@ fn acknowledgment() {
 
     let component = client_app
         .world_mut()
-        .query::<Ref<BoolComponent>>()
+        .query::<&BoolComponent>()
         .single(client_app.world());
-    let tick2 = component.last_changed();
+    assert!(!component.0, "sleeping entity shouldn't be mutated");
 
-    assert!(
-        tick1.get() < tick2.get(),
-        "client should receive the same mutation twice because server missed the ack"
-    );
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<ReplicationSleeping>();
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
@@ -922,19 +1095,13 @@ fn acknowledgment() {
 
     let component = client_app
         .world_mut()
-        .query::<Ref<BoolComponent>>()
+        .query::<&BoolComponent>()
         .single(client_app.world());
-    let tick3 = component.last_changed();
-
-    assert_eq!(
-        tick2.get(),
-        tick3.get(),
-        "client shouldn't receive acked mutation"
-    );
+    assert!(component.0, "mutation should be sent after waking up");
 }
 
 #[test]
-fn confirm_history() {
+fn paused() {
     let mut server_app = App::new();
     let mut client_app = App::new();
     for app in [&mut server_app, &mut client_app] {
@@ -947,7 +1114,6 @@ fn confirm_history() {
         ))
         .replicate::<BoolComponent>();
     }
-    client_app.finish();
 
     server_app.connect_client(&mut client_app);
 
@@ -961,34 +1127,857 @@ fn confirm_history() {
     client_app.update();
     server_app.exchange_with_client(&mut client_app);
 
-    // Change value.
-    let mut component = server_app
-        .world_mut()
-        .get_mut::<BoolComponent>(server_entity)
-        .unwrap();
-    component.0 = true;
-
-    // Clear previous events.
-    client_app
-        .world_mut()
-        .resource_mut::<Events<EntityReplicated>>()
-        .clear();
+    let mut entity = server_app.world_mut().entity_mut(server_entity);
+    entity.insert(ReplicationPaused);
+    entity.get_mut::<BoolComponent>().unwrap().0 = true;
 
     server_app.update();
     server_app.exchange_with_client(&mut client_app);
     client_app.update();
+    server_app.exchange_with_client(&mut client_app);
 
-    let tick = **server_app.world().resource::<ServerTick>();
-
-    let (client_entity, confirm_history) = client_app
+    let component = client_app
         .world_mut()
-        .query::<(Entity, &ConfirmHistory)>()
+        .query::<&BoolComponent>()
         .single(client_app.world());
-    assert!(confirm_history.contains(tick));
+    assert!(!component.0, "paused entity shouldn't be mutated");
 
-    let mut replicated_events = client_app
-        .world_mut()
-        .resource_mut::<Events<EntityReplicated>>();
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let client = replicated_clients.client(client_id);
+    assert!(
+        client.mutation_tick(server_entity).is_none(),
+        "pausing should reset the acknowledged state"
+    );
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<ReplicationPaused>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(component.0, "state should be re-sent after resuming");
+}
+
+#[test]
+fn resend_after_loss() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+    server_app.with_network_conditions(0, 1.0);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(!component.0, "mutation should be lost");
+
+    server_app
+        .world_mut()
+        .resource_mut::<NetworkConditions>()
+        .loss_rate = 0.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(component.0, "unacknowledged mutation should be resent");
+}
+
+#[test]
+fn delta() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with_delta(RuleFns::<DeltaComponent>::default());
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DeltaComponent(vec![0; 1000])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for value in 1..=2 {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<DeltaComponent>(server_entity)
+            .unwrap();
+        component.0[10] = value;
+
+        server_app.update();
+
+        let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+        let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+        let mut sent_bytes = 0;
+        for (_, channel_id, message) in server.drain_sent() {
+            if channel_id == ReplicationChannel::Mutations.into() {
+                sent_bytes += message.len();
+            }
+            client.insert_received(channel_id, message);
+        }
+        assert!(
+            sent_bytes < 100,
+            "mutation should be sent as a diff, but got {sent_bytes} bytes"
+        );
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let component = client_app
+            .world_mut()
+            .query::<&DeltaComponent>()
+            .single(client_app.world());
+        assert_eq!(component.0[10], value);
+        assert!(component
+            .0
+            .iter()
+            .enumerate()
+            .all(|(index, &v)| index == 10 || v == 0));
+    }
+}
+
+#[test]
+fn delta_after_loss() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with_delta(RuleFns::<DeltaComponent>::default());
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DeltaComponent(vec![0; 1000])))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<DeltaComponent>(server_entity)
+        .unwrap();
+    component.0[10] = 1;
+
+    server_app.update();
+    server_app.with_network_conditions(0, 1.0);
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&DeltaComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0[10], 0, "mutation should be lost");
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<DeltaComponent>(server_entity)
+        .unwrap();
+    component.0[20] = 2;
+
+    server_app
+        .world_mut()
+        .resource_mut::<NetworkConditions>()
+        .loss_rate = 0.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&DeltaComponent>()
+        .single(client_app.world());
+    assert_eq!(
+        component.0[10], 1,
+        "diff should be based on the acknowledged value"
+    );
+    assert_eq!(component.0[20], 2);
+}
+
+#[test]
+fn change_filter() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with_change_filter(RuleFns::<FloatComponent>::default(), |a, b| {
+            (a.0 - b.0).abs() < 0.1
+        });
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, FloatComponent(0.0)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<FloatComponent>(server_entity)
+        .unwrap();
+    component.0 = 0.05;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&FloatComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 0.0, "filtered mutation shouldn't be sent");
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<FloatComponent>(server_entity)
+        .unwrap();
+    component.0 = 1.0;
+
+    server_app.update();
+    server_app.with_network_conditions(0, 1.0);
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<&FloatComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 0.0, "mutation should be lost");
+
+    // Write the same value, it shouldn't prevent resending the lost one.
+    server_app
+        .world_mut()
+        .get_mut::<FloatComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+
+    server_app
+        .world_mut()
+        .resource_mut::<NetworkConditions>()
+        .loss_rate = 0.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&FloatComponent>()
+        .single(client_app.world());
+    assert_eq!(component.0, 1.0, "lost mutation should be resent");
+}
+
+#[test]
+fn latency() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+    server_app.with_network_conditions(20, 0.0).insert_resource(
+        TimeUpdateStrategy::ManualDuration(Duration::from_millis(20)),
+    );
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        0,
+        "messages should be delayed"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(components.iter(client_app.world()).count(), 1);
+}
+
+#[test]
+fn sleep_threshold() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_with_sleep_threshold::<BoolComponent>(1);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.update();
+    assert!(server_app
+        .world()
+        .entity(server_entity)
+        .contains::<ReplicationSleeping>());
+
+    // Change value.
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(!server_app
+        .world()
+        .entity(server_entity)
+        .contains::<ReplicationSleeping>());
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(component.0, "mutation should wake up the entity");
+}
+
+#[test]
+fn bandwidth_budget() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                bandwidth_budget_bytes_per_tick: Some(1),
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let low_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let high_entity = server_app
+        .world_mut()
+        .spawn((Replicated, ReplicationPriority(1), BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change values.
+    for entity in [low_entity, high_entity] {
+        let mut component = server_app
+            .world_mut()
+            .get_mut::<BoolComponent>(entity)
+            .unwrap();
+        component.0 = true;
+    }
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_low_entity = *entity_map.to_client().get(&low_entity).unwrap();
+    let client_high_entity = *entity_map.to_client().get(&high_entity).unwrap();
+
+    let high_component = client_app
+        .world()
+        .get::<BoolComponent>(client_high_entity)
+        .unwrap();
+    assert!(
+        high_component.0,
+        "entity with higher priority should be sent first"
+    );
+    let low_component = client_app
+        .world()
+        .get::<BoolComponent>(client_low_entity)
+        .unwrap();
+    assert!(
+        !low_component.0,
+        "entity outside the budget should be deferred"
+    );
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let client = replicated_clients.iter().next().unwrap();
+    assert_eq!(client.mutate_stats().deferred_entities, 1);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let low_component = client_app
+        .world()
+        .get::<BoolComponent>(client_low_entity)
+        .unwrap();
+    assert!(
+        low_component.0,
+        "deferred entity should be sent on the next tick"
+    );
+}
+
+#[test]
+fn old_ignored() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_mapped::<MappedComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_map_entity = server_app.world_mut().spawn_empty().id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, MappedComponent(server_map_entity)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change the value, but don't process it on client.
+    let dummy_entity1 = server_app.world_mut().spawn_empty().id();
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<MappedComponent>(server_entity)
+        .unwrap();
+    component.0 = dummy_entity1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change the value again to trigger another message.
+    let dummy_entity2 = server_app.world_mut().spawn_empty().id();
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<MappedComponent>(server_entity)
+        .unwrap();
+    component.0 = dummy_entity2;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        !entity_map.to_client().contains_key(&dummy_entity1),
+        "client should ignore older mutation"
+    );
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app.world()).len(),
+        3,
+        "client should have 2 initial entities and 1 from mutation"
+    );
+}
+
+#[test]
+fn acknowledgment() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                mutations_timeout: Duration::ZERO, // Will cause dropping updates after each frame.
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<Ref<BoolComponent>>()
+        .single(client_app.world());
+    let tick1 = component.last_changed();
+
+    // Take and drop ack message.
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    assert_eq!(client.drain_sent().count(), 1);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let component = client_app
+        .world_mut()
+        .query::<Ref<BoolComponent>>()
+        .single(client_app.world());
+    let tick2 = component.last_changed();
+
+    assert!(
+        tick1.get() < tick2.get(),
+        "client should receive the same mutation twice because server missed the ack"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<Ref<BoolComponent>>()
+        .single(client_app.world());
+    let tick3 = component.last_changed();
+
+    assert_eq!(
+        tick2.get(),
+        tick3.get(),
+        "client shouldn't receive acked mutation"
+    );
+}
+
+#[test]
+fn multiple_channels() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(
+                ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                }
+                .per_mutation_channel(2),
+            ),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity1 = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let server_entity2 = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for server_entity in [server_entity1, server_entity2] {
+        server_app
+            .world_mut()
+            .get_mut::<BoolComponent>(server_entity)
+            .unwrap()
+            .0 = true;
+    }
+
+    server_app.update();
+
+    let mutation_channels = server_app
+        .world()
+        .resource::<RepliconChannels>()
+        .mutation_channel_ids()
+        .to_vec();
+    assert_eq!(mutation_channels.len(), 2);
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let messages: Vec<_> = server.drain_sent().collect();
+    let mut channel_ids: Vec<_> = messages
+        .iter()
+        .map(|&(_, channel_id, _)| channel_id)
+        .collect();
+    channel_ids.sort_unstable();
+    assert_eq!(
+        channel_ids, mutation_channels,
+        "each entity should be sent over its own channel"
+    );
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in messages {
+        client.insert_received(channel_id, message);
+    }
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    assert!(components
+        .iter(client_app.world())
+        .all(|component| component.0));
+
+    server_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    assert_eq!(
+        server.drain_sent().count(),
+        0,
+        "mutations from both channels should be acknowledged"
+    );
+}
+
+#[test]
+fn mismatched_channels() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for (app, channels) in [(&mut server_app, 1), (&mut client_app, 2)] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(
+                ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                }
+                .per_mutation_channel(channels),
+            ),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Entities are assigned to channels by index, so pick the one that belongs to the second channel.
+    let server_entity = server_app
+        .world_mut()
+        .spawn_batch([(Replicated, BoolComponent(false)); 2])
+        .find(|entity| entity.index() % 2 == 1)
+        .unwrap();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = *entity_map.to_client().get(&server_entity).unwrap();
+    let component = client_app
+        .world()
+        .get::<BoolComponent>(client_entity)
+        .unwrap();
+    assert!(
+        !component.0,
+        "mutation sent over the first channel for an entity from the second channel should be discarded"
+    );
+}
+
+#[test]
+fn confirm_history() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    client_app.finish();
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value.
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    // Clear previous events.
+    client_app
+        .world_mut()
+        .resource_mut::<Events<EntityReplicated>>()
+        .clear();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let tick = **server_app.world().resource::<ServerTick>();
+
+    let (client_entity, confirm_history) = client_app
+        .world_mut()
+        .query::<(Entity, &ConfirmHistory)>()
+        .single(client_app.world());
+    assert!(confirm_history.contains(tick));
+
+    let component_id = client_app.world().component_id::<BoolComponent>().unwrap();
+    let mut replicated_events = client_app
+        .world_mut()
+        .resource_mut::<Events<EntityReplicated>>();
     let [event] = replicated_events
         .drain()
         .collect::<Vec<_>>()
@@ -996,6 +1985,344 @@ fn confirm_history() {
         .unwrap();
     assert_eq!(event.entity, client_entity);
     assert_eq!(event.tick, tick);
+    assert_eq!(*event.changed_components, [component_id]);
+    assert_eq!(event.source, None);
+}
+
+#[test]
+fn confirm_history_skips_invalid() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate_with(RuleFns::<ValidatedComponent>::default().with_validate(validate_false));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false), ValidatedComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change values, the validated component will be rejected.
+    let mut entity = server_app.world_mut().entity_mut(server_entity);
+    entity.get_mut::<BoolComponent>().unwrap().0 = true;
+    entity.get_mut::<ValidatedComponent>().unwrap().0 = true;
+
+    // Clear previous events.
+    client_app
+        .world_mut()
+        .resource_mut::<Events<EntityReplicated>>()
+        .clear();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&ValidatedComponent>()
+        .single(client_app.world());
+    assert!(!component.0);
+
+    let component_id = client_app.world().component_id::<BoolComponent>().unwrap();
+    let mut replicated_events = client_app
+        .world_mut()
+        .resource_mut::<Events<EntityReplicated>>();
+    let [event] = replicated_events
+        .drain()
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        *event.changed_components,
+        [component_id],
+        "skipped component shouldn't be reported as changed"
+    );
+}
+
+#[test]
+fn detailed_replicated_events() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    detailed_replicated_events: true,
+                    ..Default::default()
+                }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated_events = client_app
+        .world_mut()
+        .resource_mut::<Events<EntityReplicated>>();
+    let [event] = replicated_events
+        .drain()
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    assert_eq!(event.source, Some(ReplicatedSource::Update));
+
+    // Change value.
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated_events = client_app
+        .world_mut()
+        .resource_mut::<Events<EntityReplicated>>();
+    let [event] = replicated_events
+        .drain()
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    assert_eq!(event.source, Some(ReplicatedSource::Mutation));
+}
+
+#[test]
+fn buffer_timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    mutation_buffer_timeout: Some(Duration::from_millis(10)),
+                    ..Default::default()
+                }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value and spawn an entity to make the mutation depend on an update message.
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+
+    server_app.update();
+
+    // Deliver only the mutation.
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let mut messages: Vec<_> = server.drain_sent().collect();
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    messages.retain(|(_, channel_id, message)| {
+        if *channel_id == ReplicationChannel::Mutations.into() {
+            client.insert_received(*channel_id, message.clone());
+            false
+        } else {
+            true
+        }
+    });
+
+    client_app.update();
+
+    let buffered_mutations = client_app.world().resource::<BufferedMutations>();
+    assert_eq!(
+        buffered_mutations.len(),
+        1,
+        "mutation should wait for update"
+    );
+
+    thread::sleep(Duration::from_millis(20));
+    client_app.update();
+
+    let buffered_mutations = client_app.world().resource::<BufferedMutations>();
+    assert!(buffered_mutations.is_empty());
+
+    let (client_entity, component) = client_app
+        .world_mut()
+        .query::<(Entity, &BoolComponent)>()
+        .single(client_app.world());
+    assert!(!component.0, "evicted mutation shouldn't be applied");
+
+    let mut evicted_events = client_app
+        .world_mut()
+        .resource_mut::<Events<MutationEvicted>>();
+    let [event] = evicted_events
+        .drain()
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    assert_eq!(event.entity, client_entity);
+
+    // Deliver the delayed update message.
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (_, channel_id, message) in messages {
+        client.insert_received(channel_id, message);
+    }
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(
+        component.0,
+        "evicted mutation shouldn't be acknowledged and should be resent"
+    );
+}
+
+#[test]
+fn buffer_overflow() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    max_buffered_mutations: Some(1),
+                    ..Default::default()
+                }),
+        ))
+        .replicate::<BoolComponent>()
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity1 = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let server_entity2 = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut dropped_tick = None;
+    let mut update_messages = Vec::new();
+    for server_entity in [server_entity1, server_entity2] {
+        // Change value and spawn an entity to make the mutation depend on an update message.
+        server_app
+            .world_mut()
+            .get_mut::<BoolComponent>(server_entity)
+            .unwrap()
+            .0 = true;
+        server_app.world_mut().spawn((Replicated, DummyComponent));
+
+        server_app.update();
+        dropped_tick.get_or_insert(**server_app.world().resource::<ServerTick>());
+
+        // Deliver only the mutation.
+        let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+        let messages: Vec<_> = server.drain_sent().collect();
+        let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+        for (_, channel_id, message) in messages {
+            if channel_id == ReplicationChannel::Mutations.into() {
+                client.insert_received(channel_id, message);
+            } else {
+                update_messages.push((channel_id, message));
+            }
+        }
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let buffered_mutations = client_app.world().resource::<BufferedMutations>();
+    assert_eq!(buffered_mutations.len(), 1);
+    assert!(buffered_mutations.is_full());
+
+    let mut overflow_events = client_app
+        .world_mut()
+        .resource_mut::<Events<BufferedMutationsOverflow>>();
+    let [event] = overflow_events
+        .drain()
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    assert_eq!(Some(event.dropped_tick), dropped_tick);
+
+    // Deliver the delayed update messages.
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for (channel_id, message) in update_messages {
+        client.insert_received(channel_id, message);
+    }
+
+    client_app.update();
+
+    let mut components = client_app.world_mut().query::<&BoolComponent>();
+    assert!(
+        components
+            .iter(client_app.world())
+            .all(|component| component.0),
+        "dropped mutation shouldn't be acknowledged and should be resent"
+    );
 }
 
 #[derive(Component, Deserialize, Serialize)]
@@ -1007,6 +2334,34 @@ struct BoolComponent(bool);
 #[derive(Component, Default, Deserialize, Serialize)]
 struct VecComponent(Vec<u8>);
 
+#[derive(Clone, Component, Copy, Deserialize, Serialize)]
+struct FloatComponent(f32);
+
+#[derive(Clone, Component, Deserialize, Serialize)]
+struct DeltaComponent(Vec<u8>);
+
+impl Delta for DeltaComponent {
+    type Diff = Vec<(usize, u8)>;
+
+    fn diff(old: &Self, new: &Self) -> Self::Diff {
+        old.0
+            .iter()
+            .zip(&new.0)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(index, (_, &new))| (index, new))
+            .collect()
+    }
+
+    fn patch(base: &Self, diff: &Self::Diff) -> Self {
+        let mut values = base.0.clone();
+        for &(index, value) in diff {
+            values[index] = value;
+        }
+        Self(values)
+    }
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct MappedComponent(Entity);
 
@@ -1028,6 +2383,12 @@ struct ReplacedComponent(bool);
 #[derive(Component)]
 struct HistoryMarker;
 
+#[derive(Component, Deserialize, Serialize)]
+struct ValidatedComponent(bool);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+struct ConsumedValues(Vec<bool>);
+
 #[derive(Component, Deref, DerefMut)]
 struct BoolHistory(Vec<bool>);
 
@@ -1046,6 +2407,28 @@ fn replace(
     Ok(())
 }
 
+/// Instead of discarding [`ValidatedComponent`], it stores the value in [`ConsumedValues`].
+fn consume_into_resource(
+    deserialize: DeserializeFn<ValidatedComponent>,
+    ctx: &mut WriteCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component = (deserialize)(ctx, cursor)?;
+    ctx.commands.queue(move |world: &mut World| {
+        world.resource_mut::<ConsumedValues>().push(component.0);
+    });
+
+    Ok(())
+}
+
+/// Rejects `true` for [`ValidatedComponent`].
+fn validate_false(component: &ValidatedComponent) -> Result<(), String> {
+    if component.0 {
+        return Err("value can't be `true`".into());
+    }
+    Ok(())
+}
+
 /// Instead of writing into [`BoolComponent`], it writes data into [`BoolHistory`].
 fn write_history(
     ctx: &mut WriteCtx,
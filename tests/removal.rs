@@ -2,10 +2,17 @@ use std::io::Cursor;
 
 use bevy::prelude::*;
 use bevy_replicon::{
-    client::confirm_history::{ConfirmHistory, EntityReplicated},
-    core::replication::{
-        deferred_entity::DeferredEntity,
-        replication_registry::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+    client::{
+        confirm_history::{ConfirmHistory, EntityReplicated},
+        ServerUpdateTick,
+    },
+    core::{
+        channels::ReplicationChannel,
+        replication::{
+            deferred_entity::DeferredEntity,
+            replication_registry::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+        },
+        server_entity_map::ServerEntityMap,
     },
     prelude::*,
     server::server_tick::ServerTick,
@@ -449,6 +456,7 @@ fn confirm_history() {
         .unwrap();
     assert_eq!(event.entity, client_entity);
     assert_eq!(event.tick, tick);
+    assert!(event.changed_components.is_empty());
 }
 
 #[test]
@@ -495,6 +503,302 @@ fn hidden() {
     );
 }
 
+#[test]
+fn sorted_receive_order() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    replication_receive_order: ReceiveOrder::Sorted,
+                    ..Default::default()
+                }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<DummyComponent>();
+
+    server_app.update();
+
+    // Deliver update messages in reverse order.
+    let mut messages: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .filter(|&(_, channel_id, _)| channel_id == ReplicationChannel::Updates as u8)
+        .map(|(_, _, message)| message)
+        .collect();
+    assert_eq!(messages.len(), 2);
+    messages.reverse();
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for message in messages {
+        client.insert_received(ReplicationChannel::Updates, message);
+    }
+
+    client_app.update();
+
+    let client_entity = client_app
+        .world()
+        .resource::<ServerEntityMap>()
+        .to_client()
+        .get(&server_entity)
+        .copied()
+        .expect("entity should be replicated");
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(!client_entity.contains::<DummyComponent>());
+}
+
+#[test]
+fn sorted_receive_order_with_stale_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    replication_receive_order: ReceiveOrder::Sorted,
+                    ..Default::default()
+                }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Produce three update messages, one per tick.
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<DummyComponent>();
+    server_app.update();
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+    server_app.update();
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+    server_app.update();
+
+    let mut messages: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .filter(|&(_, channel_id, _)| channel_id == ReplicationChannel::Updates as u8)
+        .map(|(_, _, message)| message)
+        .collect();
+    assert_eq!(messages.len(), 3);
+    let newest = messages.pop().unwrap();
+    let middle = messages.pop().unwrap();
+    let stale = messages.pop().unwrap();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, middle);
+
+    client_app.update();
+
+    // Deliver the message older than the last applied together with the newest one.
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, newest);
+    client.insert_received(ReplicationChannel::Updates, stale);
+
+    client_app.update();
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let update_tick = **client_app.world().resource::<ServerUpdateTick>();
+    assert_eq!(
+        update_tick, server_tick,
+        "newest message should be applied last"
+    );
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 2);
+}
+
+#[test]
+fn coalescing_window() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                removal_coalescing_window: 2,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity1 = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+    let server_entity2 = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut components = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<DummyComponent>>();
+    assert_eq!(components.iter(client_app.world()).count(), 2);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity1)
+        .remove::<DummyComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        2,
+        "removal should be buffered"
+    );
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity2)
+        .remove::<DummyComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(components.iter(client_app.world()).count(), 2);
+
+    server_app.update();
+
+    let messages: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .drain_sent()
+        .filter(|&(_, channel_id, _)| channel_id == ReplicationChannel::Updates as u8)
+        .map(|(_, _, message)| message)
+        .collect();
+    assert_eq!(
+        messages.len(),
+        1,
+        "removals should be sent in a single message"
+    );
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for message in messages {
+        client.insert_received(ReplicationChannel::Updates, message);
+    }
+
+    client_app.update();
+    assert_eq!(
+        components.iter(client_app.world()).count(),
+        0,
+        "all removals should be sent after the window"
+    );
+}
+
+#[test]
+fn coalescing_window_reinsertion() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                removal_coalescing_window: 1,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<DummyComponent>>()
+        .single(client_app.world());
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<DummyComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(DummyComponent);
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let client_entity = client_app.world().entity(client_entity);
+        assert!(
+            client_entity.contains::<DummyComponent>(),
+            "removal of a re-added component shouldn't be sent"
+        );
+    }
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
 
@@ -0,0 +1,338 @@
+use std::io::Cursor;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::{
+        channels::ReplicationChannel, replication::replicated_resources::ResourceFns,
+        replicon_tick::RepliconTick,
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn initial() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<DummyResource>();
+    }
+
+    server_app.insert_resource(DummyResource(1));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(resource.0, 1);
+}
+
+#[test]
+fn mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<DummyResource>();
+    }
+
+    server_app.insert_resource(DummyResource(0));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value.
+    server_app.world_mut().resource_mut::<DummyResource>().0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(resource.0, 1);
+
+    // Overwrite value on client and make sure that it won't be resent without changes.
+    client_app.insert_resource(DummyResource(2));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(resource.0, 2);
+}
+
+#[test]
+fn once() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource_once::<DummyResource>();
+    }
+
+    server_app.insert_resource(DummyResource(0));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change value.
+    server_app.world_mut().resource_mut::<DummyResource>().0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(resource.0, 0, "changes shouldn't be sent");
+}
+
+#[test]
+fn with_fns() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource_with(ResourceFns::new(serialize_doubled, deserialize_doubled));
+    }
+
+    server_app.insert_resource(DummyResource(1));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(resource.0, 2);
+}
+
+#[test]
+fn before_started_replication() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                replicate_after_connect: false,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<DummyResource>();
+    }
+
+    server_app.insert_resource(DummyResource(1));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        !client_app.world().contains_resource::<DummyResource>(),
+        "client shouldn't receive resources before replication started"
+    );
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    server_app.world_mut().trigger(StartReplication(client_id));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(resource.0, 1);
+}
+
+#[test]
+fn reconnect_in_same_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<DummyResource>();
+    }
+
+    server_app.insert_resource(DummyResource(1));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app.insert_resource(DummyResource(2));
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    server_app
+        .world_mut()
+        .trigger(ServerEvent::ClientDisconnected {
+            client_id,
+            reason: Default::default(),
+        });
+    server_app
+        .world_mut()
+        .trigger(ServerEvent::ClientConnected { client_id });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(
+        resource.0, 1,
+        "reconnected client should receive the resource again"
+    );
+}
+
+#[test]
+fn before_update_message() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<DummyResource>();
+    }
+
+    server_app.insert_resource(DummyResource(0));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+    server_app.world_mut().resource_mut::<DummyResource>().0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Delay the update message.
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    let messages: Vec<_> = client.receive(ReplicationChannel::Updates).collect();
+    assert_eq!(messages.len(), 1);
+
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(
+        resource.0, 0,
+        "resource shouldn't be applied before the update message"
+    );
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for message in messages {
+        client.insert_received(ReplicationChannel::Updates, message);
+    }
+
+    client_app.update();
+
+    let resource = client_app.world().resource::<DummyResource>();
+    assert_eq!(resource.0, 1);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn invalid_index() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_resource::<DummyResource>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut message = Vec::new();
+    bincode::serialize_into(&mut message, &RepliconTick::default()).unwrap();
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut message, &(usize::MAX, [0u8]))
+        .unwrap();
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Resources, message);
+
+    client_app.update();
+}
+
+#[derive(Resource, Deserialize, Serialize)]
+struct DummyResource(usize);
+
+fn serialize_doubled(resource: &DummyResource, message: &mut Vec<u8>) -> bincode::Result<()> {
+    bincode::DefaultOptions::new().serialize_into(message, &(resource.0 * 2))
+}
+
+fn deserialize_doubled(cursor: &mut Cursor<&[u8]>) -> bincode::Result<DummyResource> {
+    let value = bincode::DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(DummyResource(value))
+}
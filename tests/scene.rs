@@ -1,5 +1,5 @@
-use bevy::prelude::*;
-use bevy_replicon::{prelude::*, scene};
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use bevy_replicon::{core::server_entity_map::ServerEntityMap, prelude::*, scene};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -102,6 +102,148 @@ fn entity_update() {
     assert_eq!(dyn_entity.components.len(), 2);
 }
 
+#[test]
+fn hierarchy() {
+    let mut app = App::new();
+    app.add_plugins(RepliconPlugins);
+
+    let parent_entity = app.world_mut().spawn(Replicated).id();
+    let child_entity = app
+        .world_mut()
+        .spawn(Replicated)
+        .set_parent(parent_entity)
+        .id();
+    let non_replicated_parent = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .spawn(Replicated)
+        .set_parent(non_replicated_parent);
+
+    let mut scene = DynamicScene::default();
+    scene::replicate_into_with_hierarchy(&mut scene, app.world());
+
+    assert_eq!(scene.entities.len(), 3);
+    let components_count: usize = scene
+        .entities
+        .iter()
+        .map(|dyn_entity| dyn_entity.components.len())
+        .sum();
+    assert_eq!(
+        components_count, 1,
+        "only child with replicated parent should have `ParentSync`"
+    );
+
+    let mut new_app = App::new();
+    new_app.add_plugins((MinimalPlugins, RepliconPlugins));
+
+    let mut entity_map = EntityHashMap::default();
+    scene
+        .write_to_world(new_app.world_mut(), &mut entity_map)
+        .unwrap();
+
+    new_app.update();
+
+    let parent = new_app
+        .world()
+        .get::<Parent>(entity_map[&child_entity])
+        .expect("hierarchy should be restored");
+    assert_eq!(**parent, entity_map[&parent_entity]);
+}
+
+#[test]
+fn filtered() {
+    let mut app = App::new();
+    app.add_plugins(RepliconPlugins)
+        .register_type::<DummyComponent>()
+        .register_type::<OtherReflectedComponent>()
+        .replicate::<DummyComponent>()
+        .replicate::<OtherReflectedComponent>();
+
+    let filtered_entity = app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, OtherReflectedComponent))
+        .id();
+    let entity = app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, OtherReflectedComponent))
+        .id();
+
+    let other_id = app
+        .world()
+        .component_id::<OtherReflectedComponent>()
+        .unwrap();
+    let mut scene = DynamicScene::default();
+    scene::replicate_into_filtered(&mut scene, app.world(), |entity, component_id| {
+        entity != filtered_entity || component_id != other_id
+    });
+
+    assert_eq!(scene.entities.len(), 2);
+    for dyn_entity in &scene.entities {
+        let expected_len = if dyn_entity.entity == entity { 2 } else { 1 };
+        assert_eq!(dyn_entity.components.len(), expected_len);
+    }
+}
+
+#[test]
+fn replicate_from() {
+    let mut app = App::new();
+    app.add_plugins(RepliconPlugins)
+        .register_type::<DummyComponent>()
+        .replicate::<DummyComponent>();
+
+    let entity = app.world_mut().spawn((Replicated, DummyComponent)).id();
+
+    let mut scene = DynamicScene::default();
+    scene::replicate_into(&mut scene, app.world());
+
+    let mut new_app = App::new();
+    new_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_type::<DummyComponent>();
+
+    let mut entity_map = EntityHashMap::default();
+    scene::replicate_from(new_app.world_mut(), &scene, &mut entity_map).unwrap();
+
+    let new_entity = entity_map[&entity];
+    let new_entity_ref = new_app.world().entity(new_entity);
+    assert!(new_entity_ref.contains::<Replicated>());
+    assert!(new_entity_ref.contains::<DummyComponent>());
+
+    let server_map = new_app.world().resource::<ServerEntityMap>();
+    assert_eq!(server_map.to_client().get(&entity), Some(&new_entity));
+}
+
+#[test]
+fn replicate_from_twice() {
+    let mut app = App::new();
+    app.add_plugins(RepliconPlugins)
+        .register_type::<DummyComponent>()
+        .replicate::<DummyComponent>();
+
+    let entity = app.world_mut().spawn((Replicated, DummyComponent)).id();
+
+    let mut scene = DynamicScene::default();
+    scene::replicate_into(&mut scene, app.world());
+
+    let mut new_app = App::new();
+    new_app
+        .add_plugins((MinimalPlugins, RepliconPlugins))
+        .register_type::<DummyComponent>();
+
+    let mut entity_map = EntityHashMap::default();
+    scene::replicate_from(new_app.world_mut(), &scene, &mut entity_map).unwrap();
+    let old_entity = entity_map[&entity];
+
+    let mut entity_map = EntityHashMap::default();
+    scene::replicate_from(new_app.world_mut(), &scene, &mut entity_map).unwrap();
+    let new_entity = entity_map[&entity];
+    assert_ne!(old_entity, new_entity);
+
+    let server_map = new_app.world().resource::<ServerEntityMap>();
+    assert_eq!(server_map.to_client().get(&entity), Some(&new_entity));
+    assert_eq!(server_map.to_server().get(&new_entity), Some(&entity));
+    assert!(!server_map.to_server().contains_key(&old_entity));
+}
+
 #[derive(Component, Default, Deserialize, Reflect, Serialize)]
 #[reflect(Component)]
 struct DummyComponent;
@@ -4,10 +4,14 @@ use bevy::{
     time::TimePlugin,
 };
 use bevy_replicon::{
-    client::ServerUpdateTick, core::server_entity_map::ServerEntityMap, prelude::*,
-    server::server_tick::ServerTick, test_app::ServerTestAppExt,
+    client::ServerUpdateTick,
+    core::{event::server_event, server_entity_map::ServerEntityMap},
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 #[test]
 fn sending_receiving() {
@@ -30,15 +34,34 @@ fn sending_receiving() {
     let client = client_app.world().resource::<RepliconClient>();
     let client_id = client.id().unwrap();
 
+    let mut client_groups = server_app.world_mut().resource_mut::<ClientGroupRegistry>();
+    let client_group = client_groups.register("client", [client_id]);
+    let server_group = client_groups.register("server", [ClientId::SERVER]);
+
     for (mode, events_count) in [
         (SendMode::Broadcast, 1),
         (SendMode::Direct(ClientId::SERVER), 0),
         (SendMode::Direct(client_id), 1),
         (SendMode::BroadcastExcept(ClientId::SERVER), 1),
         (SendMode::BroadcastExcept(client_id), 0),
+        (
+            SendMode::BroadcastExceptMultiple(smallvec![ClientId::SERVER]),
+            1,
+        ),
+        (
+            SendMode::BroadcastExceptMultiple(smallvec![ClientId::SERVER, client_id]),
+            0,
+        ),
+        (SendMode::DirectMultiple(smallvec![ClientId::SERVER]), 0),
+        (
+            SendMode::DirectMultiple(smallvec![ClientId::SERVER, client_id]),
+            1,
+        ),
+        (SendMode::Multicast(client_group), 1),
+        (SendMode::Multicast(server_group), 0),
     ] {
         server_app.world_mut().send_event(ToClients {
-            mode,
+            mode: mode.clone(),
             event: DummyEvent,
         });
 
@@ -139,9 +162,11 @@ fn sending_receiving_without_plugins() {
         (SendMode::Direct(client_id), 1),
         (SendMode::BroadcastExcept(ClientId::SERVER), 1),
         (SendMode::BroadcastExcept(client_id), 0),
+        (SendMode::BroadcastExceptMultiple(smallvec![client_id]), 0),
+        (SendMode::DirectMultiple(smallvec![client_id]), 1),
     ] {
         server_app.world_mut().send_event(ToClients {
-            mode,
+            mode: mode.clone(),
             event: DummyEvent,
         });
 
@@ -173,15 +198,35 @@ fn local_resending() {
     .finish();
 
     const DUMMY_CLIENT_ID: ClientId = ClientId::new(1);
+
+    let mut client_groups = app.world_mut().resource_mut::<ClientGroupRegistry>();
+    let server_group = client_groups.register("server", [ClientId::SERVER]);
+    let client_group = client_groups.register("client", [DUMMY_CLIENT_ID]);
+
     for (mode, events_count) in [
         (SendMode::Broadcast, 1),
         (SendMode::Direct(ClientId::SERVER), 1),
         (SendMode::Direct(DUMMY_CLIENT_ID), 0),
         (SendMode::BroadcastExcept(ClientId::SERVER), 0),
         (SendMode::BroadcastExcept(DUMMY_CLIENT_ID), 1),
+        (
+            SendMode::BroadcastExceptMultiple(smallvec![ClientId::SERVER, DUMMY_CLIENT_ID]),
+            0,
+        ),
+        (
+            SendMode::BroadcastExceptMultiple(smallvec![DUMMY_CLIENT_ID]),
+            1,
+        ),
+        (
+            SendMode::DirectMultiple(smallvec![ClientId::SERVER, DUMMY_CLIENT_ID]),
+            1,
+        ),
+        (SendMode::DirectMultiple(smallvec![DUMMY_CLIENT_ID]), 0),
+        (SendMode::Multicast(server_group), 1),
+        (SendMode::Multicast(client_group), 0),
     ] {
         app.world_mut().send_event(ToClients {
-            mode,
+            mode: mode.clone(),
             event: DummyEvent,
         });
 
@@ -199,6 +244,95 @@ fn local_resending() {
     }
 }
 
+#[test]
+fn filtering() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event_with_filter(
+            ChannelKind::Ordered,
+            server_event::default_serialize::<FilteredEvent>,
+            server_event::default_deserialize::<FilteredEvent>,
+            filter_event,
+        )
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    for (mode, allowed, events_count) in [
+        (SendMode::Broadcast, true, 1),
+        (SendMode::Broadcast, false, 0),
+        (SendMode::Direct(client_id), true, 1),
+        (SendMode::Direct(client_id), false, 0),
+        (SendMode::BroadcastExcept(client_id), true, 0),
+    ] {
+        server_app.world_mut().send_event(ToClients {
+            mode: mode.clone(),
+            event: FilteredEvent(allowed),
+        });
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut events = client_app
+            .world_mut()
+            .resource_mut::<Events<FilteredEvent>>();
+        assert_eq!(
+            events.drain().count(),
+            events_count,
+            "event should be emitted {events_count} times for {mode:?} with `{allowed}`"
+        );
+    }
+}
+
+#[test]
+fn local_filtering() {
+    let mut app = App::new();
+    app.add_plugins((
+        TimePlugin,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .add_server_event_with_filter(
+        ChannelKind::Ordered,
+        server_event::default_serialize::<FilteredEvent>,
+        server_event::default_deserialize::<FilteredEvent>,
+        filter_event,
+    )
+    .finish();
+
+    for (allowed, events_count) in [(true, 1), (false, 0)] {
+        app.world_mut().send_event(ToClients {
+            mode: SendMode::Broadcast,
+            event: FilteredEvent(allowed),
+        });
+
+        app.update();
+
+        let mut events = app.world_mut().resource_mut::<Events<FilteredEvent>>();
+        assert_eq!(
+            events.drain().count(),
+            events_count,
+            "event should be emitted {events_count} times with `{allowed}`"
+        );
+    }
+}
+
 #[test]
 fn event_buffering() {
     let mut server_app = App::new();
@@ -459,9 +593,11 @@ fn independent() {
         (SendMode::Direct(client_id), 1),
         (SendMode::BroadcastExcept(ClientId::SERVER), 1),
         (SendMode::BroadcastExcept(client_id), 0),
+        (SendMode::BroadcastExceptMultiple(smallvec![client_id]), 0),
+        (SendMode::DirectMultiple(smallvec![client_id]), 1),
     ] {
         server_app.world_mut().send_event(ToClients {
-            mode,
+            mode: mode.clone(),
             event: DummyEvent,
         });
 
@@ -615,6 +751,13 @@ struct DummyEvent;
 #[derive(Deserialize, Event, Serialize)]
 struct EntityEvent(Entity);
 
+#[derive(Deserialize, Event, Serialize)]
+struct FilteredEvent(bool);
+
+fn filter_event(event: &FilteredEvent, _client_id: ClientId) -> bool {
+    event.0
+}
+
 impl MapEntities for EntityEvent {
     fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
         self.0 = entity_mapper.map_entity(self.0);
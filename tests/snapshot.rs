@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn requested() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                allow_snapshot_requests: true,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(true)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Corrupt the client state.
+    let mut component = client_app
+        .world_mut()
+        .query::<&mut BoolComponent>()
+        .single_mut(client_app.world_mut());
+    component.0 = false;
+
+    client_app.world_mut().send_event(ClientSnapshotRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(component.0, "state should be re-sent");
+}
+
+#[test]
+fn cooldown() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                allow_snapshot_requests: true,
+                snapshot_request_cooldown: Duration::MAX,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(true)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    for expected in [true, false] {
+        let mut component = client_app
+            .world_mut()
+            .query::<&mut BoolComponent>()
+            .single_mut(client_app.world_mut());
+        component.0 = false;
+
+        client_app.world_mut().send_event(ClientSnapshotRequest);
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let component = client_app
+            .world_mut()
+            .query::<&BoolComponent>()
+            .single(client_app.world());
+        assert_eq!(
+            component.0, expected,
+            "only the first request should be accepted"
+        );
+    }
+}
+
+#[test]
+fn disabled() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(true)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = client_app
+        .world_mut()
+        .query::<&mut BoolComponent>()
+        .single_mut(client_app.world_mut());
+    component.0 = false;
+
+    client_app.world_mut().send_event(ClientSnapshotRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(!component.0, "requests should be ignored by default");
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
@@ -1,6 +1,9 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
 use bevy_replicon::{
-    client::confirm_history::ConfirmHistory, core::server_entity_map::ServerEntityMap, prelude::*,
+    client::{confirm_history::ConfirmHistory, UpdateMessageApplied},
+    core::{channels::ReplicationChannel, server_entity_map::ServerEntityMap},
+    prelude::*,
+    server::{hot_join_snapshot::HotJoinSnapshot, server_tick::ServerTick},
     test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
@@ -45,6 +48,70 @@ fn empty() {
     );
 }
 
+#[test]
+fn in_fixed_update() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::Manual,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    apply_replication_in_fixed_update: true,
+                    ..Default::default()
+                }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered)
+        .add_systems(
+            PostUpdate,
+            ServerPlugin::increment_tick.before(ServerSet::Send),
+        )
+        .finish();
+    }
+
+    let timestep = client_app.world().resource::<Time<Fixed>>().timestep();
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<Replicated>>();
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "replication shouldn't be applied without a fixed step"
+    );
+    assert!(client_app
+        .world()
+        .resource::<Events<DummyEvent>>()
+        .is_empty());
+
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+    client_app.update();
+
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+    assert_eq!(
+        client_app.world().resource::<Events<DummyEvent>>().len(),
+        1,
+        "events should be received together with replication"
+    );
+}
+
 #[test]
 fn with_component() {
     let mut server_app = App::new();
@@ -223,6 +290,64 @@ fn pre_spawn() {
     );
 }
 
+#[test]
+fn pre_spawn_remapped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    let stale_entity = client_app.world_mut().spawn(Replicated).id();
+    let client_entity = client_app.world_mut().spawn_empty().id();
+
+    // Simulate a stale mapping, for example from a previous session.
+    client_app
+        .world_mut()
+        .resource_mut::<ServerEntityMap>()
+        .insert(server_entity, stale_entity);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity),
+        "mapping should be updated to the new entity"
+    );
+    assert_eq!(
+        entity_map.to_server().get(&client_entity),
+        Some(&server_entity)
+    );
+    assert!(
+        !entity_map.to_server().contains_key(&stale_entity),
+        "stale mapping should be removed"
+    );
+}
+
 #[test]
 fn after_despawn() {
     let mut server_app = App::new();
@@ -257,5 +382,426 @@ fn after_despawn() {
         .single(client_app.world());
 }
 
+#[test]
+fn update_message_applied() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.init_resource::<AppliedMessages>().add_observer(
+        |trigger: Trigger<UpdateMessageApplied>, mut applied: ResMut<AppliedMessages>| {
+            applied.push(*trigger.event());
+        },
+    );
+
+    server_app
+        .world_mut()
+        .spawn_batch([(Replicated, DummyComponent), (Replicated, DummyComponent)]);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let applied = client_app.world().resource::<AppliedMessages>();
+    assert_eq!(applied.len(), 1);
+    let message = applied.first().unwrap();
+    assert_eq!(message.tick, server_tick);
+    assert_eq!(message.entities_changed, 2);
+}
+
+#[test]
+fn replication_order() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.init_resource::<SpawnOrder>().add_observer(
+        |trigger: Trigger<OnAdd, Replicated>, mut order: ResMut<SpawnOrder>| {
+            order.push(trigger.entity());
+        },
+    );
+
+    // Spawn passenger first to make it come first in the archetype.
+    let vehicle = server_app.world_mut().spawn_empty().id();
+    let passenger = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            EntityReplicationOrder {
+                after: Some(vehicle),
+                ..Default::default()
+            },
+        ))
+        .id();
+    server_app
+        .world_mut()
+        .entity_mut(vehicle)
+        .insert((Replicated, EntityReplicationOrder::default()));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let order: Vec<_> = client_app
+        .world()
+        .resource::<SpawnOrder>()
+        .iter()
+        .map(|entity| *entity_map.to_server().get(entity).unwrap())
+        .collect();
+    assert_eq!(order, [vehicle, passenger]);
+}
+
+#[test]
+fn replication_order_cycle() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let first = server_app.world_mut().spawn_empty().id();
+    let second = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            EntityReplicationOrder {
+                before: Some(first),
+                ..Default::default()
+            },
+        ))
+        .id();
+    server_app.world_mut().entity_mut(first).insert((
+        Replicated,
+        EntityReplicationOrder {
+            before: Some(second),
+            ..Default::default()
+        },
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().len(),
+        2,
+        "entities should be replicated even with a cycle"
+    );
+}
+
+#[test]
+fn hot_join_snapshot() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                hot_join_cache: Some(100),
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+
+    server_app.connect_client(&mut client_app1);
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    let snapshot_tick = snapshot.tick().expect("snapshot should be built");
+    assert_eq!(snapshot.len(), 1);
+
+    server_app.connect_client(&mut client_app2);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app2.update();
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    assert_eq!(
+        snapshot.tick(),
+        Some(snapshot_tick),
+        "snapshot shouldn't be rebuilt before the interval"
+    );
+
+    client_app2
+        .world_mut()
+        .query::<(&Replicated, &DummyComponent)>()
+        .single(client_app2.world());
+}
+
+#[test]
+fn lazy_hot_join_snapshot() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                hot_join_cache: Some(1),
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    assert!(
+        server_app
+            .world()
+            .resource::<HotJoinSnapshot>()
+            .tick()
+            .is_none(),
+        "snapshot shouldn't be built without new clients"
+    );
+
+    server_app.connect_client(&mut client_app1);
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    let snapshot_tick = snapshot.tick().expect("snapshot should be built");
+
+    server_app.update();
+    server_app.update();
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    assert_eq!(
+        snapshot.tick(),
+        Some(snapshot_tick),
+        "snapshot shouldn't be rebuilt without new clients"
+    );
+
+    server_app.connect_client(&mut client_app2);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app2.update();
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    assert_ne!(
+        snapshot.tick(),
+        Some(snapshot_tick),
+        "outdated snapshot should be rebuilt for a new client"
+    );
+
+    client_app2
+        .world_mut()
+        .query::<&Replicated>()
+        .single(client_app2.world());
+}
+
+#[test]
+fn hot_join_snapshot_insertion() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                hot_join_cache: Some(100),
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.connect_client(&mut client_app1);
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    let snapshot_tick = snapshot.tick().expect("snapshot should be built");
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(DummyComponent);
+
+    server_app.update();
+    assert!(
+        server_app
+            .world()
+            .resource::<HotJoinSnapshot>()
+            .tick()
+            .is_none(),
+        "snapshot should be invalidated by insertion"
+    );
+
+    server_app.connect_client(&mut client_app2);
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    assert_ne!(snapshot.tick(), Some(snapshot_tick));
+
+    // Drop mutations to ensure that the component is received as insertion.
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let messages: Vec<_> = server.drain_sent().collect();
+    let mut client = client_app2.world_mut().resource_mut::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    for (message_client_id, channel_id, message) in messages {
+        if message_client_id == client_id && channel_id == ReplicationChannel::Updates.into() {
+            client.insert_received(channel_id, message);
+        }
+    }
+    client_app2.update();
+
+    client_app2
+        .world_mut()
+        .query::<(&Replicated, &DummyComponent)>()
+        .single(client_app2.world());
+}
+
+#[test]
+fn stale_hot_join_snapshot() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                hot_join_cache: Some(100),
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+
+    server_app.connect_client(&mut client_app1);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    assert!(
+        server_app
+            .world()
+            .resource::<HotJoinSnapshot>()
+            .tick()
+            .is_none(),
+        "snapshot should be invalidated by despawn"
+    );
+
+    server_app.connect_client(&mut client_app2);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app2.update();
+
+    let snapshot = server_app.world().resource::<HotJoinSnapshot>();
+    assert_eq!(
+        snapshot.len(),
+        1,
+        "snapshot should be rebuilt without the despawned entity"
+    );
+
+    client_app2
+        .world_mut()
+        .query::<(&Replicated, &DummyComponent)>()
+        .single(client_app2.world());
+}
+
+#[test]
+fn entity_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                entity_replication_limit: Some(2),
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, ReplicationPriority(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let queue = replicated_clients.client(client_id).replication_queue();
+    assert_eq!(queue.len(), 1);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.to_client().len(), 2);
+    assert!(
+        entity_map.to_client().contains_key(&server_entity),
+        "entity with higher priority should be replicated first"
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let queue = replicated_clients.client(client_id).replication_queue();
+    assert!(queue.is_empty());
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).len(), 3);
+}
+
+#[derive(Default, Deref, DerefMut, Resource)]
+struct SpawnOrder(Vec<Entity>);
+
+#[derive(Default, Deref, DerefMut, Resource)]
+struct AppliedMessages(Vec<UpdateMessageApplied>);
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Event, Deserialize, Serialize)]
+struct DummyEvent;
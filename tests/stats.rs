@@ -1,5 +1,13 @@
 use bevy::prelude::*;
-use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use bevy_replicon::{
+    core::{
+        channels::ReplicationChannel, connected_clients::ConnectedClients,
+        replication::replicated_clients::MutateSendStats,
+    },
+    prelude::*,
+    server::network_stats_history::NetworkStatsHistory,
+    test_app::ServerTestAppExt,
+};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -63,5 +71,166 @@ fn client_stats() {
     assert_eq!(stats.bytes, 25);
 }
 
+#[test]
+fn mutate_stats() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let client = replicated_clients.iter().next().unwrap();
+    assert_eq!(client.mutate_stats(), MutateSendStats::default());
+
+    server_app
+        .world_mut()
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+
+    server_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let sent_bytes: usize = server
+        .drain_sent()
+        .filter(|&(_, channel_id, _)| channel_id == ReplicationChannel::Mutations.into())
+        .map(|(_, _, message)| message.len())
+        .sum();
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    let client = replicated_clients.iter().next().unwrap();
+    assert_eq!(
+        client.mutate_stats(),
+        MutateSendStats {
+            messages_count: 1,
+            total_bytes: sent_bytes,
+            entities_count: 1,
+            deferred_entities: 0,
+        }
+    );
+}
+
+#[test]
+fn server_stats() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+
+    server_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let sent_bytes: usize = server
+        .drain_sent()
+        .filter(|&(_, channel_id, _)| channel_id == ReplicationChannel::Mutations.into())
+        .map(|(_, _, message)| message.len())
+        .sum();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let client_stats = client_app.world().resource::<ClientReplicationStats>();
+    let stats = server_app.world().resource::<ServerReplicationStats>();
+    assert_eq!(stats.update_messages, 1);
+    assert_eq!(stats.update_bytes, client_stats.bytes);
+    assert_eq!(stats.mutate_messages, 1);
+    assert_eq!(stats.mutate_bytes, sent_bytes);
+    assert_eq!(stats.entities, 2);
+    assert_eq!(stats.client_bytes[&client_id], sent_bytes);
+}
+
+#[test]
+fn stats_history() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                track_stats: true,
+                stats_history_window: 2,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_id = client_app
+        .world()
+        .resource::<RepliconClient>()
+        .id()
+        .unwrap();
+    let mut connected_clients = server_app.world_mut().resource_mut::<ConnectedClients>();
+    connected_clients.iter_mut().next().unwrap().set_rtt(0.05);
+
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+
+    for _ in 0..3 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+    }
+
+    let history = server_app.world().resource::<NetworkStatsHistory>();
+    let client_history = history.client(client_id).unwrap();
+    assert_eq!(client_history.len(), 2);
+    assert!((client_history.average_latency_ms() - 50.0).abs() < 1e-9);
+    assert_eq!(client_history.average_packet_loss(), 0.0);
+    assert_eq!(
+        client_history.peak_bytes_sent(),
+        0,
+        "entity was sent before the window"
+    );
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
@@ -300,5 +300,308 @@ fn whitelist_with_despawn() {
     assert!(!visibility.is_visible(server_entity));
 }
 
+#[test]
+fn hierarchical() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Hierarchical,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_parent = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .with_child((Replicated, DummyComponent))
+        .id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_parent, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated = client_app
+        .world_mut()
+        .query_filtered::<Entity, (With<Replicated>, With<DummyComponent>)>();
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        2,
+        "child should be visible together with its parent"
+    );
+
+    // Reverse visibility.
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(server_parent, false);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "child should be hidden together with its parent"
+    );
+}
+
+#[test]
+fn tag_based() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::TagBased,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            DummyComponent,
+            ReplicationTags([1, 2].into_iter().collect()),
+        ))
+        .id();
+    server_app.world_mut().spawn((
+        Replicated,
+        DummyComponent,
+        ReplicationTags([3].into_iter().collect()),
+    ));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated = client_app
+        .world_mut()
+        .query_filtered::<Entity, (With<Replicated>, With<DummyComponent>)>();
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "entities shouldn't be visible without tags in the filter"
+    );
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .tag_filter_mut()
+        .add_tag(2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        1,
+        "only entity with a matching tag should be visible"
+    );
+
+    server_app
+        .world_mut()
+        .get_mut::<ReplicationTags>(server_entity)
+        .unwrap()
+        .retain(|&mut tag| tag != 2);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "entity should be hidden after losing the tag"
+    );
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(ReplicationTags([2].into_iter().collect()));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<ReplicationTags>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "entity should be hidden after tags removal"
+    );
+}
+
+#[test]
+fn tag_based_paused_despawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::TagBased,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .tag_filter_mut()
+        .add_tag(1);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            DummyComponent,
+            ReplicationTags([1].into_iter().collect()),
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated = client_app
+        .world_mut()
+        .query_filtered::<Entity, (With<Replicated>, With<DummyComponent>)>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(ReplicationPaused);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "despawn should be sent for paused entity"
+    );
+}
+
+#[test]
+fn tag_based_snapshot_despawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::TagBased,
+                allow_snapshot_requests: true,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .finish();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .tag_filter_mut()
+        .add_tag(1);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((
+            Replicated,
+            DummyComponent,
+            ReplicationTags([1].into_iter().collect()),
+        ))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut replicated = client_app
+        .world_mut()
+        .query_filtered::<Entity, (With<Replicated>, With<DummyComponent>)>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+
+    client_app.world_mut().send_event(ClientSnapshotRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "despawn should be sent after snapshot request"
+    );
+}
+
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;